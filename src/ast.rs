@@ -16,15 +16,52 @@ operands. is one of:
 | Selection                | `σ`     | No     | Criteria     |
 | Projection               | `π`     | No     | *Attributes* |
 | Rename                   | `ρ`     | No     | Attributes   |
-| Order                    | `τ`     | No     | Attributes   |
-| Group                    | `γ`     | No     | Attributes   |
+| Order                    | `τ`     | No     | Sort keys    |
+| Group                    | `γ`     | No     | Attributes, *Aggregates* |
 | natural join             | `⨝`     | Yes    | No           |
 | theta join               | `⨝`     | Yes    | Criteria     |
+| left outer join          | `⟕`     | Yes    | *Criteria*   |
+| right outer join         | `⟖`     | Yes    | *Criteria*   |
+| full outer join          | `⟗`     | Yes    | *Criteria*   |
+| left semi-join           | `⋉`     | Yes    | *Criteria*   |
+| right semi-join          | `⋊`     | Yes    | *Criteria*   |
+| anti-join                | `▷`     | Yes    | *Criteria*   |
+| division                 | `÷`     | Yes    | No           |
 
 A projection may also include constant values as well as attributes, and while
 a projection with no attributes is valid it is represented in the AST as a
 separate operator `Relation` with just the relation name.
 
+A scalar expression combines attributes and constants with the arithmetic operators `+`, `-`,
+`×`, `÷`, and `mod`, string concatenation `‖`, the functions `upper`, `lower`, `length`,
+`substr`, `abs`, `round`, and `coalesce`, and `case when … then … else … end`. Either side of
+a comparison may be a scalar expression, as may a projected attribute, which may be named as
+in a rename; for example `π[name, credits × 2i64/double](σ[credits + 1i64 > 3i64]r)`.
+
+Beyond comparisons, criteria may test membership of a list, `a ∈ {1i64, 2i64}`, inclusion in a
+range, `a between 1i64 and 9i64`, or match an SQL pattern, `a like "x%"` or, ignoring case,
+`a ilike "x%"`, where `%` matches any sequence of characters and `_` any single character.
+
+A group may also include aggregates, `count`, `sum`, `avg`, `min`, and `max`,
+each calculated over the tuples of a group and each with an optional result
+name; for example `γ[course, count(*)/students, avg(distinct credits)]r`.
+
+An order is a list of sort keys, each an attribute with a direction, ascending
+`↑` or descending `↓`, optionally a collation, and optionally whether nulls are
+placed first or last; for example `τ[a↓, b↑ collate nocase nulls first]r`.
+
+An outer join is either natural, joining on the attributes common to both operands, or has
+criteria as a theta join does. Any tuple of the left (`⟕`), right (`⟖`), or either (`⟗`) operand
+without a match is padded with null values for the attributes of the other operand.
+
+A semi-join is also either natural or has criteria, and results in only those tuples of the
+left (`⋉`) or right (`⋊`) operand that have a match in the other operand. An anti-join (`▷`)
+results in only those tuples of the left operand that have no match in the right operand.
+
+A division, `r ÷ s`, results in the tuples, over the attributes of `r` not in `s`, that appear
+in `r` combined with every tuple of `s`; the attributes of `s` must be a proper subset of those
+of `r`. For example, `enrolled ÷ courses` results in the students enrolled in every course.
+
 */
 
 use crate::data::Value;
@@ -59,6 +96,9 @@ pub enum RelationalOp {
     Order(Order),
     Group(Group),
     Join(Join),
+    SemiJoin(SemiJoin),
+    AntiJoin(AntiJoin),
+    Division(Division),
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -105,7 +145,15 @@ pub enum Attribute {
 pub enum Term {
     Constant(Value),
     Exists(Attribute),
+    /// True if the attribute's value is null, and never unknown.
+    IsNull(Attribute),
+    /// True if the attribute's value is not null, and never unknown.
+    IsNotNull(Attribute),
     Atom(Atom),
+    /// True if the value is equal to any value in the list.
+    In(ScalarExpression, Vec<ScalarExpression>),
+    /// True if the value lies between the lower and upper bounds, inclusive.
+    Between(ScalarExpression, ScalarExpression, ScalarExpression),
     Negate(Box<Term>),
     And(Box<Term>, Box<Term>),
     Or(Box<Term>, Box<Term>),
@@ -113,9 +161,9 @@ pub enum Term {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Atom {
-    lhs: Attribute,
+    lhs: ScalarExpression,
     op: ComparisonOperator,
-    rhs: ProjectedAttribute,
+    rhs: ScalarExpression,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -128,6 +176,65 @@ pub enum ComparisonOperator {
     GreaterThanOrEqual,
     StringMatch,
     StringNotMatch,
+    /// An SQL pattern match, where `%` matches any sequence of characters and `_` any single
+    /// character; a character is taken literally if preceded by `\`.
+    Like,
+    NotLike,
+    /// As [`Like`](Self::Like), ignoring case.
+    ILike,
+    NotILike,
+}
+
+///
+/// An expression calculated for each tuple, which may be compared in a term or projected as a
+/// computed attribute.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScalarExpression {
+    Attribute(Attribute),
+    Constant(Value),
+    Operation(Box<ScalarExpression>, ScalarOperator, Box<ScalarExpression>),
+    Function(ScalarFunction, Vec<ScalarExpression>),
+    /// Results in the value of the first branch whose condition is true, otherwise in the value
+    /// of the final expression or, if there is none, null.
+    Case(Vec<(Term, ScalarExpression)>, Option<Box<ScalarExpression>>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarOperator {
+    /// Numeric addition, `+`, also adds an interval to a date, time stamp, or interval.
+    Add,
+    /// Numeric subtraction, `-`, also subtracts an interval, or the difference between two
+    /// dates or two time stamps.
+    Subtract,
+    /// Numeric multiplication, `×`.
+    Multiply,
+    /// Numeric division, `÷`.
+    Divide,
+    /// The numeric remainder after division, `mod`.
+    Modulo,
+    /// String concatenation, `‖`.
+    Concatenate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalarFunction {
+    /// `upper(s)`, the string `s` in upper case.
+    Upper,
+    /// `lower(s)`, the string `s` in lower case.
+    Lower,
+    /// `length(s)`, the number of characters in the string, or bytes in the binary value, `s`.
+    Length,
+    /// `substr(s, start, length)`, the characters of `s` from the 1-based `start` position,
+    /// and optionally at most `length` of them.
+    Substring,
+    /// `abs(n)`, the absolute value of the number, or interval, `n`.
+    Absolute,
+    /// `round(n, places)`, the number `n` rounded, half away from zero, to a number of decimal
+    /// places, by default zero.
+    Round,
+    /// `coalesce(a, b, …)`, the first of its arguments that is not null.
+    Coalesce,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -143,6 +250,8 @@ pub enum ProjectedAttribute {
     Index(usize),
     Name(Name),
     Constant(Value),
+    /// A scalar expression, as an attribute with an optional name.
+    Computed(ScalarExpression, Option<Name>),
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -157,24 +266,64 @@ pub struct Rename {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Order {
-    attributes: Vec<Attribute>,
+    keys: Vec<SortKey>,
     rhs: Box<RelationalOp>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SortKey {
+    attribute: Attribute,
+    direction: SortDirection,
+    collation: Option<Name>,
+    nulls: Option<NullOrdering>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NullOrdering {
+    First,
+    Last,
+}
+
 // ------------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Group {
     attributes: Vec<Attribute>,
+    aggregates: Vec<Aggregate>,
     rhs: Box<RelationalOp>,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aggregate {
+    function: AggregateFunction,
+    argument: Option<Attribute>,
+    distinct: bool,
+    name: Option<Name>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Average,
+    Minimum,
+    Maximum,
+}
+
 // ------------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Join {
     Natural(NaturalJoin),
     Theta(ThetaJoin),
+    Outer(OuterJoin),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -190,6 +339,72 @@ pub struct ThetaJoin {
     rhs: Box<RelationalOp>,
 }
 
+///
+/// Denotes an outer join, natural if there are no criteria, between two other relational
+/// operations.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct OuterJoin {
+    lhs: Box<RelationalOp>,
+    kind: OuterJoinKind,
+    criteria: Option<Term>,
+    rhs: Box<RelationalOp>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OuterJoinKind {
+    /// Retains unmatched tuples of the left-hand operand, `⟕`.
+    Left,
+    /// Retains unmatched tuples of the right-hand operand, `⟖`.
+    Right,
+    /// Retains unmatched tuples of both operands, `⟗`.
+    Full,
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Denotes a semi-join, natural if there are no criteria, between two other relational
+/// operations.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct SemiJoin {
+    lhs: Box<RelationalOp>,
+    kind: SemiJoinKind,
+    criteria: Option<Term>,
+    rhs: Box<RelationalOp>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SemiJoinKind {
+    /// Results in the matched tuples of the left-hand operand, `⋉`.
+    Left,
+    /// Results in the matched tuples of the right-hand operand, `⋊`.
+    Right,
+}
+
+///
+/// Denotes an anti-join, natural if there are no criteria, between two other relational
+/// operations.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct AntiJoin {
+    lhs: Box<RelationalOp>,
+    criteria: Option<Term>,
+    rhs: Box<RelationalOp>,
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Denotes the division of one relational operation, the dividend, by another, the divisor.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Division {
+    lhs: Box<RelationalOp>,
+    rhs: Box<RelationalOp>,
+}
+
 // ------------------------------------------------------------------------------------------------
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -359,6 +574,9 @@ impl Format for RelationalOp {
             Self::Order(v) => v.to_formatted_string(fmt),
             Self::Group(v) => v.to_formatted_string(fmt),
             Self::Join(v) => v.to_formatted_string(fmt),
+            Self::SemiJoin(v) => v.to_formatted_string(fmt),
+            Self::AntiJoin(v) => v.to_formatted_string(fmt),
+            Self::Division(v) => v.to_formatted_string(fmt),
         }
     }
 }
@@ -407,6 +625,30 @@ impl From<ThetaJoin> for RelationalOp {
     }
 }
 
+impl From<OuterJoin> for RelationalOp {
+    fn from(v: OuterJoin) -> Self {
+        Self::Join(v.into())
+    }
+}
+
+impl From<SemiJoin> for RelationalOp {
+    fn from(v: SemiJoin) -> Self {
+        Self::SemiJoin(v)
+    }
+}
+
+impl From<AntiJoin> for RelationalOp {
+    fn from(v: AntiJoin) -> Self {
+        Self::AntiJoin(v)
+    }
+}
+
+impl From<Division> for RelationalOp {
+    fn from(v: Division) -> Self {
+        Self::Division(v)
+    }
+}
+
 impl From<Rename> for RelationalOp {
     fn from(v: Rename) -> Self {
         Self::Rename(v)
@@ -567,6 +809,13 @@ impl RelationalOp {
         Self::Order(Order::new(attributes, rhs.into()))
     }
 
+    pub fn sort_by_keys<S>(keys: Vec<SortKey>, rhs: S) -> Self
+    where
+        S: Into<Self>,
+    {
+        Self::Order(Order::with_keys(keys, rhs.into()))
+    }
+
     pub fn is_sort_by(&self) -> bool {
         matches!(self, Self::Order(_))
     }
@@ -587,6 +836,13 @@ impl RelationalOp {
         Self::Group(Group::new(attributes, rhs.into()))
     }
 
+    pub fn aggregate<S>(attributes: Vec<Attribute>, aggregates: Vec<Aggregate>, rhs: S) -> Self
+    where
+        S: Into<Self>,
+    {
+        Self::Group(Group::with_aggregates(attributes, aggregates, rhs.into()))
+    }
+
     pub fn is_group_by(&self) -> bool {
         matches!(self, Self::Group(_))
     }
@@ -617,6 +873,23 @@ impl RelationalOp {
         Join::Theta(ThetaJoin::new(lhs, criteria, rhs)).into()
     }
 
+    pub fn outer_join<S1, S2>(lhs: S1, kind: OuterJoinKind, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        S2: Into<Self>,
+    {
+        Join::Outer(OuterJoin::new(lhs, kind, rhs)).into()
+    }
+
+    pub fn outer_theta_join<S1, T, S2>(lhs: S1, kind: OuterJoinKind, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        T: Into<Term>,
+        S2: Into<Self>,
+    {
+        Join::Outer(OuterJoin::with_criteria(lhs, kind, criteria, rhs)).into()
+    }
+
     pub fn is_join(&self) -> bool {
         matches!(self, Self::Join(_))
     }
@@ -629,12 +902,97 @@ impl RelationalOp {
         matches!(self, Self::Join(Join::Theta(_)))
     }
 
+    pub fn is_outer_join(&self) -> bool {
+        matches!(self, Self::Join(Join::Outer(_)))
+    }
+
     pub fn as_join(&self) -> Option<&Join> {
         match self {
             Self::Join(v) => Some(v),
             _ => None,
         }
     }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn semi_join<S1, S2>(lhs: S1, kind: SemiJoinKind, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        S2: Into<Self>,
+    {
+        SemiJoin::new(lhs, kind, rhs).into()
+    }
+
+    pub fn semi_theta_join<S1, T, S2>(lhs: S1, kind: SemiJoinKind, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        T: Into<Term>,
+        S2: Into<Self>,
+    {
+        SemiJoin::with_criteria(lhs, kind, criteria, rhs).into()
+    }
+
+    pub fn is_semi_join(&self) -> bool {
+        matches!(self, Self::SemiJoin(_))
+    }
+
+    pub fn as_semi_join(&self) -> Option<&SemiJoin> {
+        match self {
+            Self::SemiJoin(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn anti_join<S1, S2>(lhs: S1, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        S2: Into<Self>,
+    {
+        AntiJoin::new(lhs, rhs).into()
+    }
+
+    pub fn anti_theta_join<S1, T, S2>(lhs: S1, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        T: Into<Term>,
+        S2: Into<Self>,
+    {
+        AntiJoin::with_criteria(lhs, criteria, rhs).into()
+    }
+
+    pub fn is_anti_join(&self) -> bool {
+        matches!(self, Self::AntiJoin(_))
+    }
+
+    pub fn as_anti_join(&self) -> Option<&AntiJoin> {
+        match self {
+            Self::AntiJoin(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    pub fn division<S1, S2>(lhs: S1, rhs: S2) -> Self
+    where
+        S1: Into<Self>,
+        S2: Into<Self>,
+    {
+        Division::new(lhs, rhs).into()
+    }
+
+    pub fn is_division(&self) -> bool {
+        matches!(self, Self::Division(_))
+    }
+
+    pub fn as_division(&self) -> Option<&Division> {
+        match self {
+            Self::Division(v) => Some(v),
+            _ => None,
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -645,6 +1003,39 @@ impl Format for Name {
     }
 }
 
+impl Format for Value {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match (self, fmt) {
+            (
+                Self::UnsignedInteger(v),
+                DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii,
+            ) => {
+                format!("{}u64", v)
+            }
+            (Self::Integer(v), DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii) => {
+                format!("{}i64", v)
+            }
+            (Self::Float(v), DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii) => {
+                format!("{:?}", v)
+            }
+            (Self::Decimal(v), DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii) => {
+                format!("{}dec", v)
+            }
+            (
+                Self::Date(_)
+                | Self::Time(_)
+                | Self::Timestamp(_)
+                | Self::TimestampTz(_)
+                | Self::Interval(_),
+                DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii,
+            ) => {
+                format!("{}\"{}\"", self.data_type(), self)
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 impl Format for SetOperation {
@@ -652,7 +1043,7 @@ impl Format for SetOperation {
         format!(
             "{} {} {}",
             to_term_string(&self.lhs, fmt),
-            self.op,
+            self.op.to_formatted_string(fmt),
             to_term_string(&self.rhs, fmt)
         )
     }
@@ -785,7 +1176,7 @@ impl Format for SetOperator {
             (Self::SymmetricDifference, DisplayFormat::ToStringAscii) => "symdifference",
             (Self::SymmetricDifference, DisplayFormat::Latex) => "\\triangle",
             (Self::SymmetricDifference, DisplayFormat::Html) => "&xutri;",
-            (Self::CartesianProduct, DisplayFormat::ToStringUnicode) => "",
+            (Self::CartesianProduct, DisplayFormat::ToStringUnicode) => "×",
             (Self::CartesianProduct, DisplayFormat::ToStringAscii) => "product",
             (Self::CartesianProduct, DisplayFormat::Latex) => "\\times",
             (Self::CartesianProduct, DisplayFormat::Html) => "&times;",
@@ -886,60 +1277,83 @@ impl Attribute {
 impl Format for Term {
     fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
         match (self, fmt) {
-            (Self::Constant(v), _) => v.to_string(),
+            (Self::Constant(v), _) => v.to_formatted_string(fmt),
             (Self::Exists(a), _) => format!("?{}", a.to_formatted_string(fmt)),
+            (Self::IsNull(a), DisplayFormat::Latex) => {
+                format!("{}\\text{{ is null}}", a.to_formatted_string(fmt))
+            }
+            (Self::IsNull(a), _) => format!("{} is null", a.to_formatted_string(fmt)),
+            (Self::IsNotNull(a), DisplayFormat::Latex) => {
+                format!("{}\\text{{ is not null}}", a.to_formatted_string(fmt))
+            }
+            (Self::IsNotNull(a), _) => format!("{} is not null", a.to_formatted_string(fmt)),
             (Self::Atom(a), _) => a.to_formatted_string(fmt),
+            (Self::In(v, list), _) => {
+                let list = list
+                    .iter()
+                    .map(|item| item.to_formatted_string(fmt))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                match fmt {
+                    DisplayFormat::ToStringUnicode => {
+                        format!("{} ∈ {{{}}}", v.to_formatted_string(fmt), list)
+                    }
+                    DisplayFormat::ToStringAscii => {
+                        format!("{} in {{{}}}", v.to_formatted_string(fmt), list)
+                    }
+                    DisplayFormat::Latex => {
+                        format!("{} \\in \\{{{}\\}}", v.to_formatted_string(fmt), list)
+                    }
+                    DisplayFormat::Html => {
+                        format!("{} &isin; {{{}}}", v.to_formatted_string(fmt), list)
+                    }
+                }
+            }
+            (Self::Between(v, low, high), DisplayFormat::Latex) => format!(
+                "{}\\text{{ between }}{}\\text{{ and }}{}",
+                v.to_formatted_string(fmt),
+                low.to_formatted_string(fmt),
+                high.to_formatted_string(fmt)
+            ),
+            (Self::Between(v, low, high), _) => format!(
+                "{} between {} and {}",
+                v.to_formatted_string(fmt),
+                low.to_formatted_string(fmt),
+                high.to_formatted_string(fmt)
+            ),
             (Self::Negate(a), DisplayFormat::ToStringUnicode) => {
-                format!("¬{}", a.to_formatted_string(fmt))
+                format!("¬{}", to_sub_term_string(a, fmt, a.is_binary()))
             }
             (Self::Negate(a), DisplayFormat::ToStringAscii) => {
-                format!("not {}", a.to_formatted_string(fmt))
+                format!("not {}", to_sub_term_string(a, fmt, a.is_binary()))
             }
             (Self::Negate(a), DisplayFormat::Latex) => {
-                format!("\\neg{}", a.to_formatted_string(fmt))
+                format!("\\neg{}", to_sub_term_string(a, fmt, a.is_binary()))
             }
             (Self::Negate(a), DisplayFormat::Html) => {
-                format!("&not;{}", a.to_formatted_string(fmt))
+                format!("&not;{}", to_sub_term_string(a, fmt, a.is_binary()))
             }
-            (Self::And(l, r), DisplayFormat::ToStringUnicode) => format!(
-                "{} ∧ {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::And(l, r), DisplayFormat::ToStringAscii) => format!(
-                "{} and {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::And(l, r), DisplayFormat::Latex) => format!(
-                "{} \\land {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::And(l, r), DisplayFormat::Html) => format!(
-                "{} &and; {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::Or(l, r), DisplayFormat::ToStringUnicode) => format!(
-                "{} ∨ {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::Or(l, r), DisplayFormat::ToStringAscii) => format!(
-                "{} or {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
-            ),
-            (Self::Or(l, r), DisplayFormat::Latex) => format!(
-                "{} \\lor {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
+            (Self::And(l, r), _) => format!(
+                "{} {} {}",
+                to_sub_term_string(l, fmt, l.is_binary()),
+                match fmt {
+                    DisplayFormat::ToStringUnicode => "∧",
+                    DisplayFormat::ToStringAscii => "and",
+                    DisplayFormat::Latex => "\\land",
+                    DisplayFormat::Html => "&and;",
+                },
+                to_sub_term_string(r, fmt, r.is_disjunction())
             ),
-            (Self::Or(l, r), DisplayFormat::Html) => format!(
-                "{} &or; {}",
-                l.to_formatted_string(fmt),
-                r.to_formatted_string(fmt)
+            (Self::Or(l, r), _) => format!(
+                "{} {} {}",
+                to_sub_term_string(l, fmt, l.is_disjunction()),
+                match fmt {
+                    DisplayFormat::ToStringUnicode => "∨",
+                    DisplayFormat::ToStringAscii => "or",
+                    DisplayFormat::Latex => "\\lor",
+                    DisplayFormat::Html => "&or;",
+                },
+                to_sub_term_string(r, fmt, false)
             ),
         }
     }
@@ -992,96 +1406,132 @@ impl Term {
         Self::Exists(attribute.into())
     }
 
-    pub fn equals<A, P>(lhs: A, rhs: P) -> Self
+    pub fn null<A>(attribute: A) -> Self
     where
         A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
     {
-        Self::Atom(Atom::new(lhs.into(), ComparisonOperator::Equal, rhs.into()))
+        Self::IsNull(attribute.into())
     }
 
-    pub fn not_equals<A, P>(lhs: A, rhs: P) -> Self
+    pub fn not_null<A>(attribute: A) -> Self
     where
         A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::NotEqual,
-            rhs.into(),
-        ))
+        Self::IsNotNull(attribute.into())
     }
 
-    pub fn less_than<A, P>(lhs: A, rhs: P) -> Self
+    pub fn equals<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::LessThan,
-            rhs.into(),
-        ))
+        Self::Atom(Atom::equals(lhs, rhs))
     }
 
-    pub fn less_than_or_equal<A, P>(lhs: A, rhs: P) -> Self
+    pub fn not_equals<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::LessThanOrEqual,
-            rhs.into(),
-        ))
+        Self::Atom(Atom::not_equals(lhs, rhs))
     }
 
-    pub fn greater_than<A, P>(lhs: A, rhs: P) -> Self
+    pub fn less_than<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::GreaterThan,
-            rhs.into(),
-        ))
+        Self::Atom(Atom::less_than(lhs, rhs))
     }
 
-    pub fn greater_than_or_equal<A, P>(lhs: A, rhs: P) -> Self
+    pub fn less_than_or_equal<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::GreaterThanOrEqual,
-            rhs.into(),
-        ))
+        Self::Atom(Atom::less_than_or_equal(lhs, rhs))
     }
 
-    pub fn string_match<A, P>(lhs: A, rhs: P) -> Self
+    pub fn greater_than<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::StringMatch,
-            rhs.into(),
-        ))
+        Self::Atom(Atom::greater_than(lhs, rhs))
     }
 
-    pub fn string_not_match<A, P>(lhs: A, rhs: P) -> Self
+    pub fn greater_than_or_equal<L, R>(lhs: L, rhs: R) -> Self
     where
-        A: Into<Attribute>,
-        P: Into<ProjectedAttribute>,
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::greater_than_or_equal(lhs, rhs))
+    }
+
+    pub fn string_match<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::string_match(lhs, rhs))
+    }
+
+    pub fn string_not_match<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::string_not_match(lhs, rhs))
+    }
+
+    pub fn like<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::like(lhs, rhs))
+    }
+
+    pub fn not_like<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::not_like(lhs, rhs))
+    }
+
+    pub fn ilike<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::ilike(lhs, rhs))
+    }
+
+    pub fn not_ilike<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Atom(Atom::not_ilike(lhs, rhs))
+    }
+
+    pub fn in_list<V, I, E>(value: V, list: I) -> Self
+    where
+        V: Into<ScalarExpression>,
+        I: IntoIterator<Item = E>,
+        E: Into<ScalarExpression>,
+    {
+        Self::In(value.into(), list.into_iter().map(Into::into).collect())
+    }
+
+    pub fn between<V, L, H>(value: V, low: L, high: H) -> Self
+    where
+        V: Into<ScalarExpression>,
+        L: Into<ScalarExpression>,
+        H: Into<ScalarExpression>,
     {
-        Self::Atom(Atom::new(
-            lhs.into(),
-            ComparisonOperator::StringNotMatch,
-            rhs.into(),
-        ))
+        Self::Between(value.into(), low.into(), high.into())
     }
 
     pub fn and<T1, T2>(lhs: T1, rhs: T2) -> Self
@@ -1122,41 +1572,85 @@ impl Term {
         }
     }
 
-    pub fn is_atom(&self) -> bool {
-        matches!(self, Self::Atom(_))
+    pub fn is_null_test(&self) -> bool {
+        matches!(self, Self::IsNull(_))
     }
 
-    pub fn as_atom(&self) -> Option<&Atom> {
+    pub fn as_null_test(&self) -> Option<&Attribute> {
         match self {
-            Self::Atom(v) => Some(v),
+            Self::IsNull(v) => Some(v),
             _ => None,
         }
     }
 
-    pub fn is_negated(&self) -> bool {
-        matches!(self, Self::Negate(_))
+    pub fn is_not_null_test(&self) -> bool {
+        matches!(self, Self::IsNotNull(_))
     }
 
-    pub fn as_negated(&self) -> Option<&Term> {
+    pub fn as_not_null_test(&self) -> Option<&Attribute> {
         match self {
-            Self::Negate(v) => Some(v),
+            Self::IsNotNull(v) => Some(v),
             _ => None,
         }
     }
 
-    pub fn is_conjunction(&self) -> bool {
-        matches!(self, Self::And(_, _))
+    pub fn is_atom(&self) -> bool {
+        matches!(self, Self::Atom(_))
     }
 
-    pub fn as_conjunction(&self) -> Option<(&Term, &Term)> {
+    pub fn as_atom(&self) -> Option<&Atom> {
         match self {
-            Self::And(l, r) => Some((l, r)),
+            Self::Atom(v) => Some(v),
             _ => None,
         }
     }
 
-    pub fn is_disjunction(&self) -> bool {
-        matches!(self, Self::Or(_, _))
+    pub fn is_in_list(&self) -> bool {
+        matches!(self, Self::In(_, _))
+    }
+
+    pub fn as_in_list(&self) -> Option<(&ScalarExpression, &Vec<ScalarExpression>)> {
+        match self {
+            Self::In(v, list) => Some((v, list)),
+            _ => None,
+        }
+    }
+
+    pub fn is_between(&self) -> bool {
+        matches!(self, Self::Between(_, _, _))
+    }
+
+    pub fn as_between(&self) -> Option<(&ScalarExpression, &ScalarExpression, &ScalarExpression)> {
+        match self {
+            Self::Between(v, low, high) => Some((v, low, high)),
+            _ => None,
+        }
+    }
+
+    pub fn is_negated(&self) -> bool {
+        matches!(self, Self::Negate(_))
+    }
+
+    pub fn as_negated(&self) -> Option<&Term> {
+        match self {
+            Self::Negate(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn is_conjunction(&self) -> bool {
+        matches!(self, Self::And(_, _))
+    }
+
+    pub fn as_conjunction(&self) -> Option<(&Term, &Term)> {
+        match self {
+            Self::And(l, r) => Some((l, r)),
+            _ => None,
+        }
+    }
+
+    pub fn is_disjunction(&self) -> bool {
+        matches!(self, Self::Or(_, _))
     }
 
     pub fn as_disjunction(&self) -> Option<(&Term, &Term)> {
@@ -1169,59 +1663,145 @@ impl Term {
     pub fn negate(self) -> Self {
         Term::Negate(Box::new(self))
     }
+
+    #[inline]
+    fn is_binary(&self) -> bool {
+        self.is_conjunction() || self.is_disjunction()
+    }
 }
 
 impl Format for Atom {
     fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
-        format!(
-            "{}{}{}",
-            self.lhs.to_formatted_string(fmt),
-            self.op.to_formatted_string(fmt),
-            self.rhs.to_formatted_string(fmt)
-        )
+        if self.lhs.is_simple() && self.rhs.is_simple() && !self.op.is_keyword() {
+            format!(
+                "{}{}{}",
+                self.lhs.to_formatted_string(fmt),
+                self.op.to_formatted_string(fmt),
+                self.rhs.to_formatted_string(fmt)
+            )
+        } else {
+            format!(
+                "{} {} {}",
+                self.lhs.to_formatted_string(fmt),
+                self.op.to_formatted_string(fmt),
+                self.rhs.to_formatted_string(fmt)
+            )
+        }
     }
 }
 
 display_from_format!(Atom);
 
 impl Atom {
-    pub fn new(lhs: Attribute, op: ComparisonOperator, rhs: ProjectedAttribute) -> Self {
-        Self { lhs, op, rhs }
+    pub fn new<L, R>(lhs: L, op: ComparisonOperator, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self {
+            lhs: lhs.into(),
+            op,
+            rhs: rhs.into(),
+        }
     }
 
-    pub fn equals(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn equals<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::Equal, rhs)
     }
 
-    pub fn not_equals(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn not_equals<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::NotEqual, rhs)
     }
 
-    pub fn less_than(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn less_than<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::LessThan, rhs)
     }
 
-    pub fn less_than_or_equal(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn less_than_or_equal<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::LessThanOrEqual, rhs)
     }
 
-    pub fn greater_than(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn greater_than<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::GreaterThan, rhs)
     }
 
-    pub fn greater_than_or_equal(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn greater_than_or_equal<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::GreaterThanOrEqual, rhs)
     }
 
-    pub fn string_match(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn string_match<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::StringMatch, rhs)
     }
 
-    pub fn string_not_match(lhs: Attribute, rhs: ProjectedAttribute) -> Self {
+    pub fn string_not_match<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
         Self::new(lhs, ComparisonOperator::StringNotMatch, rhs)
     }
 
-    pub fn lhs(&self) -> &Attribute {
+    pub fn like<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::new(lhs, ComparisonOperator::Like, rhs)
+    }
+
+    pub fn not_like<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::new(lhs, ComparisonOperator::NotLike, rhs)
+    }
+
+    pub fn ilike<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::new(lhs, ComparisonOperator::ILike, rhs)
+    }
+
+    pub fn not_ilike<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::new(lhs, ComparisonOperator::NotILike, rhs)
+    }
+
+    pub fn lhs(&self) -> &ScalarExpression {
         &self.lhs
     }
 
@@ -1229,9 +1809,25 @@ impl Atom {
         self.op
     }
 
-    pub fn rhs(&self) -> &ProjectedAttribute {
+    pub fn rhs(&self) -> &ScalarExpression {
         &self.rhs
     }
+
+    ///
+    /// Returns this comparison with any constant on the left moved to the right, as in
+    /// `1i64<a` to `a>1i64`; a comparison that cannot be reversed, such as a pattern match, is
+    /// returned unchanged.
+    ///
+    pub fn normalized(&self) -> Self {
+        match self.op.swap_operands() {
+            Some(op) if self.lhs.is_constant() && !self.rhs.is_constant() => Self {
+                lhs: self.rhs.clone(),
+                op,
+                rhs: self.lhs.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
 }
 
 impl Format for ComparisonOperator {
@@ -1269,6 +1865,14 @@ impl Format for ComparisonOperator {
             (Self::StringNotMatch, DisplayFormat::ToStringAscii) => "/~",
             (Self::StringNotMatch, DisplayFormat::Latex) => r"\\nsim",
             (Self::StringNotMatch, DisplayFormat::Html) => "&nsim;",
+            (Self::Like, DisplayFormat::Latex) => "\\text{ like }",
+            (Self::Like, _) => "like",
+            (Self::NotLike, DisplayFormat::Latex) => "\\text{ not like }",
+            (Self::NotLike, _) => "not like",
+            (Self::ILike, DisplayFormat::Latex) => "\\text{ ilike }",
+            (Self::ILike, _) => "ilike",
+            (Self::NotILike, DisplayFormat::Latex) => "\\text{ not ilike }",
+            (Self::NotILike, _) => "not ilike",
         }
         .to_string()
     }
@@ -1284,9 +1888,367 @@ impl ComparisonOperator {
             Self::LessThan => Self::GreaterThanOrEqual,
             Self::LessThanOrEqual => Self::GreaterThan,
             Self::GreaterThan => Self::LessThanOrEqual,
-            Self::GreaterThanOrEqual => Self::GreaterThanOrEqual,
+            Self::GreaterThanOrEqual => Self::LessThan,
             Self::StringMatch => Self::StringNotMatch,
             Self::StringNotMatch => Self::StringMatch,
+            Self::Like => Self::NotLike,
+            Self::NotLike => Self::Like,
+            Self::ILike => Self::NotILike,
+            Self::NotILike => Self::ILike,
+        }
+    }
+
+    ///
+    /// Returns the operator for the same comparison with its operands swapped, if there is one;
+    /// so `a<b` is `b>a`, but a pattern match cannot be reversed.
+    ///
+    pub fn swap_operands(&self) -> Option<Self> {
+        match self {
+            Self::Equal | Self::NotEqual => Some(*self),
+            Self::LessThan => Some(Self::GreaterThan),
+            Self::LessThanOrEqual => Some(Self::GreaterThanOrEqual),
+            Self::GreaterThan => Some(Self::LessThan),
+            Self::GreaterThanOrEqual => Some(Self::LessThanOrEqual),
+            _ => None,
+        }
+    }
+
+    pub fn is_pattern_match(&self) -> bool {
+        matches!(
+            self,
+            Self::StringMatch
+                | Self::StringNotMatch
+                | Self::Like
+                | Self::NotLike
+                | Self::ILike
+                | Self::NotILike
+        )
+    }
+
+    #[inline]
+    fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            Self::Like | Self::NotLike | Self::ILike | Self::NotILike
+        )
+    }
+}
+
+impl Format for ScalarExpression {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match self {
+            Self::Attribute(v) => v.to_formatted_string(fmt),
+            Self::Constant(v) => v.to_formatted_string(fmt),
+            Self::Operation(lhs, op, rhs) => format!(
+                "{} {} {}",
+                to_sub_scalar_string(lhs, fmt, lhs.precedence() < op.precedence()),
+                op.to_formatted_string(fmt),
+                to_sub_scalar_string(rhs, fmt, rhs.precedence() <= op.precedence())
+            ),
+            Self::Function(function, arguments) => format!(
+                "{}({})",
+                function.to_formatted_string(fmt),
+                arguments
+                    .iter()
+                    .map(|argument| argument.to_formatted_string(fmt))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Case(branches, otherwise) => {
+                // a plain space is ignored in LaTeX math mode.
+                let (space, keyword): (&str, fn(&str) -> String) = match fmt {
+                    DisplayFormat::Latex => ("\\ ", |k| format!("\\mathrm{{{}}}", k)),
+                    _ => (" ", |k| k.to_string()),
+                };
+                let mut result = keyword("case");
+                for (condition, value) in branches {
+                    result.push_str(&format!(
+                        "{space}{}{space}{}{space}{}{space}{}",
+                        keyword("when"),
+                        condition.to_formatted_string(fmt),
+                        keyword("then"),
+                        value.to_formatted_string(fmt)
+                    ));
+                }
+                if let Some(otherwise) = otherwise {
+                    result.push_str(&format!(
+                        "{space}{}{space}{}",
+                        keyword("else"),
+                        otherwise.to_formatted_string(fmt)
+                    ));
+                }
+                result.push_str(space);
+                result.push_str(&keyword("end"));
+                result
+            }
+        }
+    }
+}
+
+display_from_format!(ScalarExpression);
+
+impl From<Attribute> for ScalarExpression {
+    fn from(v: Attribute) -> Self {
+        Self::Attribute(v)
+    }
+}
+
+impl From<usize> for ScalarExpression {
+    fn from(v: usize) -> Self {
+        Self::Attribute(v.into())
+    }
+}
+
+impl From<Name> for ScalarExpression {
+    fn from(v: Name) -> Self {
+        Self::Attribute(v.into())
+    }
+}
+
+impl From<Value> for ScalarExpression {
+    fn from(v: Value) -> Self {
+        Self::Constant(v)
+    }
+}
+
+impl From<ProjectedAttribute> for ScalarExpression {
+    fn from(v: ProjectedAttribute) -> Self {
+        match v {
+            ProjectedAttribute::Index(v) => Self::Attribute(Attribute::Index(v)),
+            ProjectedAttribute::Name(v) => Self::Attribute(Attribute::Name(v)),
+            ProjectedAttribute::Constant(v) => Self::Constant(v),
+            ProjectedAttribute::Computed(v, _) => v,
+        }
+    }
+}
+
+impl ScalarExpression {
+    pub fn attribute<A>(attribute: A) -> Self
+    where
+        A: Into<Attribute>,
+    {
+        Self::Attribute(attribute.into())
+    }
+
+    pub fn constant<V>(value: V) -> Self
+    where
+        V: Into<Value>,
+    {
+        Self::Constant(value.into())
+    }
+
+    pub fn operation<L, R>(lhs: L, op: ScalarOperator, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::Operation(Box::new(lhs.into()), op, Box::new(rhs.into()))
+    }
+
+    pub fn add<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Add, rhs)
+    }
+
+    pub fn subtract<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Subtract, rhs)
+    }
+
+    pub fn multiply<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Multiply, rhs)
+    }
+
+    pub fn divide<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Divide, rhs)
+    }
+
+    pub fn modulo<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Modulo, rhs)
+    }
+
+    pub fn concatenate<L, R>(lhs: L, rhs: R) -> Self
+    where
+        L: Into<ScalarExpression>,
+        R: Into<ScalarExpression>,
+    {
+        Self::operation(lhs, ScalarOperator::Concatenate, rhs)
+    }
+
+    ///
+    /// Construct a call to `function`; this does not check the number of arguments, see
+    /// [`ScalarFunction::accepts`].
+    ///
+    pub fn function(function: ScalarFunction, arguments: Vec<ScalarExpression>) -> Self {
+        Self::Function(function, arguments)
+    }
+
+    pub fn case(
+        branches: Vec<(Term, ScalarExpression)>,
+        otherwise: Option<ScalarExpression>,
+    ) -> Self {
+        assert!(!branches.is_empty());
+
+        Self::Case(branches, otherwise.map(Box::new))
+    }
+
+    pub fn is_attribute(&self) -> bool {
+        matches!(self, Self::Attribute(_))
+    }
+
+    pub fn as_attribute(&self) -> Option<&Attribute> {
+        match self {
+            Self::Attribute(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        matches!(self, Self::Constant(_))
+    }
+
+    pub fn as_constant(&self) -> Option<&Value> {
+        match self {
+            Self::Constant(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn is_operation(&self) -> bool {
+        matches!(self, Self::Operation(_, _, _))
+    }
+
+    pub fn as_operation(&self) -> Option<(&ScalarExpression, ScalarOperator, &ScalarExpression)> {
+        match self {
+            Self::Operation(l, op, r) => Some((l, *op, r)),
+            _ => None,
+        }
+    }
+
+    pub fn is_function(&self) -> bool {
+        matches!(self, Self::Function(_, _))
+    }
+
+    pub fn as_function(&self) -> Option<(ScalarFunction, &[ScalarExpression])> {
+        match self {
+            Self::Function(function, arguments) => Some((*function, arguments)),
+            _ => None,
+        }
+    }
+
+    pub fn is_case(&self) -> bool {
+        matches!(self, Self::Case(_, _))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn as_case(&self) -> Option<(&[(Term, ScalarExpression)], Option<&ScalarExpression>)> {
+        match self {
+            Self::Case(branches, otherwise) => Some((branches, otherwise.as_deref())),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns `true` if this is simply an attribute or a constant value.
+    ///
+    pub fn is_simple(&self) -> bool {
+        self.is_attribute() || self.is_constant()
+    }
+
+    #[inline]
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Operation(_, op, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl Format for ScalarOperator {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match (self, fmt) {
+            (Self::Add, _) => "+",
+            (Self::Subtract, DisplayFormat::Html) => "&minus;",
+            (Self::Subtract, _) => "-",
+            (Self::Multiply, DisplayFormat::ToStringUnicode) => "×",
+            (Self::Multiply, DisplayFormat::ToStringAscii) => "*",
+            (Self::Multiply, DisplayFormat::Latex) => "\\times",
+            (Self::Multiply, DisplayFormat::Html) => "&times;",
+            (Self::Divide, DisplayFormat::ToStringUnicode) => "÷",
+            (Self::Divide, DisplayFormat::ToStringAscii) => "div",
+            (Self::Divide, DisplayFormat::Latex) => "\\div",
+            (Self::Divide, DisplayFormat::Html) => "&divide;",
+            (Self::Modulo, DisplayFormat::Latex) => "\\bmod",
+            (Self::Modulo, _) => "mod",
+            (Self::Concatenate, DisplayFormat::ToStringUnicode) => "‖",
+            (Self::Concatenate, DisplayFormat::ToStringAscii) => "||",
+            (Self::Concatenate, DisplayFormat::Latex) => "\\mathbin{\\|}",
+            (Self::Concatenate, DisplayFormat::Html) => "&Vert;",
+        }
+        .to_string()
+    }
+}
+
+display_from_format!(ScalarOperator);
+
+impl ScalarOperator {
+    #[inline]
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Subtract | Self::Concatenate => 1,
+            Self::Multiply | Self::Divide | Self::Modulo => 2,
+        }
+    }
+}
+
+impl Format for ScalarFunction {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        let name = match self {
+            Self::Upper => "upper",
+            Self::Lower => "lower",
+            Self::Length => "length",
+            Self::Substring => "substr",
+            Self::Absolute => "abs",
+            Self::Round => "round",
+            Self::Coalesce => "coalesce",
+        };
+        match fmt {
+            DisplayFormat::Latex => format!("\\mathrm{{{}}}", name),
+            _ => name.to_string(),
+        }
+    }
+}
+
+display_from_format!(ScalarFunction);
+
+impl ScalarFunction {
+    ///
+    /// Returns `true` if this function may be called with `count` arguments.
+    ///
+    pub fn accepts(&self, count: usize) -> bool {
+        match self {
+            Self::Upper | Self::Lower | Self::Length | Self::Absolute => count == 1,
+            Self::Substring => (2..=3).contains(&count),
+            Self::Round => (1..=2).contains(&count),
+            Self::Coalesce => count >= 1,
         }
     }
 }
@@ -1344,7 +2306,11 @@ impl Format for ProjectedAttribute {
         match self {
             ProjectedAttribute::Index(v) => v.to_string(),
             ProjectedAttribute::Name(v) => v.to_formatted_string(fmt),
-            ProjectedAttribute::Constant(v) => v.to_string(),
+            ProjectedAttribute::Constant(v) => v.to_formatted_string(fmt),
+            ProjectedAttribute::Computed(v, None) => v.to_formatted_string(fmt),
+            ProjectedAttribute::Computed(v, Some(name)) => {
+                format!("{}/{}", v.to_formatted_string(fmt), name)
+            }
         }
     }
 }
@@ -1369,6 +2335,25 @@ impl From<Value> for ProjectedAttribute {
     }
 }
 
+impl From<Attribute> for ProjectedAttribute {
+    fn from(v: Attribute) -> Self {
+        match v {
+            Attribute::Index(v) => Self::Index(v),
+            Attribute::Name(v) => Self::Name(v),
+        }
+    }
+}
+
+impl From<ScalarExpression> for ProjectedAttribute {
+    fn from(v: ScalarExpression) -> Self {
+        match v {
+            ScalarExpression::Attribute(v) => v.into(),
+            ScalarExpression::Constant(v) => Self::Constant(v),
+            v => Self::Computed(v, None),
+        }
+    }
+}
+
 impl ProjectedAttribute {
     pub fn is_index(&self) -> bool {
         matches!(self, Self::Index(_))
@@ -1402,24 +2387,46 @@ impl ProjectedAttribute {
             _ => None,
         }
     }
+
+    ///
+    /// Construct a computed attribute, the value of `expression` in each tuple, with an optional
+    /// name; without a name the attribute is named for its position, as is a constant.
+    ///
+    pub fn computed<E>(expression: E, name: Option<Name>) -> Self
+    where
+        E: Into<ScalarExpression>,
+    {
+        Self::Computed(expression.into(), name)
+    }
+
+    pub fn is_computed(&self) -> bool {
+        matches!(self, Self::Computed(_, _))
+    }
+
+    pub fn as_computed(&self) -> Option<(&ScalarExpression, Option<&Name>)> {
+        match self {
+            Self::Computed(v, name) => Some((v, name.as_ref())),
+            _ => None,
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 
 impl Format for Order {
     fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
-        let attributes = self
-            .attributes
+        let keys = self
+            .keys
             .iter()
-            .map(|attribute| attribute.to_formatted_string(fmt))
+            .map(|key| key.to_formatted_string(fmt))
             .collect::<Vec<String>>()
             .join(", ");
         let rhs = to_term_string(&self.rhs, fmt);
         match fmt {
-            DisplayFormat::ToStringUnicode => format!("τ[{}]{}", attributes, rhs),
-            DisplayFormat::ToStringAscii => format!("sort[{}]{}", attributes, rhs),
-            DisplayFormat::Latex => format!("\\tau_{{{}}}{}", attributes, rhs),
-            DisplayFormat::Html => format!("&tau;<sub>{}</sub>{}", attributes, rhs),
+            DisplayFormat::ToStringUnicode => format!("τ[{}]{}", keys, rhs),
+            DisplayFormat::ToStringAscii => format!("sort[{}]{}", keys, rhs),
+            DisplayFormat::Latex => format!("\\tau_{{{}}}{}", keys, rhs),
+            DisplayFormat::Html => format!("&tau;<sub>{}</sub>{}", keys, rhs),
         }
     }
 }
@@ -1427,24 +2434,38 @@ impl Format for Order {
 display_from_format!(Order);
 
 impl Order {
+    ///
+    /// Construct a new order, sorting by each of `attributes` ascending.
+    ///
     pub fn new<S>(attributes: Vec<Attribute>, from: S) -> Self
     where
         S: Into<RelationalOp>,
     {
-        assert!(!attributes.is_empty());
+        Self::with_keys(attributes.into_iter().map(SortKey::from).collect(), from)
+    }
+
+    pub fn with_keys<S>(keys: Vec<SortKey>, from: S) -> Self
+    where
+        S: Into<RelationalOp>,
+    {
+        assert!(!keys.is_empty());
 
         Self {
-            attributes,
+            keys,
             rhs: Box::new(from.into()),
         }
     }
 
     pub fn count(&self) -> usize {
-        self.attributes.len()
+        self.keys.len()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &SortKey> {
+        self.keys.iter()
     }
 
     pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
-        self.attributes.iter()
+        self.keys.iter().map(SortKey::attribute)
     }
 
     pub fn rhs(&self) -> &RelationalOp {
@@ -1452,6 +2473,156 @@ impl Order {
     }
 }
 
+impl Format for SortKey {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        let mut result = format!(
+            "{}{}",
+            self.attribute.to_formatted_string(fmt),
+            self.direction.to_formatted_string(fmt)
+        );
+        let space = if fmt == DisplayFormat::Latex {
+            "\\ "
+        } else {
+            " "
+        };
+        let keyword = |k: &str| {
+            if fmt == DisplayFormat::Latex {
+                format!("\\mathrm{{{}}}", k)
+            } else {
+                k.to_string()
+            }
+        };
+        if let Some(collation) = &self.collation {
+            result.push_str(&format!(
+                "{}{}{}{}",
+                space,
+                keyword("collate"),
+                space,
+                collation.to_formatted_string(fmt)
+            ));
+        }
+        if let Some(nulls) = &self.nulls {
+            result.push_str(&format!(
+                "{}{}{}{}",
+                space,
+                keyword("nulls"),
+                space,
+                keyword(match nulls {
+                    NullOrdering::First => "first",
+                    NullOrdering::Last => "last",
+                })
+            ));
+        }
+        result
+    }
+}
+
+display_from_format!(SortKey);
+
+impl From<Attribute> for SortKey {
+    fn from(v: Attribute) -> Self {
+        Self::ascending(v)
+    }
+}
+
+impl From<Name> for SortKey {
+    fn from(v: Name) -> Self {
+        Self::ascending(v.into())
+    }
+}
+
+impl From<usize> for SortKey {
+    fn from(v: usize) -> Self {
+        Self::ascending(v.into())
+    }
+}
+
+impl SortKey {
+    pub fn new(attribute: Attribute, direction: SortDirection) -> Self {
+        Self {
+            attribute,
+            direction,
+            collation: None,
+            nulls: None,
+        }
+    }
+
+    pub fn ascending(attribute: Attribute) -> Self {
+        Self::new(attribute, SortDirection::Ascending)
+    }
+
+    pub fn descending(attribute: Attribute) -> Self {
+        Self::new(attribute, SortDirection::Descending)
+    }
+
+    pub fn with_collation(self, collation: Name) -> Self {
+        Self {
+            collation: Some(collation),
+            ..self
+        }
+    }
+
+    pub fn with_nulls(self, nulls: NullOrdering) -> Self {
+        Self {
+            nulls: Some(nulls),
+            ..self
+        }
+    }
+
+    pub fn attribute(&self) -> &Attribute {
+        &self.attribute
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.direction
+    }
+
+    pub fn is_descending(&self) -> bool {
+        self.direction == SortDirection::Descending
+    }
+
+    pub fn collation(&self) -> Option<&Name> {
+        self.collation.as_ref()
+    }
+
+    ///
+    /// Returns the null ordering given for this key, if any.
+    ///
+    pub fn nulls(&self) -> Option<NullOrdering> {
+        self.nulls
+    }
+
+    ///
+    /// Returns `true` if nulls are placed before all other values; where no null ordering is
+    /// given nulls are placed as if larger than any other value, so last when ascending and
+    /// first when descending.
+    ///
+    pub fn is_nulls_first(&self) -> bool {
+        match self.nulls {
+            Some(nulls) => nulls == NullOrdering::First,
+            None => self.is_descending(),
+        }
+    }
+}
+
+impl Format for SortDirection {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match (self, fmt) {
+            (Self::Ascending, DisplayFormat::ToStringUnicode) => "↑",
+            (Self::Ascending, DisplayFormat::ToStringAscii) => " asc",
+            (Self::Ascending, DisplayFormat::Latex) => "\\uparrow",
+            (Self::Ascending, DisplayFormat::Html) => "&uarr;",
+            (Self::Descending, DisplayFormat::ToStringUnicode) => "↓",
+            (Self::Descending, DisplayFormat::ToStringAscii) => " desc",
+            (Self::Descending, DisplayFormat::Latex) => "\\downarrow",
+            (Self::Descending, DisplayFormat::Html) => "&darr;",
+        }
+        .to_string()
+    }
+}
+
+display_from_format!(SortDirection);
+
 // ------------------------------------------------------------------------------------------------
 
 impl Format for Group {
@@ -1460,6 +2631,11 @@ impl Format for Group {
             .attributes
             .iter()
             .map(|attribute| attribute.to_formatted_string(fmt))
+            .chain(
+                self.aggregates
+                    .iter()
+                    .map(|aggregate| aggregate.to_formatted_string(fmt)),
+            )
             .collect::<Vec<String>>()
             .join(", ");
         let rhs = to_term_string(&self.rhs, fmt);
@@ -1479,43 +2655,191 @@ impl Group {
     where
         S: Into<RelationalOp>,
     {
-        assert!(!attributes.is_empty());
+        Self::with_aggregates(attributes, Default::default(), from)
+    }
+
+    ///
+    /// Construct a new group, the resulting relation has the grouping attributes followed by
+    /// one attribute per aggregate. Either list may be empty, but not both; with no grouping
+    /// attributes the whole relation is a single group.
+    ///
+    pub fn with_aggregates<S>(
+        attributes: Vec<Attribute>,
+        aggregates: Vec<Aggregate>,
+        from: S,
+    ) -> Self
+    where
+        S: Into<RelationalOp>,
+    {
+        assert!(!attributes.is_empty() || !aggregates.is_empty());
 
         Self {
             attributes,
+            aggregates,
             rhs: Box::new(from.into()),
         }
     }
 
-    pub fn count(&self) -> usize {
-        self.attributes.len()
+    pub fn count(&self) -> usize {
+        self.attributes.len()
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.attributes.iter()
+    }
+
+    pub fn has_aggregates(&self) -> bool {
+        !self.aggregates.is_empty()
+    }
+
+    pub fn aggregates(&self) -> impl Iterator<Item = &Aggregate> {
+        self.aggregates.iter()
+    }
+
+    pub fn rhs(&self) -> &RelationalOp {
+        &self.rhs
+    }
+}
+
+impl Format for Aggregate {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        let function = self.function.to_formatted_string(fmt);
+        let argument = match &self.argument {
+            None => "*".to_string(),
+            Some(argument) => argument.to_formatted_string(fmt),
+        };
+        let argument = match (self.distinct, fmt) {
+            (false, _) => argument,
+            (true, DisplayFormat::Latex) => format!("\\mathrm{{distinct}}\\ {}", argument),
+            (true, _) => format!("distinct {}", argument),
+        };
+        match &self.name {
+            None => format!("{}({})", function, argument),
+            Some(name) => format!("{}({})/{}", function, argument, name),
+        }
+    }
+}
+
+display_from_format!(Aggregate);
+
+impl Aggregate {
+    ///
+    /// Construct the aggregate `count(*)`, the number of tuples in each group.
+    ///
+    pub fn count_all() -> Self {
+        Self {
+            function: AggregateFunction::Count,
+            argument: None,
+            distinct: false,
+            name: None,
+        }
+    }
+
+    pub fn new(function: AggregateFunction, argument: Attribute) -> Self {
+        Self {
+            function,
+            argument: Some(argument),
+            distinct: false,
+            name: None,
+        }
+    }
+
+    ///
+    /// Construct an aggregate calculated over only the distinct values of `argument` in each
+    /// group.
+    ///
+    pub fn new_distinct(function: AggregateFunction, argument: Attribute) -> Self {
+        Self {
+            distinct: true,
+            ..Self::new(function, argument)
+        }
+    }
+
+    pub fn count(argument: Attribute) -> Self {
+        Self::new(AggregateFunction::Count, argument)
+    }
+
+    pub fn sum(argument: Attribute) -> Self {
+        Self::new(AggregateFunction::Sum, argument)
+    }
+
+    pub fn average(argument: Attribute) -> Self {
+        Self::new(AggregateFunction::Average, argument)
+    }
+
+    pub fn minimum(argument: Attribute) -> Self {
+        Self::new(AggregateFunction::Minimum, argument)
+    }
+
+    pub fn maximum(argument: Attribute) -> Self {
+        Self::new(AggregateFunction::Maximum, argument)
+    }
+
+    pub fn named(self, name: Name) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    pub fn function(&self) -> AggregateFunction {
+        self.function
+    }
+
+    pub fn is_count_all(&self) -> bool {
+        self.argument.is_none()
+    }
+
+    pub fn argument(&self) -> Option<&Attribute> {
+        self.argument.as_ref()
+    }
+
+    pub fn is_distinct(&self) -> bool {
+        self.distinct
     }
 
-    pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
-        self.attributes.iter()
+    pub fn name(&self) -> Option<&Name> {
+        self.name.as_ref()
     }
+}
 
-    pub fn rhs(&self) -> &RelationalOp {
-        &self.rhs
+impl Format for AggregateFunction {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        let name = match self {
+            Self::Count => "count",
+            Self::Sum => "sum",
+            Self::Average => "avg",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+        };
+        match fmt {
+            DisplayFormat::Latex => format!("\\mathrm{{{}}}", name),
+            _ => name.to_string(),
+        }
     }
 }
 
+display_from_format!(AggregateFunction);
+
 // ------------------------------------------------------------------------------------------------
 
 impl Format for Rename {
     fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
-        let renames = if self.renames.keys().all(Attribute::is_index) {
-            (0..self.renames.len())
-                .map(|i| self.renames.get(&Attribute::Index(i)).unwrap().to_string())
-                .collect::<Vec<String>>()
-                .join(", ")
-        } else {
-            self.renames
-                .iter()
-                .map(|(left, right)| format!("{}/{}", left, right))
-                .collect::<Vec<String>>()
-                .join(", ")
-        };
+        let renames =
+            if (0..self.renames.len()).all(|i| self.renames.contains_key(&Attribute::Index(i))) {
+                (0..self.renames.len())
+                    .map(|i| self.renames.get(&Attribute::Index(i)).unwrap().to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            } else {
+                let mut renames = self.renames.iter().collect::<Vec<(&Attribute, &Name)>>();
+                renames.sort();
+                renames
+                    .into_iter()
+                    .map(|(left, right)| format!("{}/{}", left.to_formatted_string(fmt), right))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
         let rhs = to_term_string(&self.rhs, fmt);
         match fmt {
             DisplayFormat::ToStringUnicode => format!("ρ[{}]{}", renames, rhs),
@@ -1584,10 +2908,11 @@ impl Rename {
 // ------------------------------------------------------------------------------------------------
 
 impl Format for Join {
-    fn to_formatted_string(&self, _fmt: DisplayFormat) -> String {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
         match self {
-            Self::Natural(v) => v.to_string(),
-            Self::Theta(v) => v.to_string(),
+            Self::Natural(v) => v.to_formatted_string(fmt),
+            Self::Theta(v) => v.to_formatted_string(fmt),
+            Self::Outer(v) => v.to_formatted_string(fmt),
         }
     }
 }
@@ -1606,6 +2931,12 @@ impl From<ThetaJoin> for Join {
     }
 }
 
+impl From<OuterJoin> for Join {
+    fn from(v: OuterJoin) -> Self {
+        Self::Outer(v)
+    }
+}
+
 impl Join {
     pub fn natural<S1, S2>(lhs: S1, rhs: S2) -> Self
     where
@@ -1645,6 +2976,25 @@ impl Join {
             _ => None,
         }
     }
+
+    pub fn outer<S1, S2>(lhs: S1, kind: OuterJoinKind, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        S2: Into<RelationalOp>,
+    {
+        Self::Outer(OuterJoin::new(lhs.into(), kind, rhs.into()))
+    }
+
+    pub fn is_outer(&self) -> bool {
+        matches!(self, Self::Outer(_))
+    }
+
+    pub fn as_outer(&self) -> Option<&OuterJoin> {
+        match self {
+            Self::Outer(v) => Some(v),
+            _ => None,
+        }
+    }
 }
 
 impl Format for NaturalJoin {
@@ -1742,6 +3092,291 @@ impl ThetaJoin {
     }
 }
 
+impl Format for OuterJoin {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        format!(
+            "{} {}{} {}",
+            to_term_string(&self.lhs, fmt),
+            self.kind.to_formatted_string(fmt),
+            to_criteria_string(self.criteria.as_ref(), fmt),
+            to_term_string(&self.rhs, fmt)
+        )
+    }
+}
+
+display_from_format!(OuterJoin);
+
+impl OuterJoin {
+    pub fn new<S1, S2>(lhs: S1, kind: OuterJoinKind, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            kind,
+            criteria: None,
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn with_criteria<S1, T, S2>(lhs: S1, kind: OuterJoinKind, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        T: Into<Term>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            kind,
+            criteria: Some(criteria.into()),
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn lhs(&self) -> &RelationalOp {
+        &self.lhs
+    }
+
+    pub fn kind(&self) -> OuterJoinKind {
+        self.kind
+    }
+
+    pub fn is_natural(&self) -> bool {
+        self.criteria.is_none()
+    }
+
+    pub fn criteria(&self) -> Option<&Term> {
+        self.criteria.as_ref()
+    }
+
+    pub fn rhs(&self) -> &RelationalOp {
+        &self.rhs
+    }
+}
+
+impl Format for OuterJoinKind {
+    // there are no standard LaTeX symbols for the outer joins, so each is built from `\bowtie`
+    // with a bracket on the side, or sides, whose unmatched tuples are retained.
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match (self, fmt) {
+            (Self::Left, DisplayFormat::ToStringUnicode | DisplayFormat::Html) => "⟕",
+            (Self::Left, DisplayFormat::ToStringAscii) => "leftjoin",
+            (Self::Left, DisplayFormat::Latex) => "\\mathbin{]\\mkern-5.8mu\\bowtie}",
+            (Self::Right, DisplayFormat::ToStringUnicode | DisplayFormat::Html) => "⟖",
+            (Self::Right, DisplayFormat::ToStringAscii) => "rightjoin",
+            (Self::Right, DisplayFormat::Latex) => "\\mathbin{\\bowtie\\mkern-5.8mu[}",
+            (Self::Full, DisplayFormat::ToStringUnicode | DisplayFormat::Html) => "⟗",
+            (Self::Full, DisplayFormat::ToStringAscii) => "fulljoin",
+            (Self::Full, DisplayFormat::Latex) => "\\mathbin{]\\mkern-5.8mu\\bowtie\\mkern-5.8mu[}",
+        }
+        .to_string()
+    }
+}
+
+display_from_format!(OuterJoinKind);
+
+impl OuterJoinKind {
+    /// Returns `true` if unmatched tuples of the left-hand operand are retained.
+    pub fn retains_lhs(&self) -> bool {
+        matches!(self, Self::Left | Self::Full)
+    }
+
+    /// Returns `true` if unmatched tuples of the right-hand operand are retained.
+    pub fn retains_rhs(&self) -> bool {
+        matches!(self, Self::Right | Self::Full)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Format for SemiJoin {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        format!(
+            "{} {}{} {}",
+            to_term_string(&self.lhs, fmt),
+            self.kind.to_formatted_string(fmt),
+            to_criteria_string(self.criteria.as_ref(), fmt),
+            to_term_string(&self.rhs, fmt)
+        )
+    }
+}
+
+display_from_format!(SemiJoin);
+
+impl SemiJoin {
+    pub fn new<S1, S2>(lhs: S1, kind: SemiJoinKind, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            kind,
+            criteria: None,
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn with_criteria<S1, T, S2>(lhs: S1, kind: SemiJoinKind, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        T: Into<Term>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            kind,
+            criteria: Some(criteria.into()),
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn lhs(&self) -> &RelationalOp {
+        &self.lhs
+    }
+
+    pub fn kind(&self) -> SemiJoinKind {
+        self.kind
+    }
+
+    pub fn is_natural(&self) -> bool {
+        self.criteria.is_none()
+    }
+
+    pub fn criteria(&self) -> Option<&Term> {
+        self.criteria.as_ref()
+    }
+
+    pub fn rhs(&self) -> &RelationalOp {
+        &self.rhs
+    }
+}
+
+impl Format for SemiJoinKind {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        match (self, fmt) {
+            (Self::Left, DisplayFormat::ToStringUnicode) => "⋉",
+            (Self::Left, DisplayFormat::ToStringAscii) => "leftsemijoin",
+            (Self::Left, DisplayFormat::Latex) => "\\ltimes",
+            (Self::Left, DisplayFormat::Html) => "&ltimes;",
+            (Self::Right, DisplayFormat::ToStringUnicode) => "⋊",
+            (Self::Right, DisplayFormat::ToStringAscii) => "rightsemijoin",
+            (Self::Right, DisplayFormat::Latex) => "\\rtimes",
+            (Self::Right, DisplayFormat::Html) => "&rtimes;",
+        }
+        .to_string()
+    }
+}
+
+display_from_format!(SemiJoinKind);
+
+// ------------------------------------------------------------------------------------------------
+
+impl Format for AntiJoin {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        format!(
+            "{} {}{} {}",
+            to_term_string(&self.lhs, fmt),
+            match fmt {
+                DisplayFormat::ToStringUnicode | DisplayFormat::Html => "▷",
+                DisplayFormat::ToStringAscii => "antijoin",
+                DisplayFormat::Latex => "\\triangleright",
+            },
+            to_criteria_string(self.criteria.as_ref(), fmt),
+            to_term_string(&self.rhs, fmt)
+        )
+    }
+}
+
+display_from_format!(AntiJoin);
+
+impl AntiJoin {
+    pub fn new<S1, S2>(lhs: S1, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            criteria: None,
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn with_criteria<S1, T, S2>(lhs: S1, criteria: T, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        T: Into<Term>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            criteria: Some(criteria.into()),
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    pub fn lhs(&self) -> &RelationalOp {
+        &self.lhs
+    }
+
+    pub fn is_natural(&self) -> bool {
+        self.criteria.is_none()
+    }
+
+    pub fn criteria(&self) -> Option<&Term> {
+        self.criteria.as_ref()
+    }
+
+    pub fn rhs(&self) -> &RelationalOp {
+        &self.rhs
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Format for Division {
+    fn to_formatted_string(&self, fmt: DisplayFormat) -> String {
+        format!(
+            "{} {} {}",
+            to_term_string(&self.lhs, fmt),
+            match fmt {
+                DisplayFormat::ToStringUnicode => "÷",
+                DisplayFormat::ToStringAscii => "divide",
+                DisplayFormat::Latex => "\\div",
+                DisplayFormat::Html => "&divide;",
+            },
+            to_term_string(&self.rhs, fmt)
+        )
+    }
+}
+
+display_from_format!(Division);
+
+impl Division {
+    pub fn new<S1, S2>(lhs: S1, rhs: S2) -> Self
+    where
+        S1: Into<RelationalOp>,
+        S2: Into<RelationalOp>,
+    {
+        Self {
+            lhs: Box::new(lhs.into()),
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    /// Returns the dividend.
+    pub fn lhs(&self) -> &RelationalOp {
+        &self.lhs
+    }
+
+    /// Returns the divisor.
+    pub fn rhs(&self) -> &RelationalOp {
+        &self.rhs
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
@@ -1749,10 +3384,44 @@ impl ThetaJoin {
 #[inline]
 fn to_term_string(r: &RelationalOp, fmt: DisplayFormat) -> String {
     if r.is_relation() {
-        r.to_string()
+        r.to_formatted_string(fmt)
     } else if fmt == DisplayFormat::Latex {
-        format!("\\({}\\)", r)
+        format!("\\({}\\)", r.to_formatted_string(fmt))
+    } else {
+        format!("({})", r.to_formatted_string(fmt))
+    }
+}
+
+#[inline]
+fn to_criteria_string(criteria: Option<&Term>, fmt: DisplayFormat) -> String {
+    match (criteria, fmt) {
+        (None, _) => String::new(),
+        (Some(criteria), DisplayFormat::ToStringUnicode | DisplayFormat::ToStringAscii) => {
+            format!("[{}]", criteria.to_formatted_string(fmt))
+        }
+        (Some(criteria), DisplayFormat::Latex) => {
+            format!("_{{{}}}", criteria.to_formatted_string(fmt))
+        }
+        (Some(criteria), DisplayFormat::Html) => {
+            format!("<sub>{}</sub>", criteria.to_formatted_string(fmt))
+        }
+    }
+}
+
+#[inline]
+fn to_sub_scalar_string(e: &ScalarExpression, fmt: DisplayFormat, parenthesize: bool) -> String {
+    if parenthesize {
+        format!("({})", e.to_formatted_string(fmt))
+    } else {
+        e.to_formatted_string(fmt)
+    }
+}
+
+#[inline]
+fn to_sub_term_string(t: &Term, fmt: DisplayFormat, parenthesize: bool) -> String {
+    if parenthesize {
+        format!("({})", t.to_formatted_string(fmt))
     } else {
-        format!("({})", r)
+        t.to_formatted_string(fmt)
     }
 }
@@ -2,8 +2,17 @@
 Provides the traits required to implement a Relation *instance* and Tuple *instance* for evaluation.
  */
 
+use crate::error::{invalid_value, Error, Result};
 use crate::sort::{Domain, RelationSchema};
+use crate::Name;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+pub use rust_decimal::Decimal;
+pub use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -13,8 +22,18 @@ use std::fmt::{Debug, Display};
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+///
+/// A single value, of one of the [`Domain`]s. Values have a total order, and are hashable, so
+/// that they may be members of a set; values of different domains are ordered by domain, and
+/// never equal, except that null is greater than any other value. Within the float domain all
+/// NaN values are equal, and greater than any other float, and `-0.0` is equal to `0.0`.
+/// Decimals are equal if they have the same value, whatever their scale, and timestamps with a
+/// time zone if they are the same instant, whatever their offset.
+///
+#[derive(Clone, Debug)]
 pub enum Value {
+    /// The absence of a value, as used to pad the unmatched tuples of an outer join.
+    Null,
     Boolean(bool),
     Byte(u8),
     UnsignedInteger(u64),
@@ -23,6 +42,42 @@ pub enum Value {
     Char(char),
     String(String),
     Binary(Vec<u8>),
+    /// A fixed-precision decimal number, which retains its scale, so `1.50` is not written `1.5`.
+    Decimal(Decimal),
+    Date(Date),
+    Time(Time),
+    /// A date and time without a time zone.
+    Timestamp(PrimitiveDateTime),
+    /// A date and time with an offset from UTC.
+    TimestampTz(OffsetDateTime),
+    Interval(Interval),
+}
+
+///
+/// A span of time in months, days, and nanoseconds, as the length of a month in days, and of a
+/// day in hours, varies. Intervals are ordered as if a month were always 30 days and a day 24
+/// hours, as in SQL, and then by their components; so `P1M` is less than `P30DT1S`, and `P30D`
+/// is less than `P1M`.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    nanoseconds: i64,
+}
+
+///
+/// A [`Database`] $d$ is a set of named [`Relation`] instances, usually conforming to
+/// the relations described by some [`Schema`](crate::sort::Schema).
+///
+pub trait Database {
+    type Item: Relation;
+
+    fn has_relation(&self, name: &Name) -> bool {
+        self.relation(name).is_some()
+    }
+
+    fn relation(&self, name: &Name) -> Option<&Self::Item>;
 }
 
 ///
@@ -37,6 +92,30 @@ pub trait Relation {
     fn tuples(&self) -> Box<dyn Iterator<Item = &Self::Item> + '_>;
 }
 
+///
+/// Statistics describing the content of a [`Relation`], used to estimate the cost of evaluating
+/// an expression. Attributes are identified by their index in the relation's schema; any
+/// per-attribute statistic may be unknown.
+///
+pub trait Statistics {
+    fn cardinality(&self) -> usize;
+
+    fn distinct_count(&self, attribute: usize) -> Option<usize>;
+
+    fn min_value(&self, attribute: usize) -> Option<Value>;
+
+    fn max_value(&self, attribute: usize) -> Option<Value>;
+}
+
+///
+/// A source of [`Statistics`] for a set of named relations, usually those in a [`Database`].
+///
+pub trait StatisticsProvider {
+    type Item: Statistics;
+
+    fn statistics(&self, relation: &Name) -> Option<&Self::Item>;
+}
+
 ///
 /// A [`Tuple`], or *relation instance*, $t$ comprises attribute $a$ [`Value`]s, conforming to specific `AttributeSchema`s.
 ///
@@ -65,12 +144,59 @@ pub trait Tuple {
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
+impl<R> Database for HashMap<Name, R>
+where
+    R: Relation,
+{
+    type Item = R;
+
+    fn relation(&self, name: &Name) -> Option<&Self::Item> {
+        self.get(name)
+    }
+}
+
+impl<S> StatisticsProvider for HashMap<Name, S>
+where
+    S: Statistics,
+{
+    type Item = S;
+
+    fn statistics(&self, relation: &Name) -> Option<&Self::Item> {
+        self.get(relation)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}",
             match self {
+                Self::Null => "null".to_string(),
+                Self::Decimal(v) => v.to_string(),
+                Self::Date(v) => date_to_string(v),
+                Self::Time(v) => time_to_string(v),
+                Self::Timestamp(v) => {
+                    format!(
+                        "{}T{}",
+                        date_to_string(&v.date()),
+                        time_to_string(&v.time())
+                    )
+                }
+                Self::TimestampTz(v) => {
+                    let (hours, minutes, _) = v.offset().as_hms();
+                    format!(
+                        "{}T{}{}{:02}:{:02}",
+                        date_to_string(&v.date()),
+                        time_to_string(&v.time()),
+                        if v.offset().is_negative() { '-' } else { '+' },
+                        hours.unsigned_abs(),
+                        minutes.unsigned_abs()
+                    )
+                }
+                Self::Interval(v) => v.to_string(),
                 Self::Boolean(v) => format!("{}", v),
                 Self::Byte(v) => format!("0x{:02x}", v),
                 Self::UnsignedInteger(v) => format!("{}", v),
@@ -138,10 +264,52 @@ impl From<Vec<u8>> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(v: Decimal) -> Self {
+        Self::Decimal(v)
+    }
+}
+
+impl From<Date> for Value {
+    fn from(v: Date) -> Self {
+        Self::Date(v)
+    }
+}
+
+impl From<Time> for Value {
+    fn from(v: Time) -> Self {
+        Self::Time(v)
+    }
+}
+
+impl From<PrimitiveDateTime> for Value {
+    fn from(v: PrimitiveDateTime) -> Self {
+        Self::Timestamp(v)
+    }
+}
+
+impl From<OffsetDateTime> for Value {
+    fn from(v: OffsetDateTime) -> Self {
+        Self::TimestampTz(v)
+    }
+}
+
+impl From<Interval> for Value {
+    fn from(v: Interval) -> Self {
+        Self::Interval(v)
+    }
+}
+
 impl Value {
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
     #[inline]
     pub fn data_type(&self) -> Domain {
         match self {
+            Self::Null => Domain::Null,
             Self::Boolean(_) => Domain::Boolean,
             Self::Byte(_) => Domain::Byte,
             Self::UnsignedInteger(_) => Domain::UnsignedInteger,
@@ -150,14 +318,427 @@ impl Value {
             Self::Char(_) => Domain::Char,
             Self::String(_) => Domain::String,
             Self::Binary(_) => Domain::Binary,
+            Self::Decimal(_) => Domain::Decimal,
+            Self::Date(_) => Domain::Date,
+            Self::Time(_) => Domain::Time,
+            Self::Timestamp(_) => Domain::Timestamp,
+            Self::TimestampTz(_) => Domain::TimestampTz,
+            Self::Interval(_) => Domain::Interval,
+        }
+    }
+
+    ///
+    /// Parse `s` as a value of `domain`; numbers use Rust's literal forms without a type suffix,
+    /// binary values are hexadecimal, dates are `YYYY-MM-DD`, times `HH:MM[:SS[.fraction]]`, a
+    /// timestamp is a date and time separated by `T` or a space, which for a timestamp with a
+    /// time zone is followed by `Z` or an offset `±HH[:MM]`, and intervals are ISO 8601
+    /// durations such as `P1Y2M3DT4H5M6.5S`. Only `null` is a value of the null domain.
+    ///
+    pub fn parse(domain: Domain, s: &str) -> Result<Self> {
+        match domain {
+            Domain::Null => (s == "null").then_some(Self::Null),
+            Domain::Boolean => s.parse().ok().map(Self::Boolean),
+            Domain::Byte => s.parse().ok().map(Self::Byte),
+            Domain::UnsignedInteger => s.parse().ok().map(Self::UnsignedInteger),
+            Domain::Integer => s.parse().ok().map(Self::Integer),
+            Domain::Float => s.parse().ok().map(Self::Float),
+            Domain::Char => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(Self::Char(c)),
+                    _ => None,
+                }
+            }
+            Domain::String => Some(Self::String(s.to_string())),
+            Domain::Binary => parse_hex(s).map(Self::Binary),
+            Domain::Decimal => Decimal::from_str(s).ok().map(Self::Decimal),
+            Domain::Date => parse_date(s).map(Self::Date),
+            Domain::Time => parse_time(s).map(Self::Time),
+            Domain::Timestamp => s
+                .split_once(['T', ' '])
+                .and_then(|(date, time)| {
+                    Some(PrimitiveDateTime::new(parse_date(date)?, parse_time(time)?))
+                })
+                .map(Self::Timestamp),
+            Domain::TimestampTz => parse_timestamp_tz(s).map(Self::TimestampTz),
+            Domain::Interval => s.parse().ok().map(Self::Interval),
+        }
+        .ok_or_else(|| invalid_value(domain, s))
+    }
+
+    ///
+    /// Returns this value in the form accepted by [`Value::parse`] for its domain, so without the
+    /// quotes, or type suffixes, of its display form.
+    ///
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            Self::Byte(v) => v.to_string(),
+            Self::Char(v) => v.to_string(),
+            Self::String(v) => v.clone(),
+            Self::Binary(v) => v.iter().map(|b| format!("{:02x}", b)).collect(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (Self::Null, _) => Ordering::Greater,
+            (_, Self::Null) => Ordering::Less,
+            (Self::Boolean(l), Self::Boolean(r)) => l.cmp(r),
+            (Self::Byte(l), Self::Byte(r)) => l.cmp(r),
+            (Self::UnsignedInteger(l), Self::UnsignedInteger(r)) => l.cmp(r),
+            (Self::Integer(l), Self::Integer(r)) => l.cmp(r),
+            (Self::Float(l), Self::Float(r)) => canonical_float(*l).total_cmp(&canonical_float(*r)),
+            (Self::Char(l), Self::Char(r)) => l.cmp(r),
+            (Self::String(l), Self::String(r)) => l.cmp(r),
+            (Self::Binary(l), Self::Binary(r)) => l.cmp(r),
+            (Self::Decimal(l), Self::Decimal(r)) => l.cmp(r),
+            (Self::Date(l), Self::Date(r)) => l.cmp(r),
+            (Self::Time(l), Self::Time(r)) => l.cmp(r),
+            (Self::Timestamp(l), Self::Timestamp(r)) => l.cmp(r),
+            (Self::TimestampTz(l), Self::TimestampTz(r)) => l.cmp(r),
+            (Self::Interval(l), Self::Interval(r)) => l.cmp(r),
+            (l, r) => (l.data_type() as u8).cmp(&(r.data_type() as u8)),
+        }
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Null => {}
+            Self::Boolean(v) => v.hash(state),
+            Self::Byte(v) => v.hash(state),
+            Self::UnsignedInteger(v) => v.hash(state),
+            Self::Integer(v) => v.hash(state),
+            Self::Float(v) => canonical_float(*v).to_bits().hash(state),
+            Self::Char(v) => v.hash(state),
+            Self::String(v) => v.hash(state),
+            Self::Binary(v) => v.hash(state),
+            Self::Decimal(v) => v.hash(state),
+            Self::Date(v) => v.hash(state),
+            Self::Time(v) => v.hash(state),
+            Self::Timestamp(v) => v.hash(state),
+            Self::TimestampTz(v) => v.hash(state),
+            Self::Interval(v) => v.hash(state),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if *self == Self::default() {
+            return write!(f, "PT0S");
+        }
+        write!(f, "P")?;
+        let (years, months) = (self.months / 12, self.months % 12);
+        for (value, designator) in [(years, 'Y'), (months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                write!(f, "{}{}", value, designator)?;
+            }
+        }
+        if self.nanoseconds != 0 {
+            write!(f, "T")?;
+            let hours = self.nanoseconds / NANOSECONDS_PER_HOUR;
+            let minutes = self.nanoseconds % NANOSECONDS_PER_HOUR / NANOSECONDS_PER_MINUTE;
+            let seconds = self.nanoseconds % NANOSECONDS_PER_MINUTE;
+            for (value, designator) in [(hours, 'H'), (minutes, 'M')] {
+                if value != 0 {
+                    write!(f, "{}{}", value, designator)?;
+                }
+            }
+            if seconds != 0 {
+                write!(
+                    f,
+                    "{}{}{}S",
+                    if seconds < 0 { "-" } else { "" },
+                    seconds.unsigned_abs() / NANOSECONDS_PER_SECOND as u64,
+                    fraction_to_string(
+                        (seconds.unsigned_abs() % NANOSECONDS_PER_SECOND as u64) as u32
+                    )
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_interval(s).ok_or_else(|| invalid_value(Domain::Interval, s))
+    }
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.approximate_nanoseconds()
+            .cmp(&other.approximate_nanoseconds())
+            .then(self.months.cmp(&other.months))
+            .then(self.days.cmp(&other.days))
+            .then(self.nanoseconds.cmp(&other.nanoseconds))
+    }
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, nanoseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanoseconds,
         }
     }
+
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    fn approximate_nanoseconds(&self) -> i128 {
+        (self.months as i128 * 30 + self.days as i128) * 24 * NANOSECONDS_PER_HOUR as i128
+            + self.nanoseconds as i128
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Returns a float with a single NaN value and a single zero, so that floats may be compared
+/// and hashed consistently.
+///
+fn canonical_float(v: f64) -> f64 {
+    if v.is_nan() {
+        f64::NAN
+    } else if v == 0.0 {
+        0.0
+    } else {
+        v
+    }
+}
+
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+const NANOSECONDS_PER_MINUTE: i64 = 60 * NANOSECONDS_PER_SECOND;
+const NANOSECONDS_PER_HOUR: i64 = 60 * NANOSECONDS_PER_MINUTE;
+
+fn date_to_string(date: &Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+fn time_to_string(time: &Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}",
+        time.hour(),
+        time.minute(),
+        time.second(),
+        fraction_to_string(time.nanosecond())
+    )
+}
+
+/// The fraction of a second, with a leading `.`, and without trailing zeros; empty if zero.
+fn fraction_to_string(nanoseconds: u32) -> String {
+    if nanoseconds == 0 {
+        String::new()
+    } else {
+        format!(".{:09}", nanoseconds)
+            .trim_end_matches('0')
+            .to_string()
+    }
+}
+
+/// Parse a fraction of a second, without its leading `.`, of between one and nine digits.
+fn parse_fraction(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 9 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(s.parse::<u32>().ok()? * 10u32.pow(9 - s.len() as u32))
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn parse_date(s: &str) -> Option<Date> {
+    let mut parts = s.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    Date::from_calendar_date(
+        year.parse().ok()?,
+        month.parse::<u8>().ok()?.try_into().ok()?,
+        day.parse().ok()?,
+    )
+    .ok()
+}
+
+fn parse_time(s: &str) -> Option<Time> {
+    let mut parts = s.splitn(3, ':');
+    let (hour, minute, second) = (parts.next()?, parts.next()?, parts.next().unwrap_or("00"));
+    let (second, nanosecond) = match second.split_once('.') {
+        None => (second, 0),
+        Some((second, fraction)) => (second, parse_fraction(fraction)?),
+    };
+    if [hour, minute, second].iter().any(|part| part.len() != 2) {
+        return None;
+    }
+    Time::from_hms_nano(
+        hour.parse().ok()?,
+        minute.parse().ok()?,
+        second.parse().ok()?,
+        nanosecond,
+    )
+    .ok()
+}
+
+fn parse_timestamp_tz(s: &str) -> Option<OffsetDateTime> {
+    let (date, time) = s.split_once(['T', ' '])?;
+    let (time, offset) = match time.strip_suffix('Z') {
+        Some(time) => (time, time::UtcOffset::UTC),
+        None => {
+            let (time, offset) = time.split_at(time.rfind(['+', '-'])?);
+            let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "00"));
+            if hours.len() != 2 || minutes.len() != 2 {
+                return None;
+            }
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let offset = time::UtcOffset::from_hms(
+                sign * hours.parse::<i8>().ok()?,
+                sign * minutes.parse::<i8>().ok()?,
+                0,
+            )
+            .ok()?;
+            (time, offset)
+        }
+    };
+    Some(PrimitiveDateTime::new(parse_date(date)?, parse_time(time)?).assume_offset(offset))
+}
+
+///
+/// Parse an ISO 8601 duration, `P[nY][nM][nW][nD][T[nH][nM][n[.f]S]]`, where each `n` may be
+/// negative and only seconds may have a fraction.
+///
+fn parse_interval(s: &str) -> Option<Interval> {
+    let (date, time) = match s.strip_prefix('P')?.split_once('T') {
+        None => (s.strip_prefix('P')?, None),
+        Some((date, time)) => (date, Some(time)),
+    };
+    let mut interval = Interval::default();
+    let mut any = false;
+    for (value, designator) in interval_components(date)? {
+        let value = i32::try_from(value).ok()?;
+        match designator {
+            'Y' => interval.months = interval.months.checked_add(value.checked_mul(12)?)?,
+            'M' => interval.months = interval.months.checked_add(value)?,
+            'W' => interval.days = interval.days.checked_add(value.checked_mul(7)?)?,
+            'D' => interval.days = interval.days.checked_add(value)?,
+            _ => return None,
+        }
+        any = true;
+    }
+    if let Some(time) = time {
+        let components = interval_components(time)?;
+        if components.is_empty() {
+            return None;
+        }
+        for (value, designator) in components {
+            let unit = match designator {
+                'H' => NANOSECONDS_PER_HOUR,
+                'M' => NANOSECONDS_PER_MINUTE,
+                'S' => NANOSECONDS_PER_SECOND,
+                _ => return None,
+            };
+            // a fraction of a second is returned in nanoseconds.
+            let value = if designator == 'S' {
+                value
+            } else {
+                value.checked_mul(unit)?
+            };
+            interval.nanoseconds = interval.nanoseconds.checked_add(value)?;
+        }
+        any = true;
+    }
+    any.then_some(interval)
+}
+
+///
+/// Split the components of a duration into `(value, designator)` pairs; a value followed by `S`
+/// is in nanoseconds, as it may have a fraction.
+///
+fn interval_components(s: &str) -> Option<Vec<(i64, char)>> {
+    let mut components: Vec<(i64, char)> = Default::default();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| c.is_ascii_alphabetic())?;
+        let (number, designator) = (&rest[..end], rest[end..].chars().next()?);
+        let negative = number.starts_with('-');
+        let digits = number.strip_prefix('-').unwrap_or(number);
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) if designator == 'S' => (whole, parse_fraction(fraction)?),
+            Some(_) => return None,
+            None => (digits, 0),
+        };
+        if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut value: i64 = whole.parse().ok()?;
+        if designator == 'S' {
+            value = value
+                .checked_mul(NANOSECONDS_PER_SECOND)?
+                .checked_add(fraction as i64)?;
+        }
+        components.push((if negative { -value } else { value }, designator));
+        rest = &rest[end + 1..];
+    }
+    Some(components)
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
@@ -25,26 +25,124 @@ pub enum Error {
     /// The named relation does not exist in the selected database
     RelationDoesNotExist { name: Name },
 
+    /// The relation name is already that of another relation of the schema.
+    DuplicateRelation { name: Name },
+
     /// The named attribute was not a member of the relation or view schema.
     AttributeDoesNotExist { name: Name },
 
     /// The attribute index is not valid for the relation or view schema.
     AttributeIndexInvalid { index: usize },
 
+    /// The attribute, selected from a grouped query, is neither grouped nor aggregated.
+    AttributeNotGrouped { name: Name },
+
+    /// The attribute, used to order a query, is not one of those selected.
+    AttributeNotSelected { name: Name },
+
+    /// The attribute name refers to more than one attribute of the relation or view schema.
+    AmbiguousAttribute { name: Name },
+
+    /// The attribute name is already that of another attribute of the relation or view schema.
+    DuplicateAttribute { name: Name },
+
     /// A requested operation cannot be performed as the values have incompatible types.
     IncompatibleTypes {
         lhs_domain: Domain,
         rhs_domain: Domain,
     },
 
-    /// The value is not a valid representation for the expected type.
+    /// A tuple, or relation, does not have the number of attributes required; a record read from
+    /// a file includes the 1-based row at which it was found.
+    IncompatibleArity {
+        expected: usize,
+        given: usize,
+        row: Option<usize>,
+    },
+
+    /// The value is not a valid representation for the expected type; a value read from a file
+    /// includes the 1-based row, and column, at which it was found.
     InvalidValue {
         expecting_domain: Domain,
         given_value: Value,
+        location: Option<(usize, usize)>,
     },
 
+    /// The value is not the name of a domain.
+    InvalidDomain { value: String },
+
+    /// The named collation is not one supported for sorting.
+    UnsupportedCollation { name: Name },
+
+    /// An arithmetic operation overflowed the domain of its result, or divided by zero.
+    ArithmeticOverflow { expression: String },
+
+    /// The named function cannot be called with the number of arguments provided.
+    InvalidFunctionArguments { name: String, given: usize },
+
+    /// The pattern of a string match is not a valid regular expression.
+    InvalidPattern { pattern: String },
+
+    /// The record, at the given 1-based row of a file, has no value for the named attribute.
+    MissingKey { name: Name, row: usize },
+
+    /// The record, at the given 1-based row of a file, has a key that is not an attribute of the
+    /// relation being read.
+    UnexpectedKey { key: String, row: usize },
+
     /// The arity of facts must be greater than, or equal to, 1.
     NullaryFactsNotAllowed,
+
+    /// An error reading, or writing, a file.
+    Io { source: std::io::Error },
+
+    /// The text provided could not be parsed as an expression, SQL statement, or schema.
+    Parse { details: Box<ParseError> },
+}
+
+///
+/// The details of a failure to parse some input text, see [`Error::Parse`].
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseKind,
+    input: String,
+    span: Span,
+    expected: Vec<String>,
+    fragment: Option<String>,
+    message: Option<String>,
+}
+
+///
+/// The kind of text that was being parsed, see [`ParseError::kind`].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseKind {
+    /// A relational algebra expression, or a part of one, as parsed by module `parse`.
+    Expression,
+    /// An SQL statement, as parsed by module `sql::parse`.
+    Sql,
+    /// A schema, or a part of one, as parsed by module `simple::ddl`.
+    Schema,
+}
+
+///
+/// A position within some source text, both `line` and `column` are 1-based and columns are
+/// counted in characters, not bytes.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+///
+/// A range within some source text, from `start` (inclusive) to `end` (exclusive).
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
 }
 
 ///
@@ -71,6 +169,12 @@ pub fn relation_does_not_exist(name: Name) -> Error {
     Error::RelationDoesNotExist { name }
 }
 
+/// The relation name is already that of another relation of the schema.
+#[inline]
+pub fn duplicate_relation(name: Name) -> Error {
+    Error::DuplicateRelation { name }
+}
+
 /// The attribute does not exist in the selected relation or view schema
 #[inline]
 pub fn attribute_does_not_exist(name: Name) -> Error {
@@ -83,6 +187,100 @@ pub fn attribute_index_invalid(index: usize) -> Error {
     Error::AttributeIndexInvalid { index }
 }
 
+/// The attribute, selected from a grouped query, is neither grouped nor aggregated.
+#[inline]
+pub fn attribute_not_grouped(name: Name) -> Error {
+    Error::AttributeNotGrouped { name }
+}
+
+/// The attribute, used to order a query, is not one of those selected.
+#[inline]
+pub fn attribute_not_selected(name: Name) -> Error {
+    Error::AttributeNotSelected { name }
+}
+
+/// The attribute name refers to more than one attribute of the relation or view schema.
+#[inline]
+pub fn ambiguous_attribute(name: Name) -> Error {
+    Error::AmbiguousAttribute { name }
+}
+
+/// The attribute name is already that of another attribute of the relation or view schema.
+#[inline]
+pub fn duplicate_attribute(name: Name) -> Error {
+    Error::DuplicateAttribute { name }
+}
+
+/// The value is not the name of a domain.
+#[inline]
+pub fn invalid_domain<S>(value: S) -> Error
+where
+    S: Into<String>,
+{
+    Error::InvalidDomain {
+        value: value.into(),
+    }
+}
+
+/// The named collation is not one supported for sorting.
+#[inline]
+pub fn unsupported_collation(name: Name) -> Error {
+    Error::UnsupportedCollation { name }
+}
+
+/// The pattern of a string match is not a valid regular expression.
+#[inline]
+pub fn invalid_pattern<S>(pattern: S) -> Error
+where
+    S: Into<String>,
+{
+    Error::InvalidPattern {
+        pattern: pattern.into(),
+    }
+}
+
+/// The record, at the given 1-based row of a file, has no value for the named attribute.
+#[inline]
+pub fn missing_key(name: Name, row: usize) -> Error {
+    Error::MissingKey { name, row }
+}
+
+/// The record, at the given 1-based row of a file, has a key that is not an attribute of the
+/// relation being read.
+#[inline]
+pub fn unexpected_key<S>(key: S, row: usize) -> Error
+where
+    S: Into<String>,
+{
+    Error::UnexpectedKey {
+        key: key.into(),
+        row,
+    }
+}
+
+/// An arithmetic operation overflowed the domain of its result, or divided by zero.
+#[inline]
+pub fn arithmetic_overflow<S>(expression: S) -> Error
+where
+    S: Into<String>,
+{
+    Error::ArithmeticOverflow {
+        expression: expression.into(),
+    }
+}
+
+/// The named function cannot be called with the number of arguments provided.
+#[inline]
+pub fn invalid_function_arguments<S>(name: S, given: usize) -> Error
+where
+    S: Into<String>,
+{
+    Error::InvalidFunctionArguments {
+        name: name.into(),
+        given,
+    }
+}
+
 /// The arity of facts must be greater than, or equal to, 1.
 #[inline]
 pub fn nullary_facts_not_allowed() -> Error {
@@ -98,6 +296,27 @@ pub fn incompatible_types(lhs_domain: Domain, rhs_domain: Domain) -> Error {
     }
 }
 
+/// A tuple, or relation, does not have the number of attributes required.
+#[inline]
+pub fn incompatible_arity(expected: usize, given: usize) -> Error {
+    Error::IncompatibleArity {
+        expected,
+        given,
+        row: None,
+    }
+}
+
+/// A record, read from the given 1-based row of a file, does not have the number of fields
+/// required.
+#[inline]
+pub fn incompatible_arity_at(expected: usize, given: usize, row: usize) -> Error {
+    Error::IncompatibleArity {
+        expected,
+        given,
+        row: Some(row),
+    }
+}
+
 /// A value provided is not valid for the expected type.
 #[inline]
 pub fn invalid_value<V>(expecting_domain: Domain, given_value: V) -> Error
@@ -107,6 +326,51 @@ where
     Error::InvalidValue {
         expecting_domain,
         given_value: given_value.into(),
+        location: None,
+    }
+}
+
+/// The value, read from the given 1-based row and column of a file, is not a valid
+/// representation for the expected type.
+#[inline]
+pub fn invalid_value_at<V>(
+    expecting_domain: Domain,
+    given_value: V,
+    row: usize,
+    column: usize,
+) -> Error
+where
+    V: Into<Value>,
+{
+    Error::InvalidValue {
+        expecting_domain,
+        given_value: given_value.into(),
+        location: Some((row, column)),
+    }
+}
+
+/// The text provided could not be parsed as the `kind` of text expected.
+#[inline]
+pub fn parse_error<S>(
+    kind: ParseKind,
+    input: S,
+    span: Span,
+    expected: Vec<String>,
+    fragment: Option<String>,
+    message: Option<String>,
+) -> Error
+where
+    S: Into<String>,
+{
+    Error::Parse {
+        details: Box::new(ParseError {
+            kind,
+            input: input.into(),
+            span,
+            expected,
+            fragment,
+            message,
+        }),
     }
 }
 
@@ -130,30 +394,112 @@ impl Display for Error {
                 Error::RelationDoesNotExist { name } => {
                     format!("The relation named `{}` does not exist.", name)
                 }
+                Error::DuplicateRelation { name } => {
+                    format!("The relation name `{}` is already in use.", name)
+                }
                 Error::AttributeDoesNotExist { name } => {
                     format!("The attribute named `{}` does not exist.", name)
                 }
                 Error::AttributeIndexInvalid { index } => {
                     format!("The attribute index `{}` does not exist.", index)
                 }
+                Error::AttributeNotGrouped { name } => format!(
+                    "The attribute `{}` must appear in the GROUP BY clause or be used in an aggregate.",
+                    name
+                ),
+                Error::AttributeNotSelected { name } => format!(
+                    "The ORDER BY attribute `{}` must appear in the SELECT list.",
+                    name
+                ),
+                Error::AmbiguousAttribute { name } => {
+                    format!("The attribute name `{}` is ambiguous.", name)
+                }
+                Error::DuplicateAttribute { name } => {
+                    format!("The attribute name `{}` is already in use.", name)
+                }
                 Error::IncompatibleTypes { lhs_domain, rhs_domain } => format!(
                     "The attempted operation cannot be performed as the values have incompatible types (`{}`, `{}`).",
                     lhs_domain, rhs_domain
                 ),
+                Error::IncompatibleArity {
+                    expected,
+                    given,
+                    row: None,
+                } => format!(
+                    "Expecting {} attribute(s), but {} were provided.",
+                    expected, given
+                ),
+                Error::IncompatibleArity {
+                    expected,
+                    given,
+                    row: Some(row),
+                } => format!(
+                    "Expecting {} attribute(s), but {} were provided at row {}.",
+                    expected, given, row
+                ),
                 Error::InvalidValue {
                     expecting_domain,
                     given_value,
+                    location: None,
                 } => format!(
                     "The value `{}` is not a valid `{}`.",
                     given_value, expecting_domain
                 ),
+                Error::InvalidValue {
+                    expecting_domain,
+                    given_value,
+                    location: Some((row, column)),
+                } => format!(
+                    "The value `{}`, at row {}, column {}, is not a valid `{}`.",
+                    given_value, row, column, expecting_domain
+                ),
+                Error::InvalidDomain { value } => {
+                    format!("The value `{}` is not the name of a domain.", value)
+                }
+                Error::UnsupportedCollation { name } => {
+                    format!("The collation `{}` is not supported.", name)
+                }
+                Error::ArithmeticOverflow { expression } => format!(
+                    "The arithmetic expression `{}` overflowed, or divided by zero.",
+                    expression
+                ),
+                Error::InvalidFunctionArguments { name, given } => format!(
+                    "The function `{}` cannot be called with {} argument(s).",
+                    name, given
+                ),
+                Error::InvalidPattern { pattern } => {
+                    format!("The pattern `{}` is not a valid regular expression.", pattern)
+                }
+                Error::MissingKey { name, row } => format!(
+                    "The record at row {} has no value for the attribute `{}`.",
+                    row, name
+                ),
+                Error::UnexpectedKey { key, row } => format!(
+                    "The record at row {} has the key `{}`, which is not an attribute of the relation.",
+                    row, key
+                ),
                 Error::NullaryFactsNotAllowed => "Nullary facts are not allowed".to_string(),
+                Error::Io { source } => format!("An I/O error occurred; {}", source),
+                Error::Parse { details } => details.to_string(),
             }
         )
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(source: std::io::Error) -> Self {
+        Error::Io { source }
+    }
+}
 
 impl<T> From<Error> for Result<T> {
     fn from(e: Error) -> Self {
@@ -161,6 +507,149 @@ impl<T> From<Error> for Result<T> {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Renders the error as a summary line followed by the offending line of the input with a
+/// caret marker underneath the span, for example:
+///
+/// ```text
+/// The expression could not be parsed at 1:5; found `]`, expected attribute, or literal value.
+///   |
+/// 1 | σ[0=]relation
+///   |     ^
+/// ```
+///
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The {} could not be parsed at {}",
+            self.kind, self.span.start
+        )?;
+        if let Some(fragment) = &self.fragment {
+            write!(f, "; found `{}`", fragment)?;
+        } else {
+            write!(f, "; found end of input")?;
+        }
+        if !self.expected.is_empty() {
+            write!(f, ", expected {}", self.expected.join(", or "))?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, "; {}", message)?;
+        }
+        write!(f, ".")?;
+
+        if let Some(line) = self
+            .input
+            .lines()
+            .nth(self.span.start.line.saturating_sub(1))
+        {
+            let gutter = " ".repeat(self.span.start.line.to_string().len());
+            let width = if self.span.end.line == self.span.start.line
+                && self.span.end.column > self.span.start.column
+            {
+                self.span.end.column - self.span.start.column
+            } else {
+                1
+            };
+            write!(
+                f,
+                "\n{} |\n{} | {}\n{} | {}{}",
+                gutter,
+                self.span.start.line,
+                line,
+                gutter,
+                " ".repeat(self.span.start.column.saturating_sub(1)),
+                "^".repeat(width)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ParseError {
+    /// The kind of text that was being parsed.
+    pub fn kind(&self) -> ParseKind {
+        self.kind
+    }
+
+    /// The complete input text that was being parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The location of the offending input.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Descriptions of the tokens the parser would have accepted at this location.
+    pub fn expected(&self) -> impl Iterator<Item = &String> {
+        self.expected.iter()
+    }
+
+    /// The offending input, or `None` if the parser reached the end of the input.
+    pub fn fragment(&self) -> Option<&String> {
+        self.fragment.as_ref()
+    }
+
+    /// Any additional message, for example describing an invalid literal value.
+    pub fn message(&self) -> Option<&String> {
+        self.message.as_ref()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ParseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Expression => "expression",
+                Self::Sql => "SQL statement",
+                Self::Schema => "schema",
+            }
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl From<Location> for Span {
+    fn from(v: Location) -> Self {
+        Self { start: v, end: v }
+    }
+}
+
+impl Span {
+    pub fn new(start: Location, end: Location) -> Self {
+        Self { start, end }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
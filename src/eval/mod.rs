@@ -1,370 +1,963 @@
 /*!
 Provides an implementation of a query analyzer and execution model for Expressions.
 
+Evaluation takes an [`Expression`], a [`Schema`] describing the relations that may be referenced,
+and a [`Database`] which provides the relation instances themselves. The result of evaluation is
+always a materialized [`SimpleRelation`].
+
+Before any evaluation takes place the expression is analyzed, using only the schema, to determine
+the schema of its result; this ensures that all relations and attributes referenced exist, and
+that comparisons and set operations are between compatible types. Any constant pattern of a
+string match is also compiled during analysis, so that an invalid regular expression is reported
+as [`Error::InvalidPattern`](crate::error::Error::InvalidPattern) before evaluation, and the
+compiled pattern is reused for every tuple. Analysis is also available directly, via
+[`analyze_expression`].
+
 # Example
 
+```rust
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::data::Value;
+use relational_algebra::eval::evaluate_relational;
+use relational_algebra::simple::data::{SimpleRelation, SimpleTuple};
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use relational_algebra::Name;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+let people = SimpleRelationSchema::new(
+    Name::new_unchecked("people"),
+    [
+        SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+        SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+    ],
+)
+.unwrap();
+let schema = SimpleSchema::new(Name::new_unchecked("example"), [people.clone()]).unwrap();
+
+let mut database: HashMap<Name, SimpleRelation> = Default::default();
+database.insert(
+    Name::new_unchecked("people"),
+    SimpleRelation::from_tuples(
+        people,
+        [
+            SimpleTuple::from(vec![Value::from("alice"), Value::from(32i64)]),
+            SimpleTuple::from(vec![Value::from("bob"), Value::from(12i64)]),
+        ],
+    )
+    .unwrap(),
+);
+
+let query = RelationalOp::from_str("π[name](σ[age≥18i64]people)").unwrap();
+let result = evaluate_relational(&query, &schema, &database).unwrap();
+assert_eq!(result.len(), 1);
+```
+
  */
 
+pub use self::scalar::evaluate_scalar;
+
+use self::ops::{
+    AntiJoin as _, CartesianProduct as _, Difference as _, Divide as _, Group as _, Intersect as _,
+    NaturalJoin as _, OuterJoin as _, Rename as _, Select as _, SemiJoin as _, Sort as _,
+    ThetaJoin as _, Union as _,
+};
 use crate::{
-    ast::{ComparisonOperator, Criteria, Group, Order, ProjectedAttribute, Selection},
-    data::Value,
+    ast::{
+        Aggregate, AntiJoin, Attribute, ComparisonOperator, Division, Expression, ExpressionList,
+        Group, Join, NaturalJoin, Order, OuterJoin, OuterJoinKind, ProjectedAttribute, Projection,
+        RelationalOp, Rename, Selection, SemiJoin, SemiJoinKind, SetOperation, SetOperator,
+        SortKey, Term, ThetaJoin,
+    },
+    data::{Database, Decimal, Relation, Tuple, Value},
+    error::{
+        ambiguous_attribute, attribute_does_not_exist, attribute_index_invalid, incompatible_arity,
+        incompatible_types, relation_does_not_exist, Result,
+    },
+    simple::{
+        data::SimpleRelation,
+        ops::{
+            attribute_indices, check_union_compatible, collation, common_attributes,
+            constant_attribute_name, constant_attribute_schema, division_attributes,
+            division_schema, group_schema, natural_join_schema, outer_join_schema, product_schema,
+            rename_attributes,
+        },
+        sort::{SimpleAttributeSchema, SimpleRelationSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    Name,
 };
+use regex::Regex;
+use rust_decimal::prelude::ToPrimitive;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 // ------------------------------------------------------------------------------------------------
-// Public Macros
+// Public Functions
 // ------------------------------------------------------------------------------------------------
 
-// ------------------------------------------------------------------------------------------------
-// Public Types
-// ------------------------------------------------------------------------------------------------
+///
+/// Analyze each expression in the list in turn, returning the schema of the relation each would
+/// produce. Named expressions may be referenced, by name, in any subsequent expression in the
+/// list and will hide any relation of the same name in `schema`.
+///
+pub fn analyze_expression_list<S>(list: &ExpressionList, schema: &S) -> Result<Vec<S::Item>>
+where
+    S: Schema,
+{
+    let mut analyzer = Analyzer::new(schema);
+    let mut results: Vec<S::Item> = Default::default();
+    for expression in list.as_ref() {
+        let result = analyzer.expression(expression)?;
+        results.push(from_simple_schema(&result)?);
+        if let Some(name) = expression.name() {
+            let _ = analyzer.views.insert(name.clone(), result);
+        }
+    }
+    Ok(results)
+}
 
-// ------------------------------------------------------------------------------------------------
-// Public Functions
-// ------------------------------------------------------------------------------------------------
+///
+/// Analyze a single expression, returning the schema of the relation it would produce without
+/// evaluating it. This will report any reference to a relation or attribute that does not exist,
+/// and any comparison or set operation between incompatible types.
+///
+pub fn analyze_expression<S>(expression: &Expression, schema: &S) -> Result<S::Item>
+where
+    S: Schema,
+{
+    from_simple_schema(&Analyzer::new(schema).expression(expression)?)
+}
+
+///
+/// Analyze a single relational operation, returning the schema of the relation it would produce.
+///
+pub fn analyze_relational<S>(op: &RelationalOp, schema: &S) -> Result<S::Item>
+where
+    S: Schema,
+{
+    from_simple_schema(&Analyzer::new(schema).relational(op)?)
+}
 
-pub fn analyze_expression() -> Result {}
+///
+/// Evaluate each expression in the list in turn, returning a relation for each. Named
+/// expressions may be referenced, by name, in any subsequent expression in the list and will
+/// hide any relation of the same name in `database`.
+///
+pub fn evaluate_expression_list<S, D>(
+    list: &ExpressionList,
+    schema: &S,
+    database: &D,
+) -> Result<Vec<SimpleRelation>>
+where
+    S: Schema,
+    D: Database,
+{
+    let _ = analyze_expression_list(list, schema)?;
+    let mut evaluator = Evaluator::new(schema, database);
+    let mut results: Vec<SimpleRelation> = Default::default();
+    for expression in list.as_ref() {
+        let result = evaluator.expression(expression)?;
+        if let Some(name) = expression.name() {
+            let _ = evaluator.views.insert(name.clone(), result.clone());
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+///
+/// Evaluate a single expression; if the expression is named the resulting relation's schema
+/// will take that name.
+///
+pub fn evaluate_expression<S, D>(
+    expression: &Expression,
+    schema: &S,
+    database: &D,
+) -> Result<SimpleRelation>
+where
+    S: Schema,
+    D: Database,
+{
+    let _ = analyze_expression(expression, schema)?;
+    Evaluator::new(schema, database).expression(expression)
+}
+
+///
+/// Evaluate a single relational operation.
+///
+pub fn evaluate_relational<S, D>(
+    op: &RelationalOp,
+    schema: &S,
+    database: &D,
+) -> Result<SimpleRelation>
+where
+    S: Schema,
+    D: Database,
+{
+    let _ = analyze_relational(op, schema)?;
+    Evaluator::new(schema, database).relational(op)
+}
 
-pub fn evaluate_expression() -> Result<Box<dyn Relation>> {
-    todo!()
+///
+/// Determine whether `tuple`, conforming to `schema`, satisfies the criteria in `term`; a tuple
+/// for which the criteria are unknown, see [`evaluate_predicate`], does not.
+///
+pub fn evaluate_term<R, T>(term: &Term, schema: &R, tuple: &T) -> Result<bool>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    Ok(evaluate_predicate(term, schema, tuple)? == Some(true))
+}
+
+///
+/// Evaluate the criteria in `term` for `tuple`, conforming to `schema`, using the three-valued
+/// logic of SQL; `None` denotes unknown, the result of any comparison with a null value. The
+/// negation of unknown is unknown, `false ∧ unknown` is false, and `true ∨ unknown` is true.
+///
+/// Any pattern of a string match is compiled for each call; the evaluation of a relational
+/// operation, as by [`evaluate_relational`], compiles each constant pattern only once.
+///
+pub fn evaluate_predicate<R, T>(term: &Term, schema: &R, tuple: &T) -> Result<Option<bool>>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    predicate_value(term, schema, tuple, &Patterns::default())
+}
+
+///
+/// Compare two values; values of the same domain are compared directly, values of different
+/// numeric domains are compared numerically, a date is compared with a timestamp as if it were
+/// midnight on that date, and any other combination is an error. Timestamps with a time zone are
+/// compared as instants, whatever their offset. A null value is equal to another null, and
+/// ordered after any other value.
+///
+pub fn compare_values(lhs: &Value, rhs: &Value) -> Result<Ordering> {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => Ok(Ordering::Equal),
+        (Value::Null, _) => Ok(Ordering::Greater),
+        (_, Value::Null) => Ok(Ordering::Less),
+        (Value::Boolean(l), Value::Boolean(r)) => Ok(l.cmp(r)),
+        (Value::Char(l), Value::Char(r)) => Ok(l.cmp(r)),
+        (Value::String(l), Value::String(r)) => Ok(l.cmp(r)),
+        (Value::Binary(l), Value::Binary(r)) => Ok(l.cmp(r)),
+        (Value::Date(l), Value::Date(r)) => Ok(l.cmp(r)),
+        (Value::Time(l), Value::Time(r)) => Ok(l.cmp(r)),
+        (Value::Timestamp(l), Value::Timestamp(r)) => Ok(l.cmp(r)),
+        (Value::Date(l), Value::Timestamp(r)) => Ok(l.midnight().cmp(r)),
+        (Value::Timestamp(l), Value::Date(r)) => Ok(l.cmp(&r.midnight())),
+        (Value::TimestampTz(l), Value::TimestampTz(r)) => Ok(l.cmp(r)),
+        (Value::Interval(l), Value::Interval(r)) => Ok(l.cmp(r)),
+        (l, r) if l.data_type().is_numeric() && r.data_type().is_numeric() => {
+            match (numeric_as_integer(l), numeric_as_integer(r)) {
+                (Some(l), Some(r)) => Ok(l.cmp(&r)),
+                _ if l.data_type() != Domain::Float && r.data_type() != Domain::Float => {
+                    Ok(numeric_as_decimal(l).cmp(&numeric_as_decimal(r)))
+                }
+                // compared as floats, so NaN is greater than any other number.
+                _ => Ok(Value::Float(numeric_as_float(l)).cmp(&Value::Float(numeric_as_float(r)))),
+            }
+        }
+        (l, r) => Err(incompatible_types(l.data_type(), r.data_type())),
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
 
+pub(crate) struct Analyzer<'a, S>
+where
+    S: Schema,
+{
+    schema: &'a S,
+    pub(crate) views: HashMap<Name, SimpleRelationSchema>,
+}
+
+struct Evaluator<'a, S, D>
+where
+    S: Schema,
+    D: Database,
+{
+    schema: &'a S,
+    database: &'a D,
+    views: HashMap<Name, SimpleRelation>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
-//
-// impl RelationalOp {
-//     pub fn compile_atom(atom: &Atom, project_constants: bool) -> Result<Self> {
-//         Self::compile_atom_with(atom, project_constants, Default::default())
-//     }
-//
-//     pub fn compile_atom_with(
-//         atom: &Atom,
-//         project_constants: bool,
-//         criteria: Vec<Criteria>,
-//     ) -> Result<Self> {
-//         println!(
-//             "compile_atom_with > {} ({}) {:?}",
-//             atom,
-//             if project_constants {
-//                 "project constants"
-//             } else {
-//                 "drop constants"
-//             },
-//             criteria
-//         );
-//         let projections: Vec<Attribute<Variable>> = atom
-//             .iter()
-//             .enumerate()
-//             .filter(|(_, term)| {
-//                 if project_constants {
-//                     !term.is_anonymous()
-//                 } else {
-//                     term.is_variable()
-//                 }
-//             })
-//             .map(|(i, term)| {
-//                 let mut attribute = match term {
-//                     Term::Variable(v) => Attribute::from(v.clone()),
-//                     Term::Constant(v) => Attribute::typed(v.kind()),
-//                     Term::Anonymous => unreachable!(),
-//                 };
-//                 attribute.set_index(i);
-//                 attribute
-//             })
-//             .collect();
-//         println!("compile_atom_with > project {:?}", projections);
-//
-//         if projections.is_empty() {
-//             Err(nullary_facts_not_allowed())
-//         } else {
-//             let mut static_criteria: Vec<Criteria> = atom
-//                 .iter()
-//                 .enumerate()
-//                 .filter_map(|(i, term)| term.as_constant().map(|c| (i, c)))
-//                 .map(|(i, constant)| Criteria {
-//                     index: i,
-//                     op: ComparisonOperator::Equal,
-//                     value: CriteriaValue::Value(constant.clone()),
-//                 })
-//                 .collect();
-//             println!("compile_atom_with > static_criteria {:?}", static_criteria);
-//             static_criteria.extend(criteria.into_iter());
-//             Ok(
-//                 match (
-//                     project_constants,
-//                     projections.len() == atom.len(), // true if we projection is complete
-//                     static_criteria.is_empty(),
-//                 ) {
-//                     (_, true, true) => RelationalOp::Relation(atom.label_ref().into()),
-//                     (_, true, false) => RelationalOp::Selection(Selection::new(
-//                         static_criteria,
-//                         RelationalOp::Relation(atom.label_ref().into()),
-//                         false,
-//                     )),
-//                     (true, false, false) => RelationalOp::Selection(Selection::new(
-//                         static_criteria,
-//                         RelationalOp::Projection(Projection::new(
-//                             projections,
-//                             RelationalOp::Relation(atom.label_ref().into()),
-//                         )),
-//                         false,
-//                     )),
-//                     (false, false, false) => RelationalOp::Projection(Projection::new(
-//                         projections,
-//                         RelationalOp::Selection(Selection::new(
-//                             static_criteria,
-//                             RelationalOp::Relation(atom.label_ref().into()),
-//                             false,
-//                         )),
-//                     )),
-//                     (false, false, true) => RelationalOp::Projection(Projection::new(
-//                         projections,
-//                         RelationalOp::Relation(atom.label_ref().into()),
-//                     )),
-//                     state => {
-//                         eprintln!("Unexpected state: {:?}", state);
-//                         unreachable!()
-//                     }
-//                 },
-//             )
-//         }
-//     }
-//
-//     pub fn compile_rule(rule: &Rule) -> Result<Self> {
-//         println!("----------------------------------------------------------------------");
-//         let arithmetic: Vec<(&Comparison, bool)> = rule
-//             .literals()
-//             .filter_map(|lit| lit.as_arithmetic().map(|atom| (atom, lit.is_negative())))
-//             .collect();
-//         let relational: Vec<(&Atom, bool)> = rule
-//             .literals()
-//             .filter_map(|lit| lit.as_relational().map(|comp| (comp, lit.is_negative())))
-//             .collect();
-//
-//         // TODO: (ISSUE/rust-asdi/3) negation
-//
-//         let mut ops: Vec<RelationalOp> = Default::default();
-//         let mut theta: Vec<&Comparison> = Default::default();
-//         for (atom, atom_negated) in relational {
-//             println!("compile_rule > atom {} (negated {})", atom, atom_negated);
-//             let mut criteria: Vec<Criteria> = Default::default();
-//             for (comparison, comparison_negated) in &arithmetic {
-//                 println!(
-//                     "compile_rule > atom > comparison {:?} (negated {})",
-//                     comparison, comparison_negated
-//                 );
-//                 if let Err(e) = comparison.sanity_check() {
-//                     warn!(
-//                         "Ignoring arithmetic literal '{:?}', sanity check failed: {}",
-//                         comparison, e
-//                     );
-//                 } else {
-//                     match (comparison.lhs(), comparison.operator(), comparison.rhs()) {
-//                         (Term::Variable(lhs), op, Term::Constant(rhs)) => {
-//                             if let Some(index) = atom.variable_index(lhs) {
-//                                 criteria.push(Criteria::new(
-//                                     index,
-//                                     *op,
-//                                     CriteriaValue::Value(rhs.clone()),
-//                                 ))
-//                             }
-//                         }
-//                         (Term::Constant(lhs), op, Term::Variable(rhs)) => {
-//                             if let Some(index) = atom.variable_index(rhs) {
-//                                 criteria.push(Criteria::new(
-//                                     index,
-//                                     op.inverse(),
-//                                     CriteriaValue::Value(lhs.clone()),
-//                                 ));
-//                             }
-//                         }
-//                         (Term::Variable(lhs), op, Term::Variable(rhs)) => {
-//                             if let Some(lhs_index) = atom.variable_index(lhs) {
-//                                 if let Some(rhs_index) = atom.variable_index(rhs) {
-//                                     criteria.push(Criteria::new(
-//                                         lhs_index,
-//                                         *op,
-//                                         CriteriaValue::Index(rhs_index),
-//                                     ));
-//                                 } else {
-//                                     theta.push(comparison);
-//                                 }
-//                             }
-//                         }
-//                         _ => unreachable!(),
-//                     }
-//                 }
-//             }
-//             let atom_op = Self::compile_atom_with(atom, false, criteria)?;
-//             println!("compile_rule > atom >> {}", atom_op);
-//             ops.push(atom_op);
-//         }
-//
-//         warn!(
-//             "Found comparisons for theta join, which is not yet implemented: {:?}",
-//             theta
-//         );
-//
-//         let mut ops = ops.into_iter().rev();
-//         let last = ops.next().unwrap();
-//         let joined = ops.fold(last, |left, right| Join::natural(left, right).into());
-//         println!("compile_rule > joined {:?}", joined);
-//
-//         // TODO: (ISSUE/rust-asdi/4) may need rework for disjunction.
-//
-//         let distinguished_terms = rule.distinguished_terms_in_order();
-//         let joined = if distinguished_terms.len() < rule.variables().len() {
-//             // TODO: (ISSUE/rust-asdi/12) Need to support constants in the final projection.
-//             let joined = RelationalOp::from(Projection::new(
-//                 distinguished_terms
-//                     .iter()
-//                     .filter_map(|t| t.as_variable())
-//                     .map(|v| Attribute::labeled(v.clone()))
-//                     .collect::<Vec<Attribute<Variable>>>(),
-//                 joined,
-//             ));
-//             println!("compile_rule > joined {:?}", joined);
-//             joined
-//         } else {
-//             joined
-//         };
-//         Ok(RelationalOp::Sink(RelationSink::new(
-//             joined,
-//             rule.head.get(0).unwrap().label_ref(),
-//         )))
-//     }
-// }
-//
-// // ------------------------------------------------------------------------------------------------
-//
-// impl Selection {
-//     pub fn is_match(&self, fact: &[Value]) -> Result<bool> {
-//         for criteria in &self.criteria {
-//             if !criteria.is_match(fact)? {
-//                 return Ok(false);
-//             }
-//         }
-//         Ok(true)
-//     }
-// }
-//
-// impl TryFrom<&Atom> for Selection {
-//     type Error = Error;
-//
-//     fn try_from(value: &Atom) -> std::result::Result<Self, Self::Error> {
-//         Ok(Self {
-//             source: Box::new(RelationalOp::Relation(value.label_ref().into())),
-//             criteria: value
-//                 .iter()
-//                 .enumerate()
-//                 .filter_map(|(i, term)| term.as_constant().map(|c| (i, c)))
-//                 .map(|(i, constant)| Criteria {
-//                     index: i,
-//                     op: ComparisonOperator::Equal,
-//                     value: CriteriaValue::Value(constant.clone()),
-//                 })
-//                 .collect(),
-//             negated: false,
-//         })
-//     }
-// }
-//
-// impl TryFrom<&Rule> for Selection {
-//     type Error = Error;
-//
-//     fn try_from(_value: &Rule) -> std::result::Result<Self, Self::Error> {
-//         unimplemented!()
-//     }
-// }
-//
-// // ------------------------------------------------------------------------------------------------
-//
-// impl Criteria {
-//     pub fn is_match(&self, fact: &[]) -> Result<bool> {
-//         let lhs = fact
-//             .get(self.index)
-//             .ok_or_else(|| attribute_index_invalid(self.index))?;
-//         let rhs = match &self.value {
-//             ProjectedAttribute::Value(v) => v,
-//             ProjectedAttribute::Index(i) => fact.get(*i).ok_or_else(|| attribute_index_invalid(*i))?,
-//         };
-//         if lhs.kind() != rhs.kind() {
-//             Err(incompatible_types(
-//                 lhs.kind().to_string(),
-//                 rhs.kind().to_string(),
-//             ))
-//         } else {
-//             Ok(match self.op {
-//                 ComparisonOperator::Equal => lhs == rhs,
-//                 ComparisonOperator::NotEqual => lhs != rhs,
-//                 ComparisonOperator::LessThan => lhs < rhs,
-//                 ComparisonOperator::LessThanOrEqual => lhs <= rhs,
-//                 ComparisonOperator::GreaterThan => lhs > rhs,
-//                 ComparisonOperator::GreaterThanOrEqual => lhs >= rhs,
-//                 ComparisonOperator::StringMatch => {
-//                     // TODO: cache regex
-//                     let lhs = lhs.as_string().unwrap();
-//                     let rhs = rhs.as_string().unwrap();
-//                     let regex: Regex = Regex::new(rhs).unwrap();
-//                     regex.is_match(lhs)
-//                 }
-//             })
-//         }
-//     }
-// }
-//
-//
-// impl TryFrom<&Atom> for Projection {
-//     type Error = Error;
-//
-//     fn try_from(atom: &Atom) -> std::result::Result<Self, Self::Error> {
-//         let projections: Vec<Attribute<Variable>> = atom
-//             .iter()
-//             .enumerate()
-//             .filter(|(_, term)| !term.is_anonymous())
-//             .map(|(i, term)| {
-//                 let mut attribute = match term {
-//                     Term::Variable(v) => Attribute::from(v.clone()),
-//                     Term::Constant(v) => Attribute::typed(v.kind()),
-//                     Term::Anonymous => unreachable!(),
-//                 };
-//                 attribute.set_index(i);
-//                 attribute
-//             })
-//             .collect();
-//
-//         if projections.len() == atom.len() {
-//             Ok(Self::all(RelationalOp::Relation(atom.label_ref().into())))
-//         } else if projections.is_empty() {
-//             Err(nullary_facts_not_allowed())
-//         } else {
-//             Ok(Self::new(
-//                 projections,
-//                 RelationalOp::Relation(atom.label_ref().into()),
-//             ))
-//         }
-//     }
-// }
-//
-// impl From<Projection> for Schema<Variable> {
-//     fn from(p: Projection) -> Self {
-//         Self::from(
-//             p.attributes
-//                 .into_iter()
-//                 .collect::<Vec<Attribute<Variable>>>(),
-//         )
-//     }
-// }
+impl<'a, S> Analyzer<'a, S>
+where
+    S: Schema,
+{
+    pub(crate) fn new(schema: &'a S) -> Self {
+        Self {
+            schema,
+            views: Default::default(),
+        }
+    }
+
+    pub(crate) fn expression(&self, expression: &Expression) -> Result<SimpleRelationSchema> {
+        let mut result = self.relational(expression.expression())?;
+        if let Some(name) = expression.name() {
+            result.set_name(name.clone());
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn relational(&self, op: &RelationalOp) -> Result<SimpleRelationSchema> {
+        match op {
+            RelationalOp::Relation(v) => self.relation(v),
+            RelationalOp::SetOperation(v) => self.set_operation(v),
+            RelationalOp::Selection(v) => {
+                let rhs = self.relational(v.rhs())?;
+                let _ = prepare_term(v.criteria(), &rhs)?;
+                Ok(rhs)
+            }
+            RelationalOp::Projection(v) => {
+                let rhs = self.relational(v.rhs())?;
+                SimpleRelationSchema::new(
+                    rhs.name().clone(),
+                    v.attributes()
+                        .enumerate()
+                        .map(|(i, a)| {
+                            projected_attribute_schema(i, a, &rhs, &mut Patterns::default())
+                        })
+                        .collect::<Result<Vec<SimpleAttributeSchema>>>()?,
+                )
+            }
+            RelationalOp::Rename(v) => {
+                let mut rhs = self.relational(v.rhs())?;
+                rename_attributes(&mut rhs, v.renames())?;
+                Ok(rhs)
+            }
+            RelationalOp::Order(v) => {
+                let rhs = self.relational(v.rhs())?;
+                for key in v.keys() {
+                    let _ = attribute_to_index(key.attribute(), &rhs)?;
+                    let _ = collation(key.collation())?;
+                }
+                Ok(rhs)
+            }
+            RelationalOp::Group(v) => {
+                let rhs = self.relational(v.rhs())?;
+                let aggregates: Vec<Aggregate> = v.aggregates().cloned().collect();
+                group_schema(
+                    &rhs,
+                    &attribute_indices(&v.attributes().cloned().collect::<Vec<_>>(), &rhs)?,
+                    &aggregates,
+                )
+            }
+            RelationalOp::Join(Join::Natural(v)) => {
+                let lhs = self.relational(v.lhs())?;
+                let rhs = self.relational(v.rhs())?;
+                let common = common_attributes(&lhs, &rhs)?;
+                natural_join_schema(&lhs, &rhs, &common)
+            }
+            RelationalOp::Join(Join::Theta(v)) => {
+                let result =
+                    product_schema(&self.relational(v.lhs())?, &self.relational(v.rhs())?)?;
+                let _ = prepare_term(v.criteria(), &result)?;
+                Ok(result)
+            }
+            RelationalOp::Join(Join::Outer(v)) => {
+                let lhs = self.relational(v.lhs())?;
+                let rhs = self.relational(v.rhs())?;
+                match v.criteria() {
+                    None => {
+                        let common = common_attributes(&lhs, &rhs)?;
+                        outer_join_schema(&lhs, v.kind(), Some(&common), &rhs)
+                    }
+                    Some(criteria) => {
+                        let result = outer_join_schema(&lhs, v.kind(), None, &rhs)?;
+                        let _ = prepare_term(criteria, &result)?;
+                        Ok(result)
+                    }
+                }
+            }
+            RelationalOp::SemiJoin(v) => {
+                let (lhs, rhs) = self.semi_join_operands(v.lhs(), v.criteria(), v.rhs())?;
+                match v.kind() {
+                    SemiJoinKind::Left => Ok(lhs),
+                    SemiJoinKind::Right => Ok(rhs),
+                }
+            }
+            RelationalOp::AntiJoin(v) => {
+                let (lhs, _) = self.semi_join_operands(v.lhs(), v.criteria(), v.rhs())?;
+                Ok(lhs)
+            }
+            RelationalOp::Division(v) => {
+                let lhs = self.relational(v.lhs())?;
+                let (quotient, _) = division_attributes(&lhs, &self.relational(v.rhs())?)?;
+                division_schema(&lhs, &quotient)
+            }
+        }
+    }
+
+    fn semi_join_operands(
+        &self,
+        lhs: &RelationalOp,
+        criteria: Option<&Term>,
+        rhs: &RelationalOp,
+    ) -> Result<(SimpleRelationSchema, SimpleRelationSchema)> {
+        let lhs = self.relational(lhs)?;
+        let rhs = self.relational(rhs)?;
+        match criteria {
+            None => {
+                let _ = common_attributes(&lhs, &rhs)?;
+            }
+            Some(criteria) => {
+                let _ = prepare_term(criteria, &product_schema(&lhs, &rhs)?)?;
+            }
+        }
+        Ok((lhs, rhs))
+    }
+
+    fn relation(&self, name: &Name) -> Result<SimpleRelationSchema> {
+        if let Some(view) = self.views.get(name) {
+            Ok(view.clone())
+        } else {
+            self.schema
+                .relation(name)
+                .map(SimpleRelationSchema::from_schema)
+                .ok_or_else(|| relation_does_not_exist(name.clone()))
+        }
+    }
+
+    fn set_operation(&self, op: &SetOperation) -> Result<SimpleRelationSchema> {
+        let lhs = self.relational(op.lhs())?;
+        let rhs = self.relational(op.rhs())?;
+        if op.operator() == SetOperator::CartesianProduct {
+            product_schema(&lhs, &rhs)
+        } else {
+            check_union_compatible(&lhs, &rhs)?;
+            Ok(lhs)
+        }
+    }
+}
+
+impl<'a, S, D> Evaluator<'a, S, D>
+where
+    S: Schema,
+    D: Database,
+{
+    fn new(schema: &'a S, database: &'a D) -> Self {
+        Self {
+            schema,
+            database,
+            views: Default::default(),
+        }
+    }
+
+    fn expression(&self, expression: &Expression) -> Result<SimpleRelation> {
+        let mut result = self.relational(expression.expression())?;
+        if let Some(name) = expression.name() {
+            result.set_schema_name(name.clone());
+        }
+        Ok(result)
+    }
+
+    fn relational(&self, op: &RelationalOp) -> Result<SimpleRelation> {
+        match op {
+            RelationalOp::Relation(v) => self.relation(v),
+            RelationalOp::SetOperation(v) => self.set_operation(v),
+            RelationalOp::Selection(v) => self.selection(v),
+            RelationalOp::Projection(v) => self.projection(v),
+            RelationalOp::Rename(v) => self.rename(v),
+            RelationalOp::Order(v) => self.order(v),
+            RelationalOp::Group(v) => self.group(v),
+            RelationalOp::Join(Join::Natural(v)) => self.natural_join(v),
+            RelationalOp::Join(Join::Theta(v)) => self.theta_join(v),
+            RelationalOp::Join(Join::Outer(v)) => self.outer_join(v),
+            RelationalOp::SemiJoin(v) => self.semi_join(v),
+            RelationalOp::AntiJoin(v) => self.anti_join(v),
+            RelationalOp::Division(v) => self.division(v),
+        }
+    }
+
+    fn relation(&self, name: &Name) -> Result<SimpleRelation> {
+        if let Some(view) = self.views.get(name) {
+            return Ok(view.clone());
+        }
+        let declared = self
+            .schema
+            .relation(name)
+            .ok_or_else(|| relation_does_not_exist(name.clone()))?;
+        let relation = self
+            .database
+            .relation(name)
+            .ok_or_else(|| relation_does_not_exist(name.clone()))?;
+        let actual = relation.schema();
+        if actual.len() != declared.len() {
+            return Err(incompatible_arity(declared.len(), actual.len()));
+        }
+        for (declared, actual) in declared.attributes().zip(actual.attributes()) {
+            if declared.domain() != actual.domain() {
+                return Err(incompatible_types(*declared.domain(), *actual.domain()));
+            }
+        }
+        Ok(SimpleRelation::from_relation(relation))
+    }
+
+    fn set_operation(&self, op: &SetOperation) -> Result<SimpleRelation> {
+        let lhs = self.relational(op.lhs())?;
+        let rhs = self.relational(op.rhs())?;
+        match op.operator() {
+            SetOperator::Union => lhs.union(rhs),
+            SetOperator::Intersection => lhs.intersect(rhs),
+            SetOperator::Difference => lhs.difference(rhs),
+            SetOperator::SymmetricDifference => {
+                let (lhs_only, rhs_only) =
+                    (lhs.clone().difference(rhs.clone())?, rhs.difference(lhs)?);
+                lhs_only.union(rhs_only)
+            }
+            SetOperator::CartesianProduct => lhs.cartesian_product(rhs),
+        }
+    }
+
+    fn selection(&self, op: &Selection) -> Result<SimpleRelation> {
+        self.relational(op.rhs())?.select(op.criteria().clone())
+    }
+
+    fn projection(&self, op: &Projection) -> Result<SimpleRelation> {
+        let attributes: Vec<ProjectedAttribute> = op.attributes().cloned().collect();
+        self.relational(op.rhs())?.extended_project(&attributes)
+    }
+
+    fn rename(&self, op: &Rename) -> Result<SimpleRelation> {
+        self.relational(op.rhs())?.rename_all(
+            op.renames()
+                .map(|(attribute, name)| (attribute.clone(), name.clone()))
+                .collect(),
+        )
+    }
+
+    fn order(&self, op: &Order) -> Result<SimpleRelation> {
+        let keys: Vec<SortKey> = op.keys().cloned().collect();
+        self.relational(op.rhs())?.sort(&keys)
+    }
+
+    fn group(&self, op: &Group) -> Result<SimpleRelation> {
+        let attributes: Vec<Attribute> = op.attributes().cloned().collect();
+        let aggregates: Vec<Aggregate> = op.aggregates().cloned().collect();
+        self.relational(op.rhs())?.group(&attributes, &aggregates)
+    }
+
+    fn natural_join(&self, op: &NaturalJoin) -> Result<SimpleRelation> {
+        self.relational(op.lhs())?
+            .natural_join(self.relational(op.rhs())?)
+    }
+
+    fn theta_join(&self, op: &ThetaJoin) -> Result<SimpleRelation> {
+        self.relational(op.lhs())?
+            .theta_join(op.criteria().clone(), self.relational(op.rhs())?)
+    }
+
+    fn outer_join(&self, op: &OuterJoin) -> Result<SimpleRelation> {
+        let lhs = self.relational(op.lhs())?;
+        let rhs = self.relational(op.rhs())?;
+        let criteria = op.criteria().cloned();
+        match op.kind() {
+            OuterJoinKind::Left => lhs.left_outer_join(criteria, rhs),
+            OuterJoinKind::Right => lhs.right_outer_join(criteria, rhs),
+            OuterJoinKind::Full => lhs.full_outer_join(criteria, rhs),
+        }
+    }
+
+    fn semi_join(&self, op: &SemiJoin) -> Result<SimpleRelation> {
+        let lhs = self.relational(op.lhs())?;
+        let rhs = self.relational(op.rhs())?;
+        let criteria = op.criteria().cloned();
+        match op.kind() {
+            SemiJoinKind::Left => lhs.left_semi_join(criteria, rhs),
+            SemiJoinKind::Right => lhs.right_semi_join(criteria, rhs),
+        }
+    }
+
+    fn anti_join(&self, op: &AntiJoin) -> Result<SimpleRelation> {
+        self.relational(op.lhs())?
+            .anti_join(op.criteria().cloned(), self.relational(op.rhs())?)
+    }
+
+    fn division(&self, op: &Division) -> Result<SimpleRelation> {
+        self.relational(op.lhs())?
+            .divide(self.relational(op.rhs())?)
+    }
+}
 
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+fn from_simple_schema<R>(schema: &SimpleRelationSchema) -> Result<R>
+where
+    R: RelationSchema,
+{
+    R::new(
+        schema.name().clone(),
+        schema
+            .attributes()
+            .map(|a| R::Item::new(a.name().clone(), *a.domain()).with_nullable(a.is_nullable())),
+    )
+}
+
+///
+/// Check `term` against `schema`, returning it prepared for evaluation; any constant pattern of a
+/// string match is compiled now, so that an invalid one is reported before any tuple is
+/// evaluated, and is then used for every tuple.
+///
+pub(crate) fn prepare_term<'a>(
+    term: &'a Term,
+    schema: &SimpleRelationSchema,
+) -> Result<Predicate<'a>> {
+    let mut patterns = Patterns::default();
+    check_term(term, schema, &mut patterns)?;
+    Ok(Predicate::new(term, patterns))
+}
+
+///
+/// Check `term` against `schema`, adding the compiled pattern of any string match with a
+/// constant pattern, including those within its scalar expressions, to `patterns`.
+///
+fn check_term(term: &Term, schema: &SimpleRelationSchema, patterns: &mut Patterns) -> Result<()> {
+    match term {
+        Term::Constant(Value::Boolean(_) | Value::Null) => {}
+        Term::Constant(v) => return Err(incompatible_types(Domain::Boolean, v.data_type())),
+        Term::Exists(attribute) | Term::IsNull(attribute) | Term::IsNotNull(attribute) => {
+            let _ = attribute_to_index(attribute, schema)?;
+        }
+        Term::Atom(atom) => {
+            let (lhs, _) = analyze_scalar(atom.lhs(), schema, patterns)?;
+            let (rhs, _) = analyze_scalar(atom.rhs(), schema, patterns)?;
+            check_comparison(lhs, atom.operator(), rhs)?;
+            if let Some(Value::String(pattern)) = atom.rhs().as_constant() {
+                if atom.operator().is_pattern_match() {
+                    patterns.add(atom.operator(), pattern)?;
+                }
+            }
+        }
+        Term::In(value, list) => {
+            let (value, _) = analyze_scalar(value, schema, patterns)?;
+            for item in list {
+                let (item, _) = analyze_scalar(item, schema, patterns)?;
+                check_comparison(value, ComparisonOperator::Equal, item)?;
+            }
+        }
+        Term::Between(value, low, high) => {
+            let (value, _) = analyze_scalar(value, schema, patterns)?;
+            for bound in [low, high] {
+                let (bound, _) = analyze_scalar(bound, schema, patterns)?;
+                check_comparison(value, ComparisonOperator::LessThanOrEqual, bound)?;
+            }
+        }
+        Term::Negate(v) => check_term(v, schema, patterns)?,
+        Term::And(lhs, rhs) | Term::Or(lhs, rhs) => {
+            check_term(lhs, schema, patterns)?;
+            check_term(rhs, schema, patterns)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_comparison(lhs: Domain, op: ComparisonOperator, rhs: Domain) -> Result<()> {
+    match op {
+        _ if op.is_pattern_match() => {
+            if !matches!(lhs, Domain::String | Domain::Char) {
+                Err(incompatible_types(Domain::String, lhs))
+            } else if rhs != Domain::String {
+                Err(incompatible_types(Domain::String, rhs))
+            } else {
+                Ok(())
+            }
+        }
+        _ if lhs == rhs || (lhs.is_numeric() && rhs.is_numeric()) => Ok(()),
+        _ if matches!(
+            (lhs, rhs),
+            (Domain::Date, Domain::Timestamp) | (Domain::Timestamp, Domain::Date)
+        ) =>
+        {
+            Ok(())
+        }
+        _ if lhs == Domain::Null || rhs == Domain::Null => Ok(()),
+        _ => Err(incompatible_types(lhs, rhs)),
+    }
+}
+
+///
+/// Returns the schema of the `projected` attribute at `index`, adding the compiled pattern of any
+/// string match within a computed attribute to `patterns`.
+///
+pub(crate) fn projected_attribute_schema(
+    index: usize,
+    projected: &ProjectedAttribute,
+    schema: &SimpleRelationSchema,
+    patterns: &mut Patterns,
+) -> Result<SimpleAttributeSchema> {
+    let attribute = match projected {
+        ProjectedAttribute::Index(v) => Attribute::Index(*v),
+        ProjectedAttribute::Name(v) => Attribute::Name(v.clone()),
+        ProjectedAttribute::Constant(v) => return Ok(constant_attribute_schema(index, v)),
+        ProjectedAttribute::Computed(v, name) => {
+            let (domain, nullable) = analyze_scalar(v, schema, patterns)?;
+            return Ok(SimpleAttributeSchema::new(
+                name.clone()
+                    .unwrap_or_else(|| constant_attribute_name(index)),
+                domain,
+            )
+            .with_nullable(nullable));
+        }
+    };
+    Ok(schema
+        .attribute(attribute_to_index(&attribute, schema)?)
+        .unwrap()
+        .clone())
+}
+
+pub(crate) fn attribute_to_index<R>(attribute: &Attribute, schema: &R) -> Result<usize>
+where
+    R: RelationSchema,
+{
+    match attribute {
+        Attribute::Index(index) if *index < schema.len() => Ok(*index),
+        Attribute::Index(index) => Err(attribute_index_invalid(*index)),
+        Attribute::Name(name) => {
+            let mut indices = schema.attributes().enumerate().filter_map(|(i, a)| {
+                if a.name() == name {
+                    Some(i)
+                } else {
+                    None
+                }
+            });
+            match (indices.next(), indices.next()) {
+                (Some(index), None) => Ok(index),
+                (Some(_), Some(_)) => Err(ambiguous_attribute(name.clone())),
+                (None, _) => Err(attribute_does_not_exist(name.clone())),
+            }
+        }
+    }
+}
+
+fn attribute_value<'a, R, T>(attribute: &Attribute, schema: &R, tuple: &'a T) -> Result<&'a Value>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    let index = attribute_to_index(attribute, schema)?;
+    tuple
+        .value(index)
+        .ok_or_else(|| attribute_index_invalid(index))
+}
+
+///
+/// Evaluate the criteria in `term` for `tuple`, as [`evaluate_predicate`], using the compiled
+/// `patterns` for any string match with a constant pattern.
+///
+pub(crate) fn predicate_value<R, T>(
+    term: &Term,
+    schema: &R,
+    tuple: &T,
+    patterns: &Patterns,
+) -> Result<Option<bool>>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    match term {
+        Term::Constant(Value::Boolean(v)) => Ok(Some(*v)),
+        Term::Constant(Value::Null) => Ok(None),
+        Term::Constant(v) => Err(incompatible_types(Domain::Boolean, v.data_type())),
+        Term::Exists(attribute) | Term::IsNotNull(attribute) => {
+            Ok(Some(!attribute_value(attribute, schema, tuple)?.is_null()))
+        }
+        Term::IsNull(attribute) => Ok(Some(attribute_value(attribute, schema, tuple)?.is_null())),
+        Term::Atom(atom) => {
+            let lhs = scalar_value(atom.lhs(), schema, tuple, patterns)?;
+            let rhs = scalar_value(atom.rhs(), schema, tuple, patterns)?;
+            compare_with(&lhs, atom.operator(), &rhs, patterns)
+        }
+        Term::In(value, list) => {
+            // unknown, rather than false, if there is no match but any comparison is unknown.
+            let value = scalar_value(value, schema, tuple, patterns)?;
+            let mut result = Some(false);
+            for item in list {
+                let item = scalar_value(item, schema, tuple, patterns)?;
+                match compare_with(&value, ComparisonOperator::Equal, &item, patterns)? {
+                    Some(true) => return Ok(Some(true)),
+                    Some(false) => {}
+                    None => result = None,
+                }
+            }
+            Ok(result)
+        }
+        Term::Between(value, low, high) => {
+            let value = scalar_value(value, schema, tuple, patterns)?;
+            let low = scalar_value(low, schema, tuple, patterns)?;
+            let high = scalar_value(high, schema, tuple, patterns)?;
+            Ok(
+                match (
+                    compare_with(
+                        &value,
+                        ComparisonOperator::GreaterThanOrEqual,
+                        &low,
+                        patterns,
+                    )?,
+                    compare_with(&value, ComparisonOperator::LessThanOrEqual, &high, patterns)?,
+                ) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                },
+            )
+        }
+        Term::Negate(v) => Ok(predicate_value(v, schema, tuple, patterns)?.map(|v| !v)),
+        Term::And(lhs, rhs) => {
+            and_predicates(predicate_value(lhs, schema, tuple, patterns)?, || {
+                predicate_value(rhs, schema, tuple, patterns)
+            })
+        }
+        Term::Or(lhs, rhs) => or_predicates(predicate_value(lhs, schema, tuple, patterns)?, || {
+            predicate_value(rhs, schema, tuple, patterns)
+        }),
+    }
+}
+
+fn compare_with(
+    lhs: &Value,
+    op: ComparisonOperator,
+    rhs: &Value,
+    patterns: &Patterns,
+) -> Result<Option<bool>> {
+    if lhs.is_null() || rhs.is_null() {
+        // any comparison with a null is unknown.
+        return Ok(None);
+    }
+    Ok(Some(match op {
+        ComparisonOperator::Equal => compare_values(lhs, rhs)? == Ordering::Equal,
+        ComparisonOperator::NotEqual => compare_values(lhs, rhs)? != Ordering::Equal,
+        ComparisonOperator::LessThan => compare_values(lhs, rhs)? == Ordering::Less,
+        ComparisonOperator::LessThanOrEqual => compare_values(lhs, rhs)? != Ordering::Greater,
+        ComparisonOperator::GreaterThan => compare_values(lhs, rhs)? == Ordering::Greater,
+        ComparisonOperator::GreaterThanOrEqual => compare_values(lhs, rhs)? != Ordering::Less,
+        _ => string_match(lhs, op, rhs, patterns)?,
+    }))
+}
+
+fn string_match(
+    lhs: &Value,
+    op: ComparisonOperator,
+    rhs: &Value,
+    patterns: &Patterns,
+) -> Result<bool> {
+    match rhs {
+        Value::String(v) => match patterns.get(op, v) {
+            Some(regex) => pattern_matches(lhs, op, regex),
+            None => pattern_matches(lhs, op, &compile_pattern(op, v)?),
+        },
+        _ => Err(incompatible_types(Domain::String, rhs.data_type())),
+    }
+}
+
+///
+/// Returns `true` if `value` satisfies the string match `op` with the compiled pattern `regex`,
+/// that is if it matches, or for a negated operator does not.
+///
+fn pattern_matches(value: &Value, op: ComparisonOperator, regex: &Regex) -> Result<bool> {
+    let matched = match value {
+        Value::String(v) => regex.is_match(v),
+        Value::Char(v) => regex.is_match(&v.to_string()),
+        _ => return Err(incompatible_types(Domain::String, value.data_type())),
+    };
+    Ok(match op {
+        ComparisonOperator::StringNotMatch
+        | ComparisonOperator::NotLike
+        | ComparisonOperator::NotILike => !matched,
+        _ => matched,
+    })
+}
+
+///
+/// The three-valued conjunction of `lhs` and `rhs`, which is only evaluated if `lhs` is not
+/// false.
+///
+fn and_predicates<F>(lhs: Option<bool>, rhs: F) -> Result<Option<bool>>
+where
+    F: FnOnce() -> Result<Option<bool>>,
+{
+    Ok(match lhs {
+        Some(false) => Some(false),
+        lhs => match (lhs, rhs()?) {
+            (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+    })
+}
+
+///
+/// The three-valued disjunction of `lhs` and `rhs`, which is only evaluated if `lhs` is not true.
+///
+fn or_predicates<F>(lhs: Option<bool>, rhs: F) -> Result<Option<bool>>
+where
+    F: FnOnce() -> Result<Option<bool>>,
+{
+    Ok(match lhs {
+        Some(true) => Some(true),
+        lhs => match (lhs, rhs()?) {
+            (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+    })
+}
+
+pub(crate) fn numeric_as_integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Byte(v) => Some(*v as i128),
+        Value::UnsignedInteger(v) => Some(*v as i128),
+        Value::Integer(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+pub(crate) fn numeric_as_float(value: &Value) -> f64 {
+    match value {
+        Value::Byte(v) => *v as f64,
+        Value::UnsignedInteger(v) => *v as f64,
+        Value::Integer(v) => *v as f64,
+        Value::Float(v) => *v,
+        Value::Decimal(v) => v.to_f64().unwrap_or(f64::NAN),
+        _ => unreachable!(),
+    }
+}
+
+///
+/// Returns a numeric value other than a float as a decimal.
+///
+pub(crate) fn numeric_as_decimal(value: &Value) -> Decimal {
+    match value {
+        Value::Decimal(v) => *v,
+        value => Decimal::from_i128_with_scale(numeric_as_integer(value).unwrap(), 0),
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
+
+pub mod ops;
+
+mod pattern;
+use self::pattern::compile_pattern;
+pub(crate) use self::pattern::Patterns;
+
+mod predicate;
+pub(crate) use self::predicate::Predicate;
+
+mod scalar;
+use self::scalar::analyze_scalar;
+pub(crate) use self::scalar::scalar_value;
@@ -1,20 +1,21 @@
 /*!
+Provides a set of traits, one per relational operator, which allow a relation implementation to
+be used directly without building an expression first.
 
-*/
+The [`crate::simple::data::SimpleRelation`] type implements all of these traits.
 
-use crate::{ast::Term, Name};
+ */
+
+use crate::{
+    ast::{Aggregate, Attribute, SortKey, Term},
+    Name,
+};
 use std::collections::HashMap;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types & Constants
 // ------------------------------------------------------------------------------------------------
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum Attribute {
-    Index(usize),
-    Name(Box<Name>),
-}
-
 #[doc(alias = "∩")]
 pub trait Intersect<Rhs = Self> {
     type Output;
@@ -61,14 +62,14 @@ pub trait Project {
 pub trait Sort {
     type Output;
 
-    fn sort(self, attributes: &[Attribute]) -> Self::Output;
+    fn sort(self, keys: &[SortKey]) -> Self::Output;
 }
 
 #[doc(alias = "γ")]
 pub trait Group {
     type Output;
 
-    fn group(self, attributes: &[Attribute]) -> Self::Output;
+    fn group(self, attributes: &[Attribute], aggregates: &[Aggregate]) -> Self::Output;
 }
 
 #[doc(alias = "⨝")]
@@ -85,11 +86,64 @@ pub trait ThetaJoin<Rhs = Self> {
     fn theta_join(self, criteria: Term, rhs: Rhs) -> Self::Output;
 }
 
+///
+/// Outer joins, natural if `criteria` is `None`, which pad any unmatched tuple with null values
+/// for the attributes of the other operand.
+///
+#[doc(alias = "⟕")]
+#[doc(alias = "⟖")]
+#[doc(alias = "⟗")]
+pub trait OuterJoin<Rhs = Self> {
+    type Output;
+
+    fn left_outer_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+
+    fn right_outer_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+
+    fn full_outer_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+}
+
+///
+/// Semi-joins, natural if `criteria` is `None`, which result in the tuples of one operand that
+/// match any tuple of the other.
+///
+#[doc(alias = "⋉")]
+#[doc(alias = "⋊")]
+pub trait SemiJoin<Rhs = Self> {
+    type Output;
+
+    fn left_semi_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+
+    fn right_semi_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+}
+
+///
+/// An anti-join, natural if `criteria` is `None`, which results in the tuples of the left-hand
+/// operand that match no tuple of the right-hand operand.
+///
+#[doc(alias = "▷")]
+pub trait AntiJoin<Rhs = Self> {
+    type Output;
+
+    fn anti_join(self, criteria: Option<Term>, rhs: Rhs) -> Self::Output;
+}
+
+///
+/// Division, which results in the tuples over the attributes of `self` not in `rhs` that appear
+/// in `self` combined with every tuple of `rhs`.
+///
+#[doc(alias = "÷")]
+pub trait Divide<Rhs = Self> {
+    type Output;
+
+    fn divide(self, rhs: Rhs) -> Self::Output;
+}
+
 #[doc(alias = "ρ")]
 pub trait Rename<Rhs = Self> {
     type Output;
 
-    fn rename(self, source: Attribute, target: Box<Name>) -> Self::Output;
+    fn rename(self, source: Attribute, target: Name) -> Self::Output;
 
-    fn rename_all(self, names: HashMap<Attribute, Box<Name>>) -> Self::Output;
+    fn rename_all(self, names: HashMap<Attribute, Name>) -> Self::Output;
 }
@@ -0,0 +1,92 @@
+/*!
+Provides the compilation of the patterns used by string matches, `~` and `like`, into regular
+expressions, and the set of those compiled for the constant patterns of a term or expression.
+ */
+
+use crate::{
+    ast::ComparisonOperator,
+    error::{invalid_pattern, Result},
+};
+use regex::Regex;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The compiled regular expressions for the constant patterns of string matches, collected as
+/// terms and scalar expressions are analyzed; each distinct pattern is compiled once, and is then
+/// used for every tuple.
+///
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Patterns {
+    compiled: Vec<(ComparisonOperator, String, Regex)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the compiled regular expression for `pattern` as used by the string match `op`; an
+/// SQL pattern, for `like` or `ilike`, is first translated into an anchored regular expression.
+///
+pub(crate) fn compile_pattern(op: ComparisonOperator, pattern: &str) -> Result<Regex> {
+    let regex = match op {
+        ComparisonOperator::Like | ComparisonOperator::NotLike => like_to_regex(pattern, false),
+        ComparisonOperator::ILike | ComparisonOperator::NotILike => like_to_regex(pattern, true),
+        _ => pattern.to_string(),
+    };
+    Regex::new(&regex).map_err(|_| invalid_pattern(pattern))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Patterns {
+    ///
+    /// Compile `pattern`, as used by the string match `op`, unless it has been already.
+    ///
+    pub(crate) fn add(&mut self, op: ComparisonOperator, pattern: &str) -> Result<()> {
+        if self.get(op, pattern).is_none() {
+            let regex = compile_pattern(op, pattern)?;
+            self.compiled.push((op, pattern.to_string(), regex));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the compiled regular expression for `pattern`, as used by the string match `op`,
+    /// if it has been added.
+    ///
+    pub(crate) fn get(&self, op: ComparisonOperator, pattern: &str) -> Option<&Regex> {
+        self.compiled
+            .iter()
+            .find(|(o, p, _)| *o == op && p == pattern)
+            .map(|(_, _, regex)| regex)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Translate an SQL pattern, where `%` matches any sequence of characters, `_` any single
+/// character, and `\` escapes the character following it, into a regular expression.
+///
+fn like_to_regex(pattern: &str, ignore_case: bool) -> String {
+    let mut regex = String::from(if ignore_case { "(?is)^" } else { "(?s)^" });
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '\\' => regex.push_str(&regex::escape(&chars.next().unwrap_or(c).to_string())),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
@@ -0,0 +1,56 @@
+/*!
+Provides a term prepared for evaluation against the tuples of a relation; each string match with
+a constant pattern, wherever it appears in the term, has its regular expression compiled when the
+term is analyzed, so that the pattern is not compiled again for each tuple.
+ */
+
+use super::{pattern::Patterns, predicate_value};
+use crate::{ast::Term, data::Tuple, error::Result, sort::RelationSchema};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A term prepared for evaluation, see [`prepare_term`](super::prepare_term), with the compiled
+/// patterns of its string matches.
+///
+#[derive(Clone, Debug)]
+pub(crate) struct Predicate<'a> {
+    term: &'a Term,
+    patterns: Patterns,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Predicate<'a> {
+    pub(crate) fn new(term: &'a Term, patterns: Patterns) -> Self {
+        Self { term, patterns }
+    }
+
+    ///
+    /// Determine whether `tuple`, conforming to `schema`, satisfies this predicate, as
+    /// [`evaluate_term`](super::evaluate_term).
+    ///
+    pub(crate) fn is_satisfied<R, T>(&self, schema: &R, tuple: &T) -> Result<bool>
+    where
+        R: RelationSchema,
+        T: Tuple,
+    {
+        Ok(self.evaluate(schema, tuple)? == Some(true))
+    }
+
+    ///
+    /// Evaluate this predicate for `tuple`, conforming to `schema`, as
+    /// [`evaluate_predicate`](super::evaluate_predicate).
+    ///
+    pub(crate) fn evaluate<R, T>(&self, schema: &R, tuple: &T) -> Result<Option<bool>>
+    where
+        R: RelationSchema,
+        T: Tuple,
+    {
+        predicate_value(self.term, schema, tuple, &self.patterns)
+    }
+}
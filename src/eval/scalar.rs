@@ -0,0 +1,571 @@
+/*!
+Provides the analysis and evaluation of scalar expressions, as used in the terms of a selection
+and in the computed attributes of a projection.
+ */
+
+use super::{attribute_to_index, attribute_value, check_term, predicate_value, Patterns};
+use super::{numeric_as_decimal, numeric_as_float, numeric_as_integer};
+use crate::{
+    ast::{ScalarExpression, ScalarFunction, ScalarOperator},
+    data::{Date, Interval, PrimitiveDateTime, Tuple, Value},
+    error::{
+        arithmetic_overflow, incompatible_types, invalid_function_arguments, invalid_value, Result,
+    },
+    simple::sort::SimpleRelationSchema,
+    sort::{AttributeSchema, Domain, RelationSchema},
+};
+use rust_decimal::RoundingStrategy;
+use time::{Duration, Month};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Evaluate the scalar `expression` for `tuple`, conforming to `schema`. Any operation on, or
+/// function of, a null value results in null, except for `coalesce`.
+///
+/// Any pattern of a string match within a `case` is compiled for each call, as by
+/// [`evaluate_predicate`](super::evaluate_predicate).
+///
+pub fn evaluate_scalar<R, T>(expression: &ScalarExpression, schema: &R, tuple: &T) -> Result<Value>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    scalar_value(expression, schema, tuple, &Patterns::default())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Evaluate the scalar `expression` for `tuple`, as [`evaluate_scalar`], using the compiled
+/// `patterns` for any string match with a constant pattern.
+///
+pub(crate) fn scalar_value<R, T>(
+    expression: &ScalarExpression,
+    schema: &R,
+    tuple: &T,
+    patterns: &Patterns,
+) -> Result<Value>
+where
+    R: RelationSchema,
+    T: Tuple,
+{
+    match expression {
+        ScalarExpression::Attribute(attribute) => {
+            Ok(attribute_value(attribute, schema, tuple)?.clone())
+        }
+        ScalarExpression::Constant(value) => Ok(value.clone()),
+        ScalarExpression::Operation(lhs, op, rhs) => {
+            let lhs = scalar_value(lhs, schema, tuple, patterns)?;
+            let rhs = scalar_value(rhs, schema, tuple, patterns)?;
+            if lhs.is_null() || rhs.is_null() {
+                Ok(Value::Null)
+            } else {
+                operation_value(lhs, *op, rhs)
+            }
+        }
+        ScalarExpression::Function(function, arguments) => {
+            if !function.accepts(arguments.len()) {
+                return Err(invalid_function_arguments(
+                    function.to_string(),
+                    arguments.len(),
+                ));
+            }
+            let mut values: Vec<Value> = Default::default();
+            for argument in arguments {
+                let value = scalar_value(argument, schema, tuple, patterns)?;
+                if *function == ScalarFunction::Coalesce && !value.is_null() {
+                    return Ok(value);
+                }
+                values.push(value);
+            }
+            if values.iter().any(Value::is_null) {
+                Ok(Value::Null)
+            } else {
+                function_value(*function, values)
+            }
+        }
+        ScalarExpression::Case(branches, otherwise) => {
+            for (condition, value) in branches {
+                if predicate_value(condition, schema, tuple, patterns)? == Some(true) {
+                    return scalar_value(value, schema, tuple, patterns);
+                }
+            }
+            match otherwise {
+                None => Ok(Value::Null),
+                Some(otherwise) => scalar_value(otherwise, schema, tuple, patterns),
+            }
+        }
+    }
+}
+
+///
+/// Returns the domain of the values of `expression` and whether any of them may be null, or an
+/// error if it references an attribute not in `schema` or combines values of incompatible
+/// types. The compiled pattern of any string match within a `case` is added to `patterns`.
+///
+pub(crate) fn analyze_scalar(
+    expression: &ScalarExpression,
+    schema: &SimpleRelationSchema,
+    patterns: &mut Patterns,
+) -> Result<(Domain, bool)> {
+    match expression {
+        ScalarExpression::Attribute(attribute) => {
+            let attribute = schema
+                .attribute(attribute_to_index(attribute, schema)?)
+                .unwrap();
+            Ok((*attribute.domain(), attribute.is_nullable()))
+        }
+        ScalarExpression::Constant(value) => Ok((value.data_type(), value.is_null())),
+        ScalarExpression::Operation(lhs, op, rhs) => {
+            let (lhs, lhs_nullable) = analyze_scalar(lhs, schema, patterns)?;
+            let (rhs, rhs_nullable) = analyze_scalar(rhs, schema, patterns)?;
+            Ok((
+                operation_domain(lhs, *op, rhs)?,
+                lhs_nullable || rhs_nullable,
+            ))
+        }
+        ScalarExpression::Function(function, arguments) => {
+            if !function.accepts(arguments.len()) {
+                return Err(invalid_function_arguments(
+                    function.to_string(),
+                    arguments.len(),
+                ));
+            }
+            let arguments = arguments
+                .iter()
+                .map(|argument| analyze_scalar(argument, schema, patterns))
+                .collect::<Result<Vec<(Domain, bool)>>>()?;
+            let nullable = if *function == ScalarFunction::Coalesce {
+                arguments.iter().all(|(_, nullable)| *nullable)
+            } else {
+                arguments.iter().any(|(_, nullable)| *nullable)
+            };
+            let domains: Vec<Domain> = arguments.into_iter().map(|(domain, _)| domain).collect();
+            Ok((function_domain(*function, &domains)?, nullable))
+        }
+        ScalarExpression::Case(branches, otherwise) => {
+            let mut result = Domain::Null;
+            let mut nullable = otherwise.is_none();
+            for (condition, value) in branches {
+                check_term(condition, schema, patterns)?;
+                let (domain, value_nullable) = analyze_scalar(value, schema, patterns)?;
+                result = common_domain(result, domain)?;
+                nullable |= value_nullable;
+            }
+            if let Some(otherwise) = otherwise {
+                let (domain, otherwise_nullable) = analyze_scalar(otherwise, schema, patterns)?;
+                result = common_domain(result, domain)?;
+                nullable |= otherwise_nullable;
+            }
+            Ok((result, nullable))
+        }
+    }
+}
+
+fn operation_domain(lhs: Domain, op: ScalarOperator, rhs: Domain) -> Result<Domain> {
+    match (lhs, op, rhs) {
+        (Domain::Null, _, Domain::Null) => Ok(Domain::Null),
+        (Domain::Null, ScalarOperator::Concatenate, _)
+        | (_, ScalarOperator::Concatenate, Domain::Null) => Ok(Domain::String),
+        (Domain::Null, _, other) | (other, _, Domain::Null) => Ok(other),
+        (
+            Domain::String | Domain::Char,
+            ScalarOperator::Concatenate,
+            Domain::String | Domain::Char,
+        ) => Ok(Domain::String),
+        (_, ScalarOperator::Concatenate, _) => Err(incompatible_types(
+            Domain::String,
+            if matches!(lhs, Domain::String | Domain::Char) {
+                rhs
+            } else {
+                lhs
+            },
+        )),
+        (l, _, r) if l.is_numeric() && r.is_numeric() => Ok(numeric_domain(l, r)),
+        (Domain::Date, ScalarOperator::Add | ScalarOperator::Subtract, r) if is_integral(r) => {
+            Ok(Domain::Date)
+        }
+        (l, ScalarOperator::Add, Domain::Date) if is_integral(l) => Ok(Domain::Date),
+        (Domain::Date, ScalarOperator::Subtract, Domain::Date) => Ok(Domain::Integer),
+        (Domain::Date, ScalarOperator::Add | ScalarOperator::Subtract, Domain::Interval)
+        | (Domain::Interval, ScalarOperator::Add, Domain::Date) => Ok(Domain::Timestamp),
+        (
+            Domain::Time | Domain::Timestamp | Domain::TimestampTz | Domain::Interval,
+            ScalarOperator::Add | ScalarOperator::Subtract,
+            Domain::Interval,
+        ) => Ok(lhs),
+        (
+            Domain::Interval,
+            ScalarOperator::Add,
+            Domain::Time | Domain::Timestamp | Domain::TimestampTz,
+        ) => Ok(rhs),
+        (Domain::Timestamp, ScalarOperator::Subtract, Domain::Timestamp)
+        | (Domain::TimestampTz, ScalarOperator::Subtract, Domain::TimestampTz) => {
+            Ok(Domain::Interval)
+        }
+        _ => Err(incompatible_types(lhs, rhs)),
+    }
+}
+
+fn function_domain(function: ScalarFunction, arguments: &[Domain]) -> Result<Domain> {
+    let argument = arguments[0];
+    match function {
+        ScalarFunction::Upper | ScalarFunction::Lower => {
+            expect_string(argument)?;
+            Ok(Domain::String)
+        }
+        ScalarFunction::Length => match argument {
+            Domain::String | Domain::Char | Domain::Binary | Domain::Null => Ok(Domain::Integer),
+            _ => Err(incompatible_types(Domain::String, argument)),
+        },
+        ScalarFunction::Substring => {
+            expect_string(argument)?;
+            for argument in &arguments[1..] {
+                expect_integral(*argument)?;
+            }
+            Ok(Domain::String)
+        }
+        ScalarFunction::Absolute if argument.is_numeric() || argument == Domain::Interval => {
+            Ok(argument)
+        }
+        ScalarFunction::Round if argument.is_numeric() => {
+            for argument in &arguments[1..] {
+                expect_integral(*argument)?;
+            }
+            Ok(argument)
+        }
+        ScalarFunction::Absolute | ScalarFunction::Round if argument == Domain::Null => {
+            Ok(Domain::Null)
+        }
+        ScalarFunction::Absolute | ScalarFunction::Round => {
+            Err(incompatible_types(Domain::Float, argument))
+        }
+        ScalarFunction::Coalesce => arguments.iter().try_fold(Domain::Null, |result, domain| {
+            common_domain(result, *domain)
+        }),
+    }
+}
+
+///
+/// The domain of an arithmetic operation between two numbers; a float if either is a float,
+/// else a decimal if either is a decimal, else the wider of the two integer domains.
+///
+fn numeric_domain(lhs: Domain, rhs: Domain) -> Domain {
+    if lhs == Domain::Float || rhs == Domain::Float {
+        Domain::Float
+    } else if lhs == Domain::Decimal || rhs == Domain::Decimal {
+        Domain::Decimal
+    } else if lhs == Domain::Integer || rhs == Domain::Integer {
+        Domain::Integer
+    } else if lhs == Domain::UnsignedInteger || rhs == Domain::UnsignedInteger {
+        Domain::UnsignedInteger
+    } else {
+        Domain::Byte
+    }
+}
+
+fn common_domain(lhs: Domain, rhs: Domain) -> Result<Domain> {
+    match (lhs, rhs) {
+        (Domain::Null, other) | (other, Domain::Null) => Ok(other),
+        (l, r) if l == r => Ok(l),
+        (l, r) => Err(incompatible_types(l, r)),
+    }
+}
+
+#[inline]
+fn is_integral(domain: Domain) -> bool {
+    matches!(
+        domain,
+        Domain::Byte | Domain::UnsignedInteger | Domain::Integer
+    )
+}
+
+#[inline]
+fn expect_integral(domain: Domain) -> Result<()> {
+    if is_integral(domain) || domain == Domain::Null {
+        Ok(())
+    } else {
+        Err(incompatible_types(Domain::Integer, domain))
+    }
+}
+
+#[inline]
+fn expect_string(domain: Domain) -> Result<()> {
+    match domain {
+        Domain::String | Domain::Char | Domain::Null => Ok(()),
+        _ => Err(incompatible_types(Domain::String, domain)),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn operation_value(lhs: Value, op: ScalarOperator, rhs: Value) -> Result<Value> {
+    let overflow = || {
+        arithmetic_overflow(ScalarExpression::operation(lhs.clone(), op, rhs.clone()).to_string())
+    };
+    let (l, r) = (lhs.data_type(), rhs.data_type());
+    let result = match (&lhs, op, &rhs) {
+        (_, ScalarOperator::Concatenate, _) => {
+            let mut result = string_value(&lhs)?;
+            result.push_str(&string_value(&rhs)?);
+            Some(Value::String(result))
+        }
+        _ if l.is_numeric() && r.is_numeric() => match numeric_domain(l, r) {
+            Domain::Float => Some(Value::Float(float_operation(
+                numeric_as_float(&lhs),
+                op,
+                numeric_as_float(&rhs),
+            ))),
+            Domain::Decimal => {
+                let (l, r) = (numeric_as_decimal(&lhs), numeric_as_decimal(&rhs));
+                match op {
+                    ScalarOperator::Add => l.checked_add(r),
+                    ScalarOperator::Subtract => l.checked_sub(r),
+                    ScalarOperator::Multiply => l.checked_mul(r),
+                    ScalarOperator::Divide => l.checked_div(r),
+                    ScalarOperator::Modulo => l.checked_rem(r),
+                    ScalarOperator::Concatenate => unreachable!(),
+                }
+                .map(Value::Decimal)
+            }
+            domain => {
+                let l = numeric_as_integer(&lhs).unwrap();
+                let r = numeric_as_integer(&rhs).unwrap();
+                match op {
+                    ScalarOperator::Add => l.checked_add(r),
+                    ScalarOperator::Subtract => l.checked_sub(r),
+                    ScalarOperator::Multiply => l.checked_mul(r),
+                    ScalarOperator::Divide => l.checked_div(r),
+                    ScalarOperator::Modulo => l.checked_rem(r),
+                    ScalarOperator::Concatenate => unreachable!(),
+                }
+                .and_then(|v| integer_value(domain, v))
+            }
+        },
+        (Value::Date(d), ScalarOperator::Add | ScalarOperator::Subtract, n) if is_integral(r) => {
+            let days = signed_days(op, numeric_as_integer(n).unwrap());
+            days.and_then(|days| d.checked_add(Duration::days(days)))
+                .map(Value::Date)
+        }
+        (n, ScalarOperator::Add, Value::Date(d)) if is_integral(l) => {
+            i64::try_from(numeric_as_integer(n).unwrap())
+                .ok()
+                .and_then(|days| d.checked_add(Duration::days(days)))
+                .map(Value::Date)
+        }
+        (Value::Date(l), ScalarOperator::Subtract, Value::Date(r)) => {
+            Some(Value::Integer((*l - *r).whole_days()))
+        }
+        (
+            Value::Interval(l),
+            ScalarOperator::Add | ScalarOperator::Subtract,
+            Value::Interval(r),
+        ) => {
+            let r = signed_interval(op, r);
+            r.and_then(|r| {
+                Some(Interval::new(
+                    l.months().checked_add(r.months())?,
+                    l.days().checked_add(r.days())?,
+                    l.nanoseconds().checked_add(r.nanoseconds())?,
+                ))
+            })
+            .map(Value::Interval)
+        }
+        (temporal, ScalarOperator::Add | ScalarOperator::Subtract, Value::Interval(interval))
+        | (Value::Interval(interval), ScalarOperator::Add, temporal) => {
+            signed_interval(op, interval).and_then(|interval| match temporal {
+                Value::Date(v) => add_interval(v.midnight(), &interval).map(Value::Timestamp),
+                Value::Timestamp(v) => add_interval(*v, &interval).map(Value::Timestamp),
+                Value::TimestampTz(v) => {
+                    add_interval(PrimitiveDateTime::new(v.date(), v.time()), &interval)
+                        .map(|local| Value::TimestampTz(local.assume_offset(v.offset())))
+                }
+                Value::Time(v) => Some(Value::Time(
+                    *v + Duration::nanoseconds(interval.nanoseconds()),
+                )),
+                _ => None,
+            })
+        }
+        (Value::Timestamp(l), ScalarOperator::Subtract, Value::Timestamp(r)) => {
+            duration_interval(*l - *r)
+        }
+        (Value::TimestampTz(l), ScalarOperator::Subtract, Value::TimestampTz(r)) => {
+            duration_interval(*l - *r)
+        }
+        _ => None,
+    };
+    match result {
+        Some(result) => Ok(result),
+        None => {
+            // any combination of types not handled above is checked first.
+            operation_domain(l, op, r)?;
+            Err(overflow())
+        }
+    }
+}
+
+fn float_operation(lhs: f64, op: ScalarOperator, rhs: f64) -> f64 {
+    match op {
+        ScalarOperator::Add => lhs + rhs,
+        ScalarOperator::Subtract => lhs - rhs,
+        ScalarOperator::Multiply => lhs * rhs,
+        ScalarOperator::Divide => lhs / rhs,
+        ScalarOperator::Modulo => lhs % rhs,
+        ScalarOperator::Concatenate => unreachable!(),
+    }
+}
+
+fn integer_value(domain: Domain, value: i128) -> Option<Value> {
+    match domain {
+        Domain::Byte => u8::try_from(value).ok().map(Value::Byte),
+        Domain::UnsignedInteger => u64::try_from(value).ok().map(Value::UnsignedInteger),
+        _ => i64::try_from(value).ok().map(Value::Integer),
+    }
+}
+
+fn string_value(value: &Value) -> Result<String> {
+    match value {
+        Value::String(v) => Ok(v.clone()),
+        Value::Char(v) => Ok(v.to_string()),
+        _ => Err(incompatible_types(Domain::String, value.data_type())),
+    }
+}
+
+#[inline]
+fn signed_days(op: ScalarOperator, days: i128) -> Option<i64> {
+    let days = i64::try_from(days).ok()?;
+    if op == ScalarOperator::Subtract {
+        days.checked_neg()
+    } else {
+        Some(days)
+    }
+}
+
+#[inline]
+fn signed_interval(op: ScalarOperator, interval: &Interval) -> Option<Interval> {
+    if op == ScalarOperator::Subtract {
+        negate_interval(interval)
+    } else {
+        Some(*interval)
+    }
+}
+
+fn negate_interval(interval: &Interval) -> Option<Interval> {
+    Some(Interval::new(
+        interval.months().checked_neg()?,
+        interval.days().checked_neg()?,
+        interval.nanoseconds().checked_neg()?,
+    ))
+}
+
+///
+/// Add `interval` to a date and time; the months are added first, keeping the same day of the
+/// month unless that is past the end of the resulting month, and then the days and time.
+///
+fn add_interval(value: PrimitiveDateTime, interval: &Interval) -> Option<PrimitiveDateTime> {
+    let date = value.date();
+    let months = date.year() as i64 * 12 + (date.month() as i64 - 1) + interval.months() as i64;
+    let year = i32::try_from(months.div_euclid(12)).ok()?;
+    let month = Month::try_from((months.rem_euclid(12) + 1) as u8).ok()?;
+    let day = date.day().min(month.length(year));
+    PrimitiveDateTime::new(
+        Date::from_calendar_date(year, month, day).ok()?,
+        value.time(),
+    )
+    .checked_add(Duration::days(interval.days() as i64))?
+    .checked_add(Duration::nanoseconds(interval.nanoseconds()))
+}
+
+fn duration_interval(duration: Duration) -> Option<Value> {
+    let days = duration.whole_days();
+    let nanoseconds = (duration - Duration::days(days)).whole_nanoseconds();
+    Some(Value::Interval(Interval::new(
+        i32::try_from(days).ok()?,
+        0,
+        i64::try_from(nanoseconds).ok()?,
+    )))
+}
+
+// ------------------------------------------------------------------------------------------------
+
+fn function_value(function: ScalarFunction, arguments: Vec<Value>) -> Result<Value> {
+    let argument = &arguments[0];
+    match (function, argument) {
+        (ScalarFunction::Upper, _) => Ok(Value::String(string_value(argument)?.to_uppercase())),
+        (ScalarFunction::Lower, _) => Ok(Value::String(string_value(argument)?.to_lowercase())),
+        (ScalarFunction::Length, Value::Binary(v)) => Ok(Value::Integer(v.len() as i64)),
+        (ScalarFunction::Length, _) => Ok(Value::Integer(
+            string_value(argument)?.chars().count() as i64
+        )),
+        (ScalarFunction::Substring, _) => {
+            let s = string_value(argument)?;
+            let start = integer_argument(&arguments[1])? - 1;
+            let end = match arguments.get(2) {
+                None => i128::MAX,
+                Some(length) => {
+                    let length = integer_argument(length)?;
+                    if length < 0 {
+                        return Err(invalid_value(Domain::UnsignedInteger, length as i64));
+                    }
+                    start.saturating_add(length)
+                }
+            };
+            Ok(Value::String(
+                s.chars()
+                    .enumerate()
+                    .filter(|(i, _)| (*i as i128) >= start && (*i as i128) < end)
+                    .map(|(_, c)| c)
+                    .collect(),
+            ))
+        }
+        (ScalarFunction::Absolute, Value::Integer(v)) => v
+            .checked_abs()
+            .map(Value::Integer)
+            .ok_or_else(|| arithmetic_overflow(format!("abs({})", v))),
+        (ScalarFunction::Absolute, Value::Float(v)) => Ok(Value::Float(v.abs())),
+        (ScalarFunction::Absolute, Value::Decimal(v)) => Ok(Value::Decimal(v.abs())),
+        (ScalarFunction::Absolute, Value::Interval(v)) if *v < Interval::new(0, 0, 0) => {
+            negate_interval(v)
+                .map(Value::Interval)
+                .ok_or_else(|| arithmetic_overflow(format!("abs({})", v)))
+        }
+        (
+            ScalarFunction::Absolute,
+            Value::Byte(_) | Value::UnsignedInteger(_) | Value::Interval(_),
+        ) => Ok(argument.clone()),
+        (ScalarFunction::Round, _) if argument.data_type().is_numeric() => {
+            let places = match arguments.get(1) {
+                None => 0,
+                Some(places) => u32::try_from(integer_argument(places)?)
+                    .map_err(|_| invalid_value(Domain::UnsignedInteger, places.clone()))?,
+            };
+            Ok(match argument {
+                Value::Float(v) => {
+                    // beyond the precision of a float, where the scaled value is not finite, the
+                    // value is already as precise as it can be.
+                    let scale = 10f64.powi(places.min(i32::MAX as u32) as i32);
+                    if (v * scale).is_finite() {
+                        Value::Float((v * scale).round() / scale)
+                    } else {
+                        argument.clone()
+                    }
+                }
+                Value::Decimal(v) => Value::Decimal(
+                    v.round_dp_with_strategy(places, RoundingStrategy::MidpointAwayFromZero),
+                ),
+                _ => argument.clone(),
+            })
+        }
+        (ScalarFunction::Coalesce, _) => Ok(argument.clone()),
+        (_, _) => Err(incompatible_types(Domain::Float, argument.data_type())),
+    }
+}
+
+fn integer_argument(value: &Value) -> Result<i128> {
+    numeric_as_integer(value).ok_or_else(|| incompatible_types(Domain::Integer, value.data_type()))
+}
@@ -7,8 +7,8 @@ This module allows for the generation of a [GraphViz](https://graphviz.org/) DOT
 
 use crate::{
     ast::{
-        Attribute, Group, Join, Order, ProjectedAttribute, Projection, RelationalOp, Rename,
-        Selection, SetOperation,
+        Aggregate, Attribute, Group, Join, Order, ProjectedAttribute, Projection, RelationalOp,
+        Rename, Selection, SetOperation, SortKey, Term,
     },
     error::Result,
     Name,
@@ -57,6 +57,11 @@ fn relational_to_node(op: &RelationalOp) -> Result<Progress> {
         RelationalOp::Order(v) => order_to_node(v)?,
         RelationalOp::Group(v) => group_to_node(v)?,
         RelationalOp::Join(v) => join_to_node(v)?,
+        RelationalOp::SemiJoin(v) => {
+            binary_to_node(&v.kind().to_string(), v.lhs(), v.criteria(), v.rhs())?
+        }
+        RelationalOp::AntiJoin(v) => binary_to_node("▷", v.lhs(), v.criteria(), v.rhs())?,
+        RelationalOp::Division(v) => binary_to_node("÷", v.lhs(), None, v.rhs())?,
     })
 }
 
@@ -186,8 +191,8 @@ fn order_to_node(order: &Order) -> Result<Progress> {
             LabelString::from_str(&format!(
                 "τ\n{}",
                 order
-                    .attributes()
-                    .map(Attribute::to_string)
+                    .keys()
+                    .map(SortKey::to_string)
                     .collect::<Vec<String>>()
                     .join(", ")
             ))
@@ -217,6 +222,7 @@ fn group_to_node(group: &Group) -> Result<Progress> {
                 group
                     .attributes()
                     .map(Attribute::to_string)
+                    .chain(group.aggregates().map(Aggregate::to_string))
                     .collect::<Vec<String>>()
                     .join(", ")
             ))
@@ -236,18 +242,27 @@ fn group_to_node(group: &Group) -> Result<Progress> {
 }
 
 fn join_to_node(join: &Join) -> Result<Progress> {
-    let (lhs, criteria, rhs) = match join {
-        Join::Natural(j) => (j.lhs(), None, j.rhs()),
-        Join::Theta(j) => (j.lhs(), Some(j.criteria()), j.rhs()),
+    let (symbol, lhs, criteria, rhs) = match join {
+        Join::Natural(j) => ("⨝".to_string(), j.lhs(), None, j.rhs()),
+        Join::Theta(j) => ("⨝".to_string(), j.lhs(), Some(j.criteria()), j.rhs()),
+        Join::Outer(j) => (j.kind().to_string(), j.lhs(), j.criteria(), j.rhs()),
     };
+    binary_to_node(&symbol, lhs, criteria, rhs)
+}
 
+fn binary_to_node(
+    symbol: &str,
+    lhs: &RelationalOp,
+    criteria: Option<&Term>,
+    rhs: &RelationalOp,
+) -> Result<Progress> {
     let lhs = relational_to_node(lhs)?;
     let rhs = relational_to_node(rhs)?;
 
     let label_string = if let Some(criteria) = criteria {
-        LabelString::from_str(&format!("⨝\n{}", criteria)).unwrap()
+        LabelString::from_str(&format!("{}\n{}", symbol, criteria)).unwrap()
     } else {
-        LabelString::from_str("⨝").unwrap()
+        LabelString::from_str(symbol).unwrap()
     };
 
     let node_id = DotId::new_node();
@@ -30,6 +30,8 @@ Alternatively, try the tool [RelaX - relational algebra calculator](https://dbis
 
 ## Optional Features
 
+* `evaluation` - include the ability to analyze, optimize, and evaluate expressions against relation
+  data, and to translate expressions to SQL; this requires `simple_data`.
 * `graphviz` - include the ability to create a DOT graph from the AST.
 * `simple_data` - include the ability to read CSV and JSON files as relation tuples.
 
@@ -64,7 +66,8 @@ Alternatively, try the tool [RelaX - relational algebra calculator](https://dbis
 #![deny(
     // ---------- Public
     exported_private_dependencies,
-    private_in_public,
+    private_interfaces,
+    private_bounds,
     // ---------- Deprecated
     anonymous_parameters,
     bare_trait_objects,
@@ -149,10 +152,18 @@ pub mod error;
 #[cfg(feature = "evaluation")]
 pub mod eval;
 
+#[cfg(feature = "evaluation")]
+pub mod optimize;
+
 pub mod sort;
 
+#[cfg(feature = "evaluation")]
+pub mod sql;
+
 #[cfg(feature = "simple_data")]
 pub mod simple;
 
 #[cfg(feature = "graphviz")]
 pub mod graph;
+
+pub mod parse;
@@ -0,0 +1,890 @@
+/*!
+Provides cost-based join ordering, using [`Statistics`] about the relations in a database to
+choose the order in which a chain of joins is evaluated.
+
+A *chain* is a maximal tree of natural joins, or a maximal tree of theta joins and cartesian
+products; the operands of the chain, which may be any other operation, are its *leaves*. The
+[`JoinOrderer`] uses dynamic programming over every subset of a chain's leaves to find the join
+tree with the lowest cost, where the cost of a tree is the sum of the estimated cardinality of
+each intermediate result. If a cheaper tree is found it replaces the original, with a projection
+added where needed to keep the original order of attributes in the result.
+
+# Example
+
+```rust
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::optimize::join_order::JoinOrderer;
+use relational_algebra::simple::data::{SimpleAttributeStatistics, SimpleStatistics};
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use relational_algebra::Name;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+let relation = |name: &str, attributes: &[&str]| {
+    SimpleRelationSchema::new(
+        Name::new_unchecked(name),
+        attributes
+            .iter()
+            .map(|a| SimpleAttributeSchema::new(Name::new_unchecked(a), Domain::Integer)),
+    )
+    .unwrap()
+};
+let schema = SimpleSchema::new(
+    Name::new_unchecked("example"),
+    [
+        relation("a", &["x"]),
+        relation("b", &["x", "y"]),
+        relation("c", &["y"]),
+    ],
+)
+.unwrap();
+
+let statistics: HashMap<Name, SimpleStatistics> = [
+    ("a", 1_000_000, vec![1_000_000]),
+    ("b", 1_000, vec![1_000, 1_000]),
+    ("c", 10, vec![10]),
+]
+.into_iter()
+.map(|(name, cardinality, distinct): (&str, usize, Vec<usize>)| {
+    (
+        Name::new_unchecked(name),
+        SimpleStatistics::new(
+            cardinality,
+            distinct
+                .into_iter()
+                .map(|d| SimpleAttributeStatistics::new(Some(d), None, None))
+                .collect(),
+        ),
+    )
+})
+.collect();
+
+let query = RelationalOp::from_str("(a ⨝ b) ⨝ c").unwrap();
+let reordered = JoinOrderer::new(&statistics).reorder(&query, &schema).unwrap();
+assert_eq!(reordered, RelationalOp::from_str("a ⨝ (b ⨝ c)").unwrap());
+```
+
+ */
+
+use super::map_children;
+use super::map_term_attributes;
+use crate::{
+    ast::{
+        Attribute, ComparisonOperator, Join, NaturalJoin, OuterJoinKind, ProjectedAttribute,
+        Projection, RelationalOp, ScalarExpression, SemiJoinKind, SetOperation, SetOperator, Term,
+        ThetaJoin,
+    },
+    data::{Statistics, StatisticsProvider, Value},
+    error::{attribute_index_invalid, Result},
+    eval::{attribute_to_index, Analyzer},
+    simple::{
+        ops::{common_attributes, division_attributes},
+        sort::SimpleRelationSchema,
+    },
+    sort::{AttributeSchema, RelationSchema, Schema},
+    Name,
+};
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::HashMap;
+use tracing::debug;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Reorders chains of joins using statistics from a [`StatisticsProvider`]; relations for which
+/// no statistics are available are assumed to be of a fixed, moderate, size.
+///
+#[derive(Debug)]
+pub struct JoinOrderer<'a, P>
+where
+    P: StatisticsProvider,
+{
+    statistics: &'a P,
+    max_relations: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const DEFAULT_MAX_RELATIONS: usize = 10;
+
+const DEFAULT_CARDINALITY: f64 = 1000.0;
+
+const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+
+type Analyze<'b> = &'b dyn Fn(&RelationalOp) -> Result<SimpleRelationSchema>;
+
+#[derive(Clone, Debug)]
+struct Estimate {
+    cardinality: f64,
+    attributes: Vec<AttributeEstimate>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct AttributeEstimate {
+    distinct_count: Option<f64>,
+    min_value: Option<Value>,
+    max_value: Option<Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChainKind {
+    Natural,
+    Theta,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Plan {
+    Leaf(usize),
+    Join(Box<Plan>, Box<Plan>),
+}
+
+#[derive(Debug)]
+struct Chain {
+    kind: ChainKind,
+    leaves: Vec<RelationalOp>,
+    schemas: Vec<SimpleRelationSchema>,
+    offsets: Vec<usize>,
+    conjuncts: Vec<(Term, usize)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, P> JoinOrderer<'a, P>
+where
+    P: StatisticsProvider,
+{
+    pub fn new(statistics: &'a P) -> Self {
+        Self {
+            statistics,
+            max_relations: DEFAULT_MAX_RELATIONS,
+        }
+    }
+
+    ///
+    /// Set the largest number of leaves in a chain that will be reordered; the number of plans
+    /// considered grows exponentially with this value.
+    ///
+    pub fn with_max_relations(self, max_relations: usize) -> Self {
+        Self {
+            max_relations,
+            ..self
+        }
+    }
+
+    pub fn max_relations(&self) -> usize {
+        self.max_relations
+    }
+
+    ///
+    /// Reorder all join chains in `op`, the operation is analyzed first and any errors from
+    /// analysis are returned.
+    ///
+    pub fn reorder<S>(&self, op: &RelationalOp, schema: &S) -> Result<RelationalOp>
+    where
+        S: Schema,
+    {
+        let analyzer = Analyzer::new(schema);
+        let _ = analyzer.relational(op)?;
+        self.reorder_op(op, &|op| analyzer.relational(op))
+    }
+
+    fn reorder_op(&self, op: &RelationalOp, analyze: Analyze<'_>) -> Result<RelationalOp> {
+        let kind = match op {
+            RelationalOp::Join(Join::Natural(_)) => ChainKind::Natural,
+            RelationalOp::Join(Join::Theta(_)) => ChainKind::Theta,
+            RelationalOp::SetOperation(v) if v.is_cartesian_product() => ChainKind::Theta,
+            _ => return map_children(op, &mut |child| self.reorder_op(child, analyze)),
+        };
+
+        let mut chain = Chain::new(kind);
+        let original = chain.collect(op, analyze)?;
+        let n = chain.leaves.len();
+        if n < 3 || n > self.max_relations || !chain.is_reorderable() {
+            return map_children(op, &mut |child| self.reorder_op(child, analyze));
+        }
+
+        chain.leaves = chain
+            .leaves
+            .iter()
+            .map(|leaf| self.reorder_op(leaf, analyze))
+            .collect::<Result<Vec<RelationalOp>>>()?;
+        let estimates = chain
+            .leaves
+            .iter()
+            .map(|leaf| self.estimate(leaf, analyze))
+            .collect::<Result<Vec<Estimate>>>()?;
+        let cardinalities = chain.cardinalities(&estimates)?;
+
+        let mut best: Vec<Option<(f64, Plan)>> = vec![None; 1 << n];
+        for i in 0..n {
+            best[1 << i] = Some((0.0, Plan::Leaf(i)));
+        }
+        for mask in 1..(1usize << n) {
+            if mask.count_ones() < 2 {
+                continue;
+            }
+            let lowest = mask & mask.wrapping_neg();
+            let mut lhs = (mask - 1) & mask;
+            while lhs > 0 {
+                let rhs = mask ^ lhs;
+                if lhs & lowest != 0 && rhs != 0 {
+                    let (lhs_cost, lhs_plan) = best[lhs].clone().unwrap();
+                    let (rhs_cost, rhs_plan) = best[rhs].clone().unwrap();
+                    let cost = lhs_cost + rhs_cost + cardinalities[mask];
+                    if best[mask].as_ref().map(|(c, _)| cost < *c).unwrap_or(true) {
+                        best[mask] =
+                            Some((cost, Plan::Join(Box::new(lhs_plan), Box::new(rhs_plan))));
+                    }
+                }
+                lhs = (lhs - 1) & mask;
+            }
+        }
+
+        let (best_cost, best_plan) = best[(1 << n) - 1].clone().unwrap();
+        let original_cost = original.cost(&cardinalities);
+        debug!(original_cost, best_cost, "join order for {}", op);
+        if best_cost < original_cost * (1.0 - f64::EPSILON.sqrt()) {
+            let reordered = chain.build(&best_plan, true);
+            chain.restore_attribute_order(reordered, &best_plan, analyze(op)?, analyze)
+        } else {
+            let mut leaves = chain.leaves.into_iter();
+            replace_leaves(op, kind, &mut leaves)
+        }
+    }
+
+    fn estimate(&self, op: &RelationalOp, analyze: Analyze<'_>) -> Result<Estimate> {
+        let schema = analyze(op)?;
+        Ok(match op {
+            RelationalOp::Relation(name) => self.relation_estimate(name, &schema),
+            RelationalOp::Selection(v) => {
+                let mut estimate = self.estimate(v.rhs(), analyze)?;
+                estimate.cardinality *= selectivity(v.criteria(), &schema, &estimate);
+                estimate.cap_distinct_counts();
+                estimate
+            }
+            RelationalOp::Projection(v) => {
+                let rhs_schema = analyze(v.rhs())?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                let attributes = v
+                    .attributes()
+                    .map(|a| {
+                        let attribute = match a {
+                            ProjectedAttribute::Index(i) => Attribute::Index(*i),
+                            ProjectedAttribute::Name(n) => Attribute::Name(n.clone()),
+                            ProjectedAttribute::Constant(v) => {
+                                return Ok(AttributeEstimate {
+                                    distinct_count: Some(1.0),
+                                    min_value: Some(v.clone()),
+                                    max_value: Some(v.clone()),
+                                })
+                            }
+                            // nothing is known of the values of a computed attribute.
+                            ProjectedAttribute::Computed(_, _) => {
+                                return Ok(AttributeEstimate::default())
+                            }
+                        };
+                        Ok(rhs.attributes[attribute_to_index(&attribute, &rhs_schema)?].clone())
+                    })
+                    .collect::<Result<Vec<AttributeEstimate>>>()?;
+                Estimate {
+                    cardinality: rhs.cardinality,
+                    attributes,
+                }
+            }
+            RelationalOp::Rename(v) => self.estimate(v.rhs(), analyze)?,
+            RelationalOp::Order(v) => self.estimate(v.rhs(), analyze)?,
+            RelationalOp::Group(v) => {
+                let rhs_schema = analyze(v.rhs())?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                let mut attributes = v
+                    .attributes()
+                    .map(|a| Ok(rhs.attributes[attribute_to_index(a, &rhs_schema)?].clone()))
+                    .collect::<Result<Vec<AttributeEstimate>>>()?;
+                let groups = attributes
+                    .iter()
+                    .map(|a| a.distinct_count.unwrap_or(rhs.cardinality))
+                    .product::<f64>();
+                attributes.extend(Estimate::unknown(v.aggregates().count()));
+                Estimate {
+                    cardinality: rhs.cardinality.min(groups),
+                    attributes,
+                }
+            }
+            RelationalOp::SetOperation(v) => {
+                let lhs = self.estimate(v.lhs(), analyze)?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                match v.operator() {
+                    SetOperator::Union | SetOperator::SymmetricDifference => Estimate {
+                        cardinality: lhs.cardinality + rhs.cardinality,
+                        attributes: Estimate::unknown(schema.len()),
+                    },
+                    SetOperator::Intersection => Estimate {
+                        cardinality: lhs.cardinality.min(rhs.cardinality),
+                        attributes: lhs.attributes,
+                    },
+                    SetOperator::Difference => lhs,
+                    SetOperator::CartesianProduct => lhs.product(rhs),
+                }
+            }
+            RelationalOp::Join(Join::Natural(v)) => {
+                let lhs_schema = analyze(v.lhs())?;
+                let rhs_schema = analyze(v.rhs())?;
+                let lhs = self.estimate(v.lhs(), analyze)?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                let mut divisor = 1.0;
+                for (l, r) in common_attributes(&lhs_schema, &rhs_schema)? {
+                    divisor *= lhs.distinct_count(l).max(rhs.distinct_count(r));
+                }
+                let rhs_retained: Vec<usize> = (0..rhs_schema.len())
+                    .filter(|i| {
+                        rhs_schema
+                            .attribute(*i)
+                            .map(|a| !lhs_schema.has_attribute(a.name()))
+                            .unwrap_or(false)
+                    })
+                    .collect();
+                let mut estimate = Estimate {
+                    cardinality: lhs.cardinality * rhs.cardinality / divisor,
+                    attributes: lhs
+                        .attributes
+                        .iter()
+                        .cloned()
+                        .chain(rhs_retained.iter().map(|i| rhs.attributes[*i].clone()))
+                        .collect(),
+                };
+                estimate.cap_distinct_counts();
+                estimate
+            }
+            RelationalOp::Join(Join::Theta(v)) => {
+                let lhs = self.estimate(v.lhs(), analyze)?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                let mut estimate = lhs.product(rhs);
+                estimate.cardinality *= selectivity(v.criteria(), &schema, &estimate);
+                estimate.cap_distinct_counts();
+                estimate
+            }
+            RelationalOp::Join(Join::Outer(v)) => {
+                // at least every retained tuple, assuming each matches at most once.
+                let lhs = self.estimate(v.lhs(), analyze)?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                Estimate {
+                    cardinality: match v.kind() {
+                        OuterJoinKind::Left => lhs.cardinality,
+                        OuterJoinKind::Right => rhs.cardinality,
+                        OuterJoinKind::Full => lhs.cardinality + rhs.cardinality,
+                    },
+                    attributes: Estimate::unknown(schema.len()),
+                }
+            }
+            // at most every tuple of the retained operand, as for a difference.
+            RelationalOp::SemiJoin(v) => match v.kind() {
+                SemiJoinKind::Left => self.estimate(v.lhs(), analyze)?,
+                SemiJoinKind::Right => self.estimate(v.rhs(), analyze)?,
+            },
+            RelationalOp::AntiJoin(v) => self.estimate(v.lhs(), analyze)?,
+            RelationalOp::Division(v) => {
+                // each quotient tuple appears once with each tuple of the divisor.
+                let (quotient, _) = division_attributes(&analyze(v.lhs())?, &analyze(v.rhs())?)?;
+                let lhs = self.estimate(v.lhs(), analyze)?;
+                let rhs = self.estimate(v.rhs(), analyze)?;
+                let mut estimate = Estimate {
+                    cardinality: lhs.cardinality / rhs.cardinality.max(1.0),
+                    attributes: quotient
+                        .into_iter()
+                        .map(|i| lhs.attributes[i].clone())
+                        .collect(),
+                };
+                estimate.cap_distinct_counts();
+                estimate
+            }
+        })
+    }
+
+    fn relation_estimate(&self, name: &Name, schema: &SimpleRelationSchema) -> Estimate {
+        match self.statistics.statistics(name) {
+            Some(statistics) => {
+                let mut estimate = Estimate {
+                    cardinality: statistics.cardinality() as f64,
+                    attributes: (0..schema.len())
+                        .map(|i| AttributeEstimate {
+                            distinct_count: statistics.distinct_count(i).map(|d| d as f64),
+                            min_value: statistics.min_value(i),
+                            max_value: statistics.max_value(i),
+                        })
+                        .collect(),
+                };
+                estimate.cap_distinct_counts();
+                estimate
+            }
+            None => Estimate {
+                cardinality: DEFAULT_CARDINALITY,
+                attributes: Estimate::unknown(schema.len()),
+            },
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Estimate {
+    fn unknown(len: usize) -> Vec<AttributeEstimate> {
+        vec![AttributeEstimate::default(); len]
+    }
+
+    fn distinct_count(&self, attribute: usize) -> f64 {
+        self.attributes
+            .get(attribute)
+            .and_then(|a| a.distinct_count)
+            .unwrap_or(self.cardinality)
+            .max(1.0)
+    }
+
+    fn product(self, rhs: Estimate) -> Estimate {
+        Estimate {
+            cardinality: self.cardinality * rhs.cardinality,
+            attributes: self.attributes.into_iter().chain(rhs.attributes).collect(),
+        }
+    }
+
+    fn cap_distinct_counts(&mut self) {
+        for attribute in &mut self.attributes {
+            if let Some(distinct_count) = &mut attribute.distinct_count {
+                *distinct_count = distinct_count.min(self.cardinality);
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Plan {
+    fn mask(&self) -> usize {
+        match self {
+            Plan::Leaf(i) => 1 << i,
+            Plan::Join(lhs, rhs) => lhs.mask() | rhs.mask(),
+        }
+    }
+
+    fn leaves(&self) -> Vec<usize> {
+        match self {
+            Plan::Leaf(i) => vec![*i],
+            Plan::Join(lhs, rhs) => {
+                let mut leaves = lhs.leaves();
+                leaves.extend(rhs.leaves());
+                leaves
+            }
+        }
+    }
+
+    fn is_join(&self) -> bool {
+        matches!(self, Plan::Join(_, _))
+    }
+
+    fn cost(&self, cardinalities: &[f64]) -> f64 {
+        match self {
+            Plan::Leaf(_) => 0.0,
+            Plan::Join(lhs, rhs) => {
+                lhs.cost(cardinalities) + rhs.cost(cardinalities) + cardinalities[self.mask()]
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Chain {
+    fn new(kind: ChainKind) -> Self {
+        Self {
+            kind,
+            leaves: Default::default(),
+            schemas: Default::default(),
+            offsets: Default::default(),
+            conjuncts: Default::default(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.schemas.iter().map(|s| s.len()).sum()
+    }
+
+    fn collect(&mut self, op: &RelationalOp, analyze: Analyze<'_>) -> Result<Plan> {
+        if !is_chain_node(self.kind, op) {
+            let schema = analyze(op)?;
+            self.offsets.push(self.width());
+            self.schemas.push(schema);
+            self.leaves.push(op.clone());
+            return Ok(Plan::Leaf(self.leaves.len() - 1));
+        }
+        let offset = self.width();
+        let (lhs, rhs, criteria) = match op {
+            RelationalOp::Join(Join::Natural(v)) => (v.lhs(), v.rhs(), None),
+            RelationalOp::Join(Join::Theta(v)) => (v.lhs(), v.rhs(), Some(v.criteria())),
+            RelationalOp::SetOperation(v) => (v.lhs(), v.rhs(), None),
+            _ => unreachable!(),
+        };
+        let lhs = self.collect(lhs, analyze)?;
+        let rhs = self.collect(rhs, analyze)?;
+        if let Some(criteria) = criteria {
+            let schema = analyze(op)?;
+            for conjunct in conjuncts(criteria) {
+                let global = map_term_attributes(conjunct, &mut |attribute| {
+                    attribute_to_index(attribute, &schema)
+                        .ok()
+                        .map(|i| Attribute::Index(offset + i))
+                })
+                .ok_or_else(|| attribute_index_invalid(offset))?;
+                let mask = term_indices(&global)
+                    .into_iter()
+                    .fold(0, |mask, i| mask | (1 << self.leaf_of(i)));
+                self.conjuncts.push((global, mask));
+            }
+        }
+        Ok(Plan::Join(Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn is_reorderable(&self) -> bool {
+        // natural joins are only associative if no relation repeats an attribute name.
+        self.kind == ChainKind::Theta
+            || self.schemas.iter().all(|schema| {
+                schema
+                    .attributes()
+                    .enumerate()
+                    .all(|(i, a)| schema.attribute_index(a.name()) == Some(i))
+            })
+    }
+
+    fn leaf_of(&self, global: usize) -> usize {
+        self.offsets
+            .iter()
+            .rposition(|offset| *offset <= global)
+            .unwrap_or_default()
+    }
+
+    fn global_schema(&self) -> Result<SimpleRelationSchema> {
+        SimpleRelationSchema::new(
+            Name::new_unchecked("chain"),
+            self.schemas
+                .iter()
+                .flat_map(|s| s.attributes().cloned().collect::<Vec<_>>()),
+        )
+    }
+
+    fn cardinalities(&self, estimates: &[Estimate]) -> Result<Vec<f64>> {
+        let n = self.leaves.len();
+        let global_schema = self.global_schema()?;
+        let global_estimate = Estimate {
+            cardinality: 1.0,
+            attributes: estimates
+                .iter()
+                .flat_map(|e| e.attributes.iter().cloned())
+                .collect(),
+        };
+        let mut names: HashMap<&Name, Vec<(usize, usize)>> = Default::default();
+        if self.kind == ChainKind::Natural {
+            for (leaf, schema) in self.schemas.iter().enumerate() {
+                for (i, attribute) in schema.attributes().enumerate() {
+                    names.entry(attribute.name()).or_default().push((leaf, i));
+                }
+            }
+        }
+
+        Ok((0..(1usize << n))
+            .map(|mask| {
+                let in_mask = |leaf: usize| mask & (1 << leaf) != 0;
+                let mut cardinality: f64 = (0..n)
+                    .filter(|leaf| in_mask(*leaf))
+                    .map(|leaf| estimates[leaf].cardinality)
+                    .product();
+                match self.kind {
+                    ChainKind::Natural => {
+                        for occurrences in names.values() {
+                            let mut distinct: Vec<f64> = occurrences
+                                .iter()
+                                .filter(|(leaf, _)| in_mask(*leaf))
+                                .map(|(leaf, i)| estimates[*leaf].distinct_count(*i))
+                                .collect();
+                            distinct.sort_by(|lhs, rhs| rhs.total_cmp(lhs));
+                            for d in distinct.iter().take(distinct.len().saturating_sub(1)) {
+                                cardinality /= d;
+                            }
+                        }
+                    }
+                    ChainKind::Theta => {
+                        for (term, term_mask) in &self.conjuncts {
+                            if term_mask & mask == *term_mask {
+                                cardinality *= selectivity(term, &global_schema, &global_estimate);
+                            }
+                        }
+                    }
+                }
+                cardinality
+            })
+            .collect())
+    }
+
+    ///
+    /// Build the operation for `plan`; each conjunct of the original criteria is placed on the
+    /// lowest join which includes all the leaves it references.
+    ///
+    fn build(&self, plan: &Plan, top: bool) -> RelationalOp {
+        let (lhs, rhs) = match plan {
+            Plan::Leaf(i) => return self.leaves[*i].clone(),
+            Plan::Join(lhs, rhs) => (lhs, rhs),
+        };
+        let lhs_op = self.build(lhs, false);
+        let rhs_op = self.build(rhs, false);
+        if self.kind == ChainKind::Natural {
+            return NaturalJoin::new(lhs_op, rhs_op).into();
+        }
+
+        let (mask, lhs_mask, rhs_mask) = (plan.mask(), lhs.mask(), rhs.mask());
+        let local = self.local_indices(&plan.leaves());
+        let criteria: Vec<Term> = self
+            .conjuncts
+            .iter()
+            .filter(|(_, m)| {
+                if *m == 0 {
+                    top
+                } else {
+                    m & mask == *m
+                        && !(m & lhs_mask == *m && lhs.is_join())
+                        && !(m & rhs_mask == *m && rhs.is_join())
+                }
+            })
+            .map(|(term, _)| {
+                map_term_attributes(term, &mut |attribute| {
+                    attribute
+                        .as_index()
+                        .and_then(|i| local.get(&i))
+                        .map(|i| Attribute::Index(*i))
+                })
+                // every index referenced is in a leaf below this join.
+                .unwrap()
+            })
+            .collect();
+        match criteria
+            .into_iter()
+            .rev()
+            .reduce(|rhs, lhs| Term::and(lhs, rhs))
+        {
+            Some(criteria) => ThetaJoin::new(lhs_op, criteria, rhs_op).into(),
+            None => SetOperation::cartesian_product(lhs_op, rhs_op).into(),
+        }
+    }
+
+    ///
+    /// Reordering will change the order of attributes in the result, add a projection to
+    /// restore the order of `expected`, if needed.
+    ///
+    fn restore_attribute_order(
+        &self,
+        reordered: RelationalOp,
+        plan: &Plan,
+        expected: SimpleRelationSchema,
+        analyze: Analyze<'_>,
+    ) -> Result<RelationalOp> {
+        let attributes: Vec<ProjectedAttribute> = match self.kind {
+            ChainKind::Natural => {
+                let actual = analyze(&reordered)?;
+                if expected
+                    .attributes()
+                    .map(|a| a.name())
+                    .eq(actual.attributes().map(|a| a.name()))
+                {
+                    return Ok(reordered);
+                }
+                expected
+                    .attributes()
+                    .map(|a| ProjectedAttribute::Name(a.name().clone()))
+                    .collect()
+            }
+            ChainKind::Theta => {
+                let leaves = plan.leaves();
+                if leaves.windows(2).all(|pair| pair[0] < pair[1]) {
+                    return Ok(reordered);
+                }
+                let local = self.local_indices(&leaves);
+                (0..self.width())
+                    .map(|i| ProjectedAttribute::Index(local[&i]))
+                    .collect()
+            }
+        };
+        Ok(Projection::new(attributes, reordered).into())
+    }
+
+    fn local_indices(&self, leaves: &[usize]) -> HashMap<usize, usize> {
+        let mut local: HashMap<usize, usize> = Default::default();
+        let mut position = 0;
+        for leaf in leaves {
+            for i in 0..self.schemas[*leaf].len() {
+                let _ = local.insert(self.offsets[*leaf] + i, position);
+                position += 1;
+            }
+        }
+        local
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_chain_node(kind: ChainKind, op: &RelationalOp) -> bool {
+    match kind {
+        ChainKind::Natural => op.is_natural_join(),
+        ChainKind::Theta => {
+            op.is_theta_join()
+                || op
+                    .as_set_operation()
+                    .map(SetOperation::is_cartesian_product)
+                    .unwrap_or(false)
+        }
+    }
+}
+
+fn replace_leaves(
+    op: &RelationalOp,
+    kind: ChainKind,
+    leaves: &mut dyn Iterator<Item = RelationalOp>,
+) -> Result<RelationalOp> {
+    if is_chain_node(kind, op) {
+        map_children(op, &mut |child| replace_leaves(child, kind, leaves))
+    } else {
+        Ok(leaves.next().unwrap())
+    }
+}
+
+fn conjuncts(term: &Term) -> Vec<&Term> {
+    match term {
+        Term::And(lhs, rhs) => {
+            let mut conjuncts = conjuncts(lhs);
+            conjuncts.extend(self::conjuncts(rhs));
+            conjuncts
+        }
+        _ => vec![term],
+    }
+}
+
+fn term_indices(term: &Term) -> Vec<usize> {
+    let mut indices: Vec<usize> = Default::default();
+    let _ = map_term_attributes(term, &mut |attribute| {
+        if let Some(i) = attribute.as_index() {
+            indices.push(i);
+        }
+        Some(attribute.clone())
+    });
+    indices
+}
+
+fn selectivity(term: &Term, schema: &SimpleRelationSchema, estimate: &Estimate) -> f64 {
+    match term {
+        Term::Constant(Value::Boolean(false)) => 0.0,
+        Term::Constant(_) | Term::Exists(_) | Term::IsNull(_) | Term::IsNotNull(_) => 1.0,
+        Term::Atom(atom) => {
+            let atom = atom.normalized();
+            let lhs = match atom
+                .lhs()
+                .as_attribute()
+                .map(|a| attribute_to_index(a, schema))
+            {
+                Some(Ok(lhs)) => lhs,
+                _ => return DEFAULT_SELECTIVITY,
+            };
+            let rhs = match atom.rhs() {
+                ScalarExpression::Attribute(a) => attribute_to_index(a, schema).ok(),
+                ScalarExpression::Constant(_) => None,
+                _ => return DEFAULT_SELECTIVITY,
+            };
+            let distinct = match rhs {
+                Some(rhs) => estimate
+                    .distinct_count(lhs)
+                    .max(estimate.distinct_count(rhs)),
+                None => estimate.distinct_count(lhs),
+            };
+            match (atom.operator(), atom.rhs().as_constant()) {
+                (ComparisonOperator::Equal, _) => 1.0 / distinct,
+                (ComparisonOperator::NotEqual, _) => 1.0 - (1.0 / distinct),
+                (ComparisonOperator::LessThan, Some(v))
+                | (ComparisonOperator::LessThanOrEqual, Some(v)) => {
+                    range_fraction(estimate.attributes.get(lhs), v).unwrap_or(DEFAULT_SELECTIVITY)
+                }
+                (ComparisonOperator::GreaterThan, Some(v))
+                | (ComparisonOperator::GreaterThanOrEqual, Some(v)) => {
+                    range_fraction(estimate.attributes.get(lhs), v)
+                        .map(|f| 1.0 - f)
+                        .unwrap_or(DEFAULT_SELECTIVITY)
+                }
+                _ => DEFAULT_SELECTIVITY,
+            }
+        }
+        Term::In(value, list) => {
+            match value.as_attribute().map(|a| attribute_to_index(a, schema)) {
+                Some(Ok(index)) => (list.len() as f64 / estimate.distinct_count(index)).min(1.0),
+                _ => DEFAULT_SELECTIVITY,
+            }
+        }
+        Term::Between(value, low, high) => {
+            match (
+                value.as_attribute().map(|a| attribute_to_index(a, schema)),
+                low.as_constant(),
+                high.as_constant(),
+            ) {
+                (Some(Ok(index)), Some(low), Some(high)) => {
+                    let attribute = estimate.attributes.get(index);
+                    match (
+                        range_fraction(attribute, low),
+                        range_fraction(attribute, high),
+                    ) {
+                        (Some(low), Some(high)) => (high - low).max(0.0),
+                        _ => DEFAULT_SELECTIVITY,
+                    }
+                }
+                _ => DEFAULT_SELECTIVITY,
+            }
+        }
+        Term::Negate(v) => 1.0 - selectivity(v, schema, estimate),
+        Term::And(lhs, rhs) => {
+            selectivity(lhs, schema, estimate) * selectivity(rhs, schema, estimate)
+        }
+        Term::Or(lhs, rhs) => {
+            let (lhs, rhs) = (
+                selectivity(lhs, schema, estimate),
+                selectivity(rhs, schema, estimate),
+            );
+            lhs + rhs - (lhs * rhs)
+        }
+    }
+}
+
+///
+/// The fraction of values in the range of an attribute that are less than `value`.
+///
+fn range_fraction(attribute: Option<&AttributeEstimate>, value: &Value) -> Option<f64> {
+    let attribute = attribute?;
+    let min = numeric_value(attribute.min_value.as_ref()?)?;
+    let max = numeric_value(attribute.max_value.as_ref()?)?;
+    let value = numeric_value(value)?;
+    if max > min {
+        Some(((value - min) / (max - min)).clamp(0.0, 1.0))
+    } else {
+        None
+    }
+}
+
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Byte(v) => Some(*v as f64),
+        Value::UnsignedInteger(v) => Some(*v as f64),
+        Value::Integer(v) => Some(*v as f64),
+        Value::Float(v) => Some(*v),
+        Value::Decimal(v) => v.to_f64(),
+        _ => None,
+    }
+}
@@ -0,0 +1,411 @@
+/*!
+Provides a rule-based logical optimizer which rewrites [`RelationalOp`] trees into equivalent,
+but cheaper to evaluate, trees using the standard algebraic identities.
+
+Each rewrite is implemented as a [`Rule`] trait object, the [`Optimizer`] applies its rules
+bottom-up over an expression until no further rule applies. The set of rules used is chosen when
+the optimizer is constructed; [`Optimizer::default`] uses the rules returned by
+[`rules::default_rules`]. Each time a rule fires it is logged, at the `debug` level, via
+`tracing`.
+
+Rules may require the schema of the relation produced by some part of the tree, this is provided
+by the [`RuleContext`] which uses the same analysis as [`crate::eval::analyze_expression`].
+
+# Example
+
+```rust
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::optimize::Optimizer;
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use relational_algebra::Name;
+use std::str::FromStr;
+
+let schema = SimpleSchema::new(
+    Name::new_unchecked("example"),
+    [
+        SimpleRelationSchema::new(
+            Name::new_unchecked("people"),
+            [
+                SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+                SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+            ],
+        )
+        .unwrap(),
+        SimpleRelationSchema::new(
+            Name::new_unchecked("places"),
+            [
+                SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+                SimpleAttributeSchema::new(Name::new_unchecked("city"), Domain::String),
+            ],
+        )
+        .unwrap(),
+    ],
+)
+.unwrap();
+
+let query = RelationalOp::from_str("σ[age>18i64 ∧ city=\"Paris\"](people ⨝ places)").unwrap();
+let optimized = Optimizer::default().optimize(&query, &schema).unwrap();
+assert_eq!(
+    optimized,
+    RelationalOp::from_str("σ[age>18i64]people ⨝ σ[city=\"Paris\"]places").unwrap()
+);
+```
+
+ */
+
+use crate::{
+    ast::{
+        AntiJoin, Atom, Attribute, Division, Expression, ExpressionList, Group, Join, NaturalJoin,
+        Order, OuterJoin, Projection, RelationalOp, Rename, ScalarExpression, Selection, SemiJoin,
+        SetOperation, Term, ThetaJoin,
+    },
+    error::Result,
+    eval::Analyzer,
+    simple::sort::SimpleRelationSchema,
+    sort::Schema,
+};
+use std::fmt::Debug;
+use tracing::debug;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A single rewrite rule; a rule is given each node in the tree in turn and returns a
+/// replacement for the node if the rule applies to it.
+///
+pub trait Rule: Debug {
+    ///
+    /// A short name used to identify this rule in logs.
+    ///
+    fn name(&self) -> &'static str;
+
+    ///
+    /// Returns `Some` equivalent replacement for `op`, or `None` if this rule does not apply.
+    ///
+    fn apply(&self, op: &RelationalOp, context: &RuleContext<'_>) -> Result<Option<RelationalOp>>;
+}
+
+///
+/// Provides rules with access to the schema of any sub-tree of the expression being optimized.
+///
+pub struct RuleContext<'a> {
+    analyze: &'a dyn Fn(&RelationalOp) -> Result<SimpleRelationSchema>,
+}
+
+///
+/// Applies a set of rules to an expression until no rule applies, or a maximum number of passes
+/// over the expression have been made.
+///
+#[derive(Debug)]
+pub struct Optimizer {
+    rules: Vec<Box<dyn Rule>>,
+    max_passes: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const DEFAULT_MAX_PASSES: usize = 32;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for RuleContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleContext").finish_non_exhaustive()
+    }
+}
+
+impl RuleContext<'_> {
+    ///
+    /// Returns the schema of the relation produced by `op`.
+    ///
+    pub fn schema_of(&self, op: &RelationalOp) -> Result<SimpleRelationSchema> {
+        (self.analyze)(op)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new(rules::default_rules())
+    }
+}
+
+impl Optimizer {
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self {
+            rules,
+            max_passes: DEFAULT_MAX_PASSES,
+        }
+    }
+
+    pub fn with_max_passes(self, max_passes: usize) -> Self {
+        Self { max_passes, ..self }
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+
+    pub fn max_passes(&self) -> usize {
+        self.max_passes
+    }
+
+    ///
+    /// Optimize each expression in the list; named expressions may be referenced, by name, in
+    /// any subsequent expression in the list.
+    ///
+    pub fn optimize_expression_list<S>(
+        &self,
+        list: &ExpressionList,
+        schema: &S,
+    ) -> Result<ExpressionList>
+    where
+        S: Schema,
+    {
+        let mut analyzer = Analyzer::new(schema);
+        let mut results: Vec<Expression> = Default::default();
+        for expression in list.as_ref() {
+            let result = analyzer.expression(expression)?;
+            let optimized =
+                self.optimize_with(expression.expression(), &|op| analyzer.relational(op))?;
+            results.push(match expression.name() {
+                Some(name) => Expression::named(name.clone(), optimized),
+                None => Expression::new(optimized),
+            });
+            if let Some(name) = expression.name() {
+                let _ = analyzer.views.insert(name.clone(), result);
+            }
+        }
+        Ok(results.into())
+    }
+
+    pub fn optimize_expression<S>(&self, expression: &Expression, schema: &S) -> Result<Expression>
+    where
+        S: Schema,
+    {
+        let optimized = self.optimize(expression.expression(), schema)?;
+        Ok(match expression.name() {
+            Some(name) => Expression::named(name.clone(), optimized),
+            None => Expression::new(optimized),
+        })
+    }
+
+    ///
+    /// Optimize a single relational operation, the operation is analyzed first and any errors
+    /// from analysis are returned.
+    ///
+    pub fn optimize<S>(&self, op: &RelationalOp, schema: &S) -> Result<RelationalOp>
+    where
+        S: Schema,
+    {
+        let analyzer = Analyzer::new(schema);
+        let _ = analyzer.relational(op)?;
+        self.optimize_with(op, &|op| analyzer.relational(op))
+    }
+
+    fn optimize_with(
+        &self,
+        op: &RelationalOp,
+        analyze: &dyn Fn(&RelationalOp) -> Result<SimpleRelationSchema>,
+    ) -> Result<RelationalOp> {
+        let context = RuleContext { analyze };
+        let mut current = op.clone();
+        for pass in 0..self.max_passes {
+            let (next, changed) = self.rewrite(&current, &context)?;
+            current = next;
+            if !changed {
+                debug!(passes = pass + 1, "optimization complete");
+                break;
+            }
+        }
+        Ok(current)
+    }
+
+    fn rewrite(
+        &self,
+        op: &RelationalOp,
+        context: &RuleContext<'_>,
+    ) -> Result<(RelationalOp, bool)> {
+        let mut changed = false;
+        let mut current = map_children(op, &mut |child| {
+            let (child, child_changed) = self.rewrite(child, context)?;
+            changed |= child_changed;
+            Ok(child)
+        })?;
+        'rules: loop {
+            for rule in &self.rules {
+                if let Some(rewritten) = rule.apply(&current, context)? {
+                    debug!(
+                        rule = rule.name(),
+                        "rewrote `{}` as `{}`", current, rewritten
+                    );
+                    current = rewritten;
+                    changed = true;
+                    continue 'rules;
+                }
+            }
+            break;
+        }
+        Ok((current, changed))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn map_children(
+    op: &RelationalOp,
+    f: &mut dyn FnMut(&RelationalOp) -> Result<RelationalOp>,
+) -> Result<RelationalOp> {
+    Ok(match op {
+        RelationalOp::Relation(_) => op.clone(),
+        RelationalOp::SetOperation(v) => {
+            SetOperation::new(f(v.lhs())?, v.operator(), f(v.rhs())?).into()
+        }
+        RelationalOp::Selection(v) => Selection::new(v.criteria().clone(), f(v.rhs())?).into(),
+        RelationalOp::Projection(v) => {
+            Projection::new(v.attributes().cloned().collect(), f(v.rhs())?).into()
+        }
+        RelationalOp::Rename(v) => Rename::new(
+            v.renames()
+                .map(|(attribute, name)| (attribute.clone(), name.clone()))
+                .collect(),
+            f(v.rhs())?,
+        )?
+        .into(),
+        RelationalOp::Order(v) => Order::with_keys(v.keys().cloned().collect(), f(v.rhs())?).into(),
+        RelationalOp::Group(v) => Group::with_aggregates(
+            v.attributes().cloned().collect(),
+            v.aggregates().cloned().collect(),
+            f(v.rhs())?,
+        )
+        .into(),
+        RelationalOp::Join(Join::Natural(v)) => NaturalJoin::new(f(v.lhs())?, f(v.rhs())?).into(),
+        RelationalOp::Join(Join::Theta(v)) => {
+            ThetaJoin::new(f(v.lhs())?, v.criteria().clone(), f(v.rhs())?).into()
+        }
+        RelationalOp::Join(Join::Outer(v)) => match v.criteria() {
+            None => OuterJoin::new(f(v.lhs())?, v.kind(), f(v.rhs())?).into(),
+            Some(criteria) => {
+                OuterJoin::with_criteria(f(v.lhs())?, v.kind(), criteria.clone(), f(v.rhs())?)
+                    .into()
+            }
+        },
+        RelationalOp::SemiJoin(v) => match v.criteria() {
+            None => SemiJoin::new(f(v.lhs())?, v.kind(), f(v.rhs())?).into(),
+            Some(criteria) => {
+                SemiJoin::with_criteria(f(v.lhs())?, v.kind(), criteria.clone(), f(v.rhs())?).into()
+            }
+        },
+        RelationalOp::AntiJoin(v) => match v.criteria() {
+            None => AntiJoin::new(f(v.lhs())?, f(v.rhs())?).into(),
+            Some(criteria) => {
+                AntiJoin::with_criteria(f(v.lhs())?, criteria.clone(), f(v.rhs())?).into()
+            }
+        },
+        RelationalOp::Division(v) => Division::new(f(v.lhs())?, f(v.rhs())?).into(),
+    })
+}
+
+///
+/// Rewrite each attribute referenced by `term`, returning `None` if `f` returns `None` for any
+/// attribute.
+///
+fn map_term_attributes(
+    term: &Term,
+    f: &mut dyn FnMut(&Attribute) -> Option<Attribute>,
+) -> Option<Term> {
+    Some(match term {
+        Term::Constant(_) => term.clone(),
+        Term::Exists(v) => Term::Exists(f(v)?),
+        Term::IsNull(v) => Term::IsNull(f(v)?),
+        Term::IsNotNull(v) => Term::IsNotNull(f(v)?),
+        Term::Atom(v) => Term::Atom(Atom::new(
+            map_scalar_attributes(v.lhs(), f)?,
+            v.operator(),
+            map_scalar_attributes(v.rhs(), f)?,
+        )),
+        Term::In(v, list) => Term::In(
+            map_scalar_attributes(v, f)?,
+            list.iter()
+                .map(|item| map_scalar_attributes(item, f))
+                .collect::<Option<Vec<ScalarExpression>>>()?,
+        ),
+        Term::Between(v, low, high) => Term::Between(
+            map_scalar_attributes(v, f)?,
+            map_scalar_attributes(low, f)?,
+            map_scalar_attributes(high, f)?,
+        ),
+        Term::Negate(v) => Term::Negate(Box::new(map_term_attributes(v, f)?)),
+        Term::And(lhs, rhs) => Term::And(
+            Box::new(map_term_attributes(lhs, f)?),
+            Box::new(map_term_attributes(rhs, f)?),
+        ),
+        Term::Or(lhs, rhs) => Term::Or(
+            Box::new(map_term_attributes(lhs, f)?),
+            Box::new(map_term_attributes(rhs, f)?),
+        ),
+    })
+}
+
+///
+/// Rewrite each attribute referenced by `expression`, including those in the conditions of a
+/// `case`, returning `None` if `f` returns `None` for any attribute.
+///
+fn map_scalar_attributes(
+    expression: &ScalarExpression,
+    f: &mut dyn FnMut(&Attribute) -> Option<Attribute>,
+) -> Option<ScalarExpression> {
+    Some(match expression {
+        ScalarExpression::Attribute(v) => ScalarExpression::Attribute(f(v)?),
+        ScalarExpression::Constant(_) => expression.clone(),
+        ScalarExpression::Operation(lhs, op, rhs) => ScalarExpression::operation(
+            map_scalar_attributes(lhs, f)?,
+            *op,
+            map_scalar_attributes(rhs, f)?,
+        ),
+        ScalarExpression::Function(function, arguments) => ScalarExpression::Function(
+            *function,
+            arguments
+                .iter()
+                .map(|argument| map_scalar_attributes(argument, f))
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        ScalarExpression::Case(branches, otherwise) => ScalarExpression::Case(
+            branches
+                .iter()
+                .map(|(condition, value)| {
+                    Some((
+                        map_term_attributes(condition, f)?,
+                        map_scalar_attributes(value, f)?,
+                    ))
+                })
+                .collect::<Option<Vec<_>>>()?,
+            match otherwise {
+                None => None,
+                Some(otherwise) => Some(Box::new(map_scalar_attributes(otherwise, f)?)),
+            },
+        ),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod join_order;
+
+pub mod rules;
@@ -0,0 +1,361 @@
+/*!
+Provides the standard set of rewrite rules used by the [`Optimizer`](super::Optimizer).
+
+| Rule                             | Rewrite                                                  |
+|----------------------------------|----------------------------------------------------------|
+| [`SplitConjunctiveSelection`]    | $\sigma_{a \land b}(R) \rightarrow \sigma_a(\sigma_b(R))$ |
+| [`PushSelectionDown`]            | $\sigma_a(R \bowtie S) \rightarrow \sigma_a(R) \bowtie S$, and similarly for set operations |
+| [`SelectionToThetaJoin`]         | $\sigma_a(R \times S) \rightarrow R \bowtie_a S$           |
+| [`MergeProjections`]             | $\pi_a(\pi_b(R)) \rightarrow \pi_{a'}(R)$                  |
+| [`RemoveRedundantRename`]        | $\rho_{a/a}(R) \rightarrow R$                              |
+
+ */
+
+use super::{map_scalar_attributes, map_term_attributes, Rule, RuleContext};
+use crate::{
+    ast::{
+        Attribute, Join, NaturalJoin, ProjectedAttribute, Projection, RelationalOp, Rename,
+        SetOperation, Term, ThetaJoin,
+    },
+    error::Result,
+    eval::attribute_to_index,
+    simple::{ops::common_attributes, sort::SimpleRelationSchema},
+    sort::{AttributeSchema, RelationSchema},
+    Name,
+};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Splits a selection on a conjunction into a selection on each side of the conjunction, so that
+/// each may be pushed down independently.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SplitConjunctiveSelection;
+
+///
+/// Pushes a selection below a join, product, or set operation when the selection criteria only
+/// reference attributes from one side of a join, or into both sides of a set operation.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PushSelectionDown;
+
+///
+/// Replaces a selection over a cartesian product with a theta join, and merges a selection over
+/// a theta join into the join criteria.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SelectionToThetaJoin;
+
+///
+/// Replaces a projection of a projection with a single projection.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MergeProjections;
+
+///
+/// Removes any rename of an attribute to its existing name, and any rename left with no attributes
+/// to rename.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RemoveRedundantRename;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the default set of rules, in the order they are applied.
+///
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(RemoveRedundantRename),
+        Box::new(MergeProjections),
+        Box::new(SplitConjunctiveSelection),
+        Box::new(PushSelectionDown),
+        Box::new(SelectionToThetaJoin),
+    ]
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Rule for SplitConjunctiveSelection {
+    fn name(&self) -> &'static str {
+        "split-conjunctive-selection"
+    }
+
+    fn apply(&self, op: &RelationalOp, _: &RuleContext<'_>) -> Result<Option<RelationalOp>> {
+        if let RelationalOp::Selection(selection) = op {
+            if let Term::And(lhs, rhs) = selection.criteria() {
+                return Ok(Some(RelationalOp::select(
+                    lhs.as_ref().clone(),
+                    RelationalOp::select(rhs.as_ref().clone(), selection.rhs().clone()),
+                )));
+            }
+        }
+        Ok(None)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rule for PushSelectionDown {
+    fn name(&self) -> &'static str {
+        "push-selection-down"
+    }
+
+    fn apply(&self, op: &RelationalOp, context: &RuleContext<'_>) -> Result<Option<RelationalOp>> {
+        let selection = match op {
+            RelationalOp::Selection(v) => v,
+            _ => return Ok(None),
+        };
+        let criteria = selection.criteria();
+        let (lhs, rhs) = match selection.rhs() {
+            RelationalOp::SetOperation(v) => (v.lhs(), v.rhs()),
+            RelationalOp::Join(Join::Natural(v)) => (v.lhs(), v.rhs()),
+            RelationalOp::Join(Join::Theta(v)) => (v.lhs(), v.rhs()),
+            _ => return Ok(None),
+        };
+        let output = context.schema_of(selection.rhs())?;
+        let lhs_schema = context.schema_of(lhs)?;
+        let rhs_schema = context.schema_of(rhs)?;
+
+        Ok(match selection.rhs() {
+            RelationalOp::SetOperation(v) if !v.is_cartesian_product() => {
+                let identity: Vec<Option<usize>> = (0..output.len()).map(Some).collect();
+                match (
+                    push_term(criteria, &output, &lhs_schema, &identity),
+                    push_term(criteria, &output, &rhs_schema, &identity),
+                ) {
+                    (Some(lhs_criteria), Some(rhs_criteria)) => Some(
+                        SetOperation::new(
+                            RelationalOp::select(lhs_criteria, lhs.clone()),
+                            v.operator(),
+                            RelationalOp::select(rhs_criteria, rhs.clone()),
+                        )
+                        .into(),
+                    ),
+                    _ => None,
+                }
+            }
+            join => {
+                let (lhs_map, rhs_map) = if join.is_natural_join() {
+                    let common = common_attributes(&lhs_schema, &rhs_schema)?;
+                    natural_join_maps(&lhs_schema, &rhs_schema, &common)
+                } else {
+                    product_maps(&lhs_schema, &rhs_schema)
+                };
+                if let Some(lhs_criteria) = push_term(criteria, &output, &lhs_schema, &lhs_map) {
+                    Some(rebuild_join(
+                        join,
+                        RelationalOp::select(lhs_criteria, lhs.clone()),
+                        rhs.clone(),
+                    ))
+                } else {
+                    push_term(criteria, &output, &rhs_schema, &rhs_map).map(|rhs_criteria| {
+                        rebuild_join(
+                            join,
+                            lhs.clone(),
+                            RelationalOp::select(rhs_criteria, rhs.clone()),
+                        )
+                    })
+                }
+            }
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rule for SelectionToThetaJoin {
+    fn name(&self) -> &'static str {
+        "selection-to-theta-join"
+    }
+
+    fn apply(&self, op: &RelationalOp, _: &RuleContext<'_>) -> Result<Option<RelationalOp>> {
+        Ok(match op {
+            RelationalOp::Selection(selection) => match selection.rhs() {
+                RelationalOp::SetOperation(v) if v.is_cartesian_product() => {
+                    Some(RelationalOp::theta_join(
+                        v.lhs().clone(),
+                        selection.criteria().clone(),
+                        v.rhs().clone(),
+                    ))
+                }
+                RelationalOp::Join(Join::Theta(v)) => Some(RelationalOp::theta_join(
+                    v.lhs().clone(),
+                    Term::and(v.criteria().clone(), selection.criteria().clone()),
+                    v.rhs().clone(),
+                )),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rule for MergeProjections {
+    fn name(&self) -> &'static str {
+        "merge-projections"
+    }
+
+    fn apply(&self, op: &RelationalOp, context: &RuleContext<'_>) -> Result<Option<RelationalOp>> {
+        let (outer, inner) = match op {
+            RelationalOp::Projection(outer) => match outer.rhs() {
+                RelationalOp::Projection(inner) => (outer, inner),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let inner_schema = context.schema_of(outer.rhs())?;
+        let inner_attributes: Vec<&ProjectedAttribute> = inner.attributes().collect();
+
+        let mut merged: Vec<ProjectedAttribute> = Default::default();
+        for (position, attribute) in outer.attributes().enumerate() {
+            let index = match attribute {
+                ProjectedAttribute::Index(v) => *v,
+                ProjectedAttribute::Name(v) => {
+                    attribute_to_index(&Attribute::Name(v.clone()), &inner_schema)?
+                }
+                ProjectedAttribute::Constant(_) => {
+                    merged.push(attribute.clone());
+                    continue;
+                }
+                ProjectedAttribute::Computed(expression, name) => {
+                    // only attributes of the inner projection's operand may be substituted.
+                    let expression =
+                        map_scalar_attributes(expression, &mut |attribute| match inner_attributes
+                            [attribute_to_index(attribute, &inner_schema).ok()?]
+                        {
+                            ProjectedAttribute::Index(v) => Some(Attribute::Index(*v)),
+                            ProjectedAttribute::Name(v) => Some(Attribute::Name(v.clone())),
+                            _ => None,
+                        });
+                    match expression {
+                        Some(expression) => {
+                            merged.push(ProjectedAttribute::Computed(expression, name.clone()))
+                        }
+                        None => return Ok(None),
+                    }
+                    continue;
+                }
+            };
+            match inner_attributes[index] {
+                // a constant is named for its position, so it may only stay where it is.
+                ProjectedAttribute::Constant(_) | ProjectedAttribute::Computed(_, None)
+                    if index != position =>
+                {
+                    return Ok(None)
+                }
+                inner_attribute => merged.push(inner_attribute.clone()),
+            }
+        }
+        Ok(Some(Projection::new(merged, inner.rhs().clone()).into()))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rule for RemoveRedundantRename {
+    fn name(&self) -> &'static str {
+        "remove-redundant-rename"
+    }
+
+    fn apply(&self, op: &RelationalOp, context: &RuleContext<'_>) -> Result<Option<RelationalOp>> {
+        let rename = match op {
+            RelationalOp::Rename(v) => v,
+            _ => return Ok(None),
+        };
+        let schema = context.schema_of(rename.rhs())?;
+        let mut retained: HashMap<Attribute, Name> = Default::default();
+        for (attribute, name) in rename.renames() {
+            let index = attribute_to_index(attribute, &schema)?;
+            if schema.attribute(index).unwrap().name() != name {
+                let _ = retained.insert(attribute.clone(), name.clone());
+            }
+        }
+        Ok(if retained.is_empty() {
+            Some(rename.rhs().clone())
+        } else if retained.len() < rename.count() {
+            Some(Rename::new(retained, rename.rhs().clone())?.into())
+        } else {
+            None
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Rewrite `term`, which references attributes in `output`, to reference the attributes in
+/// `target` where `map` gives the index in `target` of each index in `output`. Returns `None` if
+/// the term references any attribute not in `target`.
+///
+fn push_term(
+    term: &Term,
+    output: &SimpleRelationSchema,
+    target: &SimpleRelationSchema,
+    map: &[Option<usize>],
+) -> Option<Term> {
+    map_term_attributes(term, &mut |attribute| {
+        let mapped = map[attribute_to_index(attribute, output).ok()?]?;
+        match attribute {
+            Attribute::Name(name) if target.attribute_index(name) == Some(mapped) => {
+                Some(attribute.clone())
+            }
+            _ => Some(Attribute::Index(mapped)),
+        }
+    })
+}
+
+fn product_maps(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let lhs_map = (0..lhs.len())
+        .map(Some)
+        .chain((0..rhs.len()).map(|_| None))
+        .collect();
+    let rhs_map = (0..lhs.len())
+        .map(|_| None)
+        .chain((0..rhs.len()).map(Some))
+        .collect();
+    (lhs_map, rhs_map)
+}
+
+fn natural_join_maps(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+    common: &[(usize, usize)],
+) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let retained: Vec<usize> = (0..rhs.len())
+        .filter(|i| !common.iter().any(|(_, r)| r == i))
+        .collect();
+    let lhs_map = (0..lhs.len())
+        .map(Some)
+        .chain(retained.iter().map(|_| None))
+        .collect();
+    let rhs_map = (0..lhs.len())
+        .map(|i| common.iter().find(|(l, _)| *l == i).map(|(_, r)| *r))
+        .chain(retained.iter().map(|i| Some(*i)))
+        .collect();
+    (lhs_map, rhs_map)
+}
+
+fn rebuild_join(join: &RelationalOp, lhs: RelationalOp, rhs: RelationalOp) -> RelationalOp {
+    match join {
+        RelationalOp::Join(Join::Natural(_)) => NaturalJoin::new(lhs, rhs).into(),
+        RelationalOp::Join(Join::Theta(v)) => ThetaJoin::new(lhs, v.criteria().clone(), rhs).into(),
+        RelationalOp::SetOperation(v) => SetOperation::new(lhs, v.operator(), rhs).into(),
+        _ => unreachable!(),
+    }
+}
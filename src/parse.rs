@@ -0,0 +1,1217 @@
+/*!
+Provides a parser for the textual forms of the Relational Algebra produced by
+[`Format::to_formatted_string`](crate::ast::Format::to_formatted_string) using either
+[`DisplayFormat::ToStringUnicode`](crate::ast::DisplayFormat::ToStringUnicode) or
+[`DisplayFormat::ToStringAscii`](crate::ast::DisplayFormat::ToStringAscii).
+
+The two syntaxes may be freely mixed within a single expression, so `σ[a<b]r` and
+`select[a<b]r` both parse to the same [`RelationalOp`].
+
+| =Operator                | =Unicode | =ASCII          |
+| ------------------------ | -------- | --------------- |
+| set union                | `∪`      | `union`         |
+| set intersection         | `∩`      | `intersect`     |
+| set difference           | `∖`      | `difference`    |
+| set symmetric difference | `△`      | `symdifference` |
+| set cartesian product    | `×`      | `product`       |
+| Selection                | `σ[…]`   | `select[…]`     |
+| Projection               | `π[…]`   | `project[…]`    |
+| Rename                   | `ρ[…]`   | `rename[…]`     |
+| Order                    | `τ[…]`   | `sort[…]`       |
+| Group                    | `γ[…]`   | `group[…]`      |
+| natural join             | `⨝`      | `join`          |
+| theta join               | `⨝[…]`   | `theta[…]`      |
+| left outer join          | `⟕`      | `leftjoin`      |
+| right outer join         | `⟖`      | `rightjoin`     |
+| full outer join          | `⟗`      | `fulljoin`      |
+| left semi-join           | `⋉`      | `leftsemijoin`  |
+| right semi-join          | `⋊`      | `rightsemijoin` |
+| anti-join                | `▷`      | `antijoin`      |
+| division                 | `÷`      | `divide`        |
+| assignment               | `≔`      | `:=`            |
+
+Within criteria and projections an unadorned number is an attribute index, constant values
+use the following literal forms: `true`, `false`, `null`, `0x1f` (byte), `42u64`, `-42i64`, `4.2`,
+`'c'`, `"string"`, `[1, 2, 3]` (binary), `12.50dec` (decimal), and a string prefixed with one
+of the domains `date`, `time`, `timestamp`, `timestamptz`, or `interval`, as in
+`timestamptz"2024-02-29T12:30:00+01:00"` or `interval"P1DT12H"`. An attribute may be tested for null with
+`a is null` or `a is not null`.
+
+Either side of a comparison, and any projected attribute, may be a scalar expression built from
+attributes and constants with the operators `+`, `-`, `×` (`*`), `÷` (`div`), `mod`, and `‖`
+(`||`, concatenation), where the multiplicative operators bind tighter; the functions `upper`,
+`lower`, `length`, `substr`, `abs`, `round`, and `coalesce`; and the conditional form
+`case when a<0i64 then "neg" else "pos" end`. A computed attribute may be named as in a rename, so
+`π[a, (b + 1i64) × 2i64/c]r`.
+
+Criteria may also test membership of a list, `a ∈ {1i64, 2i64}` or `a in {1i64, 2i64}`, an
+inclusive range, `a between 1i64 and 9i64`, or an SQL pattern, with `like`, `not like`, `ilike`,
+or `not ilike`, as in `a like "x%"`.
+
+A group lists its grouping attributes followed by any aggregates, each written as a function
+`count`, `sum`, `avg`, `min`, or `max` applied to an attribute, `distinct` attribute, or, for
+`count` only, `*`; an aggregate may be named as in a rename, so `γ[a, count(*)/n, max(b)]r`.
+
+An order lists sort keys, each an attribute followed by an optional direction, `↑` or `asc`
+(the default), `↓` or `desc`, then optionally `collate` and a collation name, and then optionally
+`nulls first` or `nulls last`; so `τ[a↓, b collate nocase nulls first]r`.
+
+An outer join, semi-join, or anti-join is natural unless followed by bracketed criteria, as in
+`r ⟕[0=2] s` or `r ▷[0=2] s`.
+
+# Example
+
+```rust
+use relational_algebra::ast::RelationalOp;
+use std::str::FromStr;
+
+let expr = RelationalOp::from_str("π[a, b](σ[a<b]relation)").unwrap();
+assert!(expr.is_projection());
+assert_eq!(expr.to_string(), "π[a, b](σ[a<b]relation)");
+```
+
+ */
+
+use crate::ast::{
+    Aggregate, AggregateFunction, Atom, Attribute, ComparisonOperator, Expression, ExpressionList,
+    NullOrdering, OuterJoinKind, ProjectedAttribute, RelationalOp, Rename, ScalarExpression,
+    ScalarFunction, ScalarOperator, SemiJoinKind, SetOperation, SetOperator, SortDirection,
+    SortKey, Term,
+};
+use crate::data::{Decimal, Value};
+use crate::error::{parse_error, Error, Location, ParseKind, Result, Span};
+use crate::sort::Domain;
+use crate::Name;
+use combine::easy::{Error as EasyError, Errors as EasyErrors};
+use combine::error::StreamError;
+use combine::parser::char::{char, digit, hex_digit, spaces, string};
+use combine::stream::{position, StreamErrorFor};
+use combine::{
+    attempt, between, chainl1, choice, count_min_max, eof, look_ahead, many, many1, none_of,
+    not_followed_by, optional, parser, satisfy, sep_by, sep_by1, sep_end_by, EasyParser, Parser,
+    Stream,
+};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a list of expressions, each terminated by `;`; the terminator on the last expression
+/// is optional.
+///
+pub fn parse_expression_list(s: &str) -> Result<ExpressionList> {
+    parse_all(s, ParseKind::Expression, spaces().with(expression_list()))
+}
+
+///
+/// Parse a single, optionally named, expression.
+///
+pub fn parse_expression(s: &str) -> Result<Expression> {
+    parse_all(s, ParseKind::Expression, spaces().with(expression()))
+}
+
+///
+/// Parse a single relational operation.
+///
+pub fn parse_relational(s: &str) -> Result<RelationalOp> {
+    parse_all(s, ParseKind::Expression, spaces().with(relational()))
+}
+
+///
+/// Parse a term, as used for the criteria of selections and theta joins.
+///
+pub fn parse_term(s: &str) -> Result<Term> {
+    parse_all(s, ParseKind::Expression, spaces().with(term()))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FromStr for ExpressionList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_expression_list(s)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_expression(s)
+    }
+}
+
+impl FromStr for RelationalOp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_relational(s)
+    }
+}
+
+impl FromStr for Term {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_term(s)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+type PositionStream<'a> = position::Stream<&'a str, position::SourcePosition>;
+
+#[derive(Clone)]
+enum Infix {
+    Set(SetOperator),
+    Natural,
+    Theta(Term),
+    Outer(OuterJoinKind, Option<Term>),
+    Semi(SemiJoinKind, Option<Term>),
+    Anti(Option<Term>),
+    Division,
+}
+
+enum GroupItem {
+    Attribute(Attribute),
+    Aggregate(Aggregate),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn parse_all<'a, T, P>(s: &'a str, kind: ParseKind, parser: P) -> Result<T>
+where
+    P: Parser<combine::easy::Stream<PositionStream<'a>>, Output = T>,
+{
+    parser
+        .skip(eof())
+        .easy_parse(position::Stream::new(s))
+        .map(|(result, _)| result)
+        .map_err(|e| easy_errors_to_error(kind, s, e))
+}
+
+fn easy_errors_to_error(
+    kind: ParseKind,
+    input: &str,
+    errors: EasyErrors<char, &str, position::SourcePosition>,
+) -> Error {
+    let start = Location::new(
+        errors.position.line as usize,
+        errors.position.column as usize,
+    );
+    let fragment = fragment_at(input, start);
+    let end = Location::new(
+        start.line,
+        start.column
+            + fragment
+                .as_ref()
+                .map(|s| s.chars().count())
+                .unwrap_or_default(),
+    );
+
+    let mut expected: Vec<String> = Default::default();
+    let mut messages: Vec<String> = Default::default();
+    for error in errors.errors {
+        match error {
+            EasyError::Expected(info) => {
+                let info = info.to_string();
+                if !info.starts_with("whitespace") && !expected.contains(&info) {
+                    expected.push(info);
+                }
+            }
+            EasyError::Message(info) => messages.push(info.to_string()),
+            EasyError::Other(e) => messages.push(e.to_string()),
+            EasyError::Unexpected(_) => {}
+        }
+    }
+
+    parse_error(
+        kind,
+        input,
+        Span::new(start, end),
+        expected,
+        fragment,
+        if messages.is_empty() {
+            None
+        } else {
+            Some(messages.join("; "))
+        },
+    )
+}
+
+///
+/// Returns the token at `location`, either a complete identifier or number, or a single
+/// character; `None` is returned at the end of the input.
+///
+fn fragment_at(input: &str, location: Location) -> Option<String> {
+    let line = input.lines().nth(location.line.saturating_sub(1))?;
+    let mut chars = line
+        .chars()
+        .skip(location.column.saturating_sub(1))
+        .peekable();
+    let first = *chars.peek()?;
+    if is_identifier_continue(first) {
+        Some(chars.take_while(|c| is_identifier_continue(*c)).collect())
+    } else {
+        Some(first.to_string())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Lexical
+
+pub(crate) fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+pub(crate) fn is_identifier_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+pub(crate) fn lex<Input, P>(p: P) -> impl Parser<Input, Output = P::Output>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    p.skip(spaces())
+}
+
+pub(crate) fn symbol<Input>(c: char) -> impl Parser<Input, Output = char>
+where
+    Input: Stream<Token = char>,
+{
+    lex(char(c))
+}
+
+pub(crate) fn keyword<Input>(k: &'static str) -> impl Parser<Input, Output = &'static str>
+where
+    Input: Stream<Token = char>,
+{
+    lex(attempt(
+        string(k).skip(not_followed_by(satisfy(is_identifier_continue))),
+    ))
+}
+
+///
+/// An operator that may be written either with a Unicode symbol or an ASCII keyword.
+///
+fn operator<Input>(
+    unicode: &'static str,
+    ascii: &'static str,
+) -> impl Parser<Input, Output = &'static str>
+where
+    Input: Stream<Token = char>,
+{
+    choice((lex(attempt(string(unicode))), keyword(ascii)))
+        .silent()
+        .expected(unicode)
+}
+
+pub(crate) fn identifier<Input>() -> impl Parser<Input, Output = Name>
+where
+    Input: Stream<Token = char>,
+{
+    lex((
+        satisfy(is_identifier_start),
+        many::<String, _, _>(satisfy(is_identifier_continue)),
+    ))
+    .map(|(c, mut s)| {
+        s.insert(0, c);
+        Name::new_unchecked(&s)
+    })
+    .expected("name")
+}
+
+fn index<Input>() -> impl Parser<Input, Output = usize>
+where
+    Input: Stream<Token = char>,
+{
+    lex(many1::<String, _, _>(digit()))
+        .and_then(|s| {
+            s.parse::<usize>().map_err(|_| {
+                StreamErrorFor::<Input>::message_format(format!("invalid index `{}`", s))
+            })
+        })
+        .expected("attribute index")
+}
+
+// ------------------------------------------------------------------------------------------------
+// Literal Values
+
+fn value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    lex(choice((
+        attempt(byte_value()),
+        number().and_then(|number| match number {
+            ProjectedAttribute::Constant(v) => Ok(v),
+            _ => Err(StreamErrorFor::<Input>::message_static_message(
+                "a number without a suffix is an attribute index, not a value",
+            )),
+        }),
+        non_numeric_value(),
+    ))
+    .silent())
+    .expected("literal value")
+}
+
+fn non_numeric_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        attempt(
+            string("true")
+                .skip(not_followed_by(satisfy(is_identifier_continue)))
+                .map(|_| Value::Boolean(true)),
+        ),
+        attempt(
+            string("false")
+                .skip(not_followed_by(satisfy(is_identifier_continue)))
+                .map(|_| Value::Boolean(false)),
+        ),
+        attempt(
+            string("null")
+                .skip(not_followed_by(satisfy(is_identifier_continue)))
+                .map(|_| Value::Null),
+        ),
+        typed_value(),
+        char_value(),
+        string_value(),
+        binary_value(),
+    ))
+}
+
+///
+/// Parses a string prefixed with the name of the domain it is to be parsed as, such as
+/// `date"2024-02-29"`; see [`Value::parse`] for the forms of each domain.
+///
+fn typed_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        attempt(
+            choice((
+                attempt(string("date")).map(|_| Domain::Date),
+                attempt(string("timestamptz")).map(|_| Domain::TimestampTz),
+                attempt(string("timestamp")).map(|_| Domain::Timestamp),
+                attempt(string("time")).map(|_| Domain::Time),
+                string("interval").map(|_| Domain::Interval),
+            ))
+            .skip(look_ahead(char('"'))),
+        ),
+        quoted_string(),
+    )
+        .and_then(|(domain, s)| {
+            Value::parse(domain, &s).map_err(|_| {
+                StreamErrorFor::<Input>::message_format(format!(
+                    "invalid {} literal `{}`",
+                    domain, s
+                ))
+            })
+        })
+}
+
+fn byte_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    string("0x")
+        .with(count_min_max::<String, _, _>(1, 2, hex_digit()))
+        .skip(not_followed_by(satisfy(is_identifier_continue)))
+        .map(|s| Value::Byte(u8::from_str_radix(&s, 16).unwrap()))
+}
+
+///
+/// Parses any numeric literal; an unsigned number with no fraction, exponent, or type suffix
+/// is returned as an attribute index, all others as constant values.
+///
+fn number<Input>() -> impl Parser<Input, Output = ProjectedAttribute>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        optional(choice((char('-'), char('+')))),
+        many1::<String, _, _>(digit()),
+        optional(attempt(char('.').with(many1::<String, _, _>(digit())))),
+        optional(attempt((
+            choice((char('e'), char('E'))),
+            optional(choice((char('-'), char('+')))),
+            many1::<String, _, _>(digit()),
+        ))),
+        optional(choice((
+            attempt(string("i64")),
+            attempt(string("u64")),
+            attempt(string("u8")),
+            attempt(string("f64")),
+            string("dec"),
+        ))),
+    )
+        .skip(not_followed_by(satisfy(is_identifier_continue)))
+        .and_then(|(sign, whole, fraction, exponent, suffix)| {
+            let mut text = String::new();
+            if let Some(sign) = sign {
+                text.push(sign);
+            }
+            text.push_str(&whole);
+            let is_float = fraction.is_some() || exponent.is_some();
+            if let Some(fraction) = fraction {
+                text.push('.');
+                text.push_str(&fraction);
+            }
+            if let Some((e, sign, digits)) = exponent {
+                text.push(e);
+                if let Some(sign) = sign {
+                    text.push(sign);
+                }
+                text.push_str(&digits);
+            }
+            let value = match (suffix, is_float, sign.is_some()) {
+                (None, false, false) => text.parse::<usize>().ok().map(ProjectedAttribute::Index),
+                (Some("f64"), _, _) | (None, true, _) => {
+                    text.parse::<f64>().ok().map(|v| Value::Float(v).into())
+                }
+                (Some("i64"), false, _) | (None, false, true) => {
+                    text.parse::<i64>().ok().map(|v| Value::Integer(v).into())
+                }
+                (Some("u64"), false, _) => text
+                    .parse::<u64>()
+                    .ok()
+                    .map(|v| Value::UnsignedInteger(v).into()),
+                (Some("u8"), false, _) => text.parse::<u8>().ok().map(|v| Value::Byte(v).into()),
+                (Some("dec"), _, _) => Decimal::from_str(&text)
+                    .or_else(|_| Decimal::from_scientific(&text))
+                    .ok()
+                    .map(|v| Value::Decimal(v).into()),
+                _ => None,
+            };
+            value.ok_or_else(|| {
+                StreamErrorFor::<Input>::message_format(format!(
+                    "invalid numeric literal `{}{}`",
+                    text,
+                    suffix.unwrap_or_default()
+                ))
+            })
+        })
+}
+
+fn escaped_char<Input>(quote: char) -> impl Parser<Input, Output = char>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        char('\\').with(choice((
+            char('n').map(|_| '\n'),
+            char('r').map(|_| '\r'),
+            char('t').map(|_| '\t'),
+            char('0').map(|_| '\0'),
+            char('\\'),
+            char('\''),
+            char('"'),
+            char('u')
+                .with(between(
+                    char('{'),
+                    char('}'),
+                    many1::<String, _, _>(hex_digit()),
+                ))
+                .and_then(|s| {
+                    u32::from_str_radix(&s, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| {
+                            StreamErrorFor::<Input>::message_format(format!(
+                                "invalid unicode escape `\\u{{{}}}`",
+                                s
+                            ))
+                        })
+                }),
+        ))),
+        none_of([quote, '\\']),
+    ))
+}
+
+fn char_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    between(char('\''), char('\''), escaped_char('\'')).map(Value::Char)
+}
+
+fn string_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    quoted_string().map(Value::String)
+}
+
+fn quoted_string<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+{
+    between(
+        char('"'),
+        char('"'),
+        many::<String, _, _>(escaped_char('"')),
+    )
+}
+
+fn binary_value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    between(
+        symbol('['),
+        char(']'),
+        sep_by(
+            lex(many1::<String, _, _>(digit())).and_then(|s| {
+                s.parse::<u8>().map_err(|_| {
+                    StreamErrorFor::<Input>::message_format(format!("invalid byte `{}`", s))
+                })
+            }),
+            symbol(','),
+        ),
+    )
+    .map(Value::Binary)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Attributes
+
+fn attribute<Input>() -> impl Parser<Input, Output = Attribute>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        index().map(Attribute::Index),
+        identifier().map(Attribute::Name),
+    ))
+    .silent()
+    .expected("attribute")
+}
+
+fn projected_attribute<Input>() -> impl Parser<Input, Output = ProjectedAttribute>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        lex(choice((
+            attempt(byte_value()).map(ProjectedAttribute::Constant),
+            number(),
+            non_numeric_value().map(ProjectedAttribute::Constant),
+        ))),
+        identifier().map(ProjectedAttribute::Name),
+    ))
+    .silent()
+    .expected("attribute, or literal value")
+}
+
+fn bracketed<Input, P>(p: P) -> impl Parser<Input, Output = P::Output>
+where
+    Input: Stream<Token = char>,
+    P: Parser<Input>,
+{
+    between(symbol('['), symbol(']'), p)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Scalar Expressions
+
+fn additive_operator<Input>() -> impl Parser<Input, Output = ScalarOperator>
+where
+    Input: Stream<Token = char>,
+{
+    lex(choice((
+        char('+').map(|_| ScalarOperator::Add),
+        char('-').map(|_| ScalarOperator::Subtract),
+        char('−').map(|_| ScalarOperator::Subtract),
+        char('‖').map(|_| ScalarOperator::Concatenate),
+        attempt(string("||")).map(|_| ScalarOperator::Concatenate),
+    )))
+    .silent()
+    .expected("arithmetic operator")
+}
+
+fn multiplicative_operator<Input>() -> impl Parser<Input, Output = ScalarOperator>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        lex(choice((
+            char('×').map(|_| ScalarOperator::Multiply),
+            char('*').map(|_| ScalarOperator::Multiply),
+            char('÷').map(|_| ScalarOperator::Divide),
+        ))),
+        keyword("div").map(|_| ScalarOperator::Divide),
+        keyword("mod").map(|_| ScalarOperator::Modulo),
+    ))
+    .silent()
+    .expected("arithmetic operator")
+}
+
+fn scalar_<Input>() -> impl Parser<Input, Output = ScalarExpression>
+where
+    Input: Stream<Token = char>,
+{
+    chainl1(
+        chainl1(
+            scalar_operand(),
+            multiplicative_operator()
+                .map(|op| move |lhs, rhs| ScalarExpression::operation(lhs, op, rhs)),
+        ),
+        additive_operator().map(|op| move |lhs, rhs| ScalarExpression::operation(lhs, op, rhs)),
+    )
+}
+
+parser! {
+    fn scalar[Input]()(Input) -> ScalarExpression
+    where [Input: Stream<Token = char>]
+    {
+        scalar_()
+    }
+}
+
+fn scalar_function<Input>() -> impl Parser<Input, Output = ScalarFunction>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        keyword("upper").map(|_| ScalarFunction::Upper),
+        keyword("lower").map(|_| ScalarFunction::Lower),
+        keyword("length").map(|_| ScalarFunction::Length),
+        keyword("substr").map(|_| ScalarFunction::Substring),
+        keyword("abs").map(|_| ScalarFunction::Absolute),
+        keyword("round").map(|_| ScalarFunction::Round),
+        keyword("coalesce").map(|_| ScalarFunction::Coalesce),
+    ))
+}
+
+fn scalar_operand<Input>() -> impl Parser<Input, Output = ScalarExpression>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        between(symbol('('), symbol(')'), scalar()),
+        (
+            attempt(keyword("case").skip(look_ahead(keyword("when")))),
+            many1::<Vec<_>, _, _>((keyword("when").with(term()), keyword("then").with(scalar()))),
+            optional(keyword("else").with(scalar())),
+            keyword("end"),
+        )
+            .map(|(_, branches, otherwise, _)| ScalarExpression::case(branches, otherwise)),
+        (
+            attempt(scalar_function().skip(symbol('('))),
+            sep_by1::<Vec<_>, _, _, _>(scalar(), symbol(',')),
+            symbol(')'),
+        )
+            .and_then(|(function, arguments, _)| {
+                if function.accepts(arguments.len()) {
+                    Ok(ScalarExpression::function(function, arguments))
+                } else {
+                    Err(StreamErrorFor::<Input>::message_format(format!(
+                        "the function {} cannot be called with {} argument(s)",
+                        function,
+                        arguments.len()
+                    )))
+                }
+            }),
+        projected_attribute().map(ScalarExpression::from),
+    ))
+}
+
+///
+/// A projected attribute is any scalar expression, optionally named as in a rename; a simple
+/// attribute or constant without a name is not a computed attribute.
+///
+fn computed_attribute<Input>() -> impl Parser<Input, Output = ProjectedAttribute>
+where
+    Input: Stream<Token = char>,
+{
+    (scalar(), optional(symbol('/').with(identifier()))).map(|(expression, name)| match name {
+        None => expression.into(),
+        Some(name) => ProjectedAttribute::Computed(expression, Some(name)),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Terms
+
+fn comparison_operator<Input>() -> impl Parser<Input, Output = ComparisonOperator>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        lex(choice((
+            attempt(string("/=")).map(|_| ComparisonOperator::NotEqual),
+            attempt(string("/~")).map(|_| ComparisonOperator::StringNotMatch),
+            attempt(string("<=")).map(|_| ComparisonOperator::LessThanOrEqual),
+            attempt(string(">=")).map(|_| ComparisonOperator::GreaterThanOrEqual),
+            char('=').map(|_| ComparisonOperator::Equal),
+            char('≠').map(|_| ComparisonOperator::NotEqual),
+            char('<').map(|_| ComparisonOperator::LessThan),
+            char('≤').map(|_| ComparisonOperator::LessThanOrEqual),
+            char('>').map(|_| ComparisonOperator::GreaterThan),
+            char('≥').map(|_| ComparisonOperator::GreaterThanOrEqual),
+            char('~').map(|_| ComparisonOperator::StringMatch),
+            char('≁').map(|_| ComparisonOperator::StringNotMatch),
+        ))),
+        attempt(keyword("not").with(choice((
+            keyword("like").map(|_| ComparisonOperator::NotLike),
+            keyword("ilike").map(|_| ComparisonOperator::NotILike),
+        )))),
+        keyword("like").map(|_| ComparisonOperator::Like),
+        keyword("ilike").map(|_| ComparisonOperator::ILike),
+    ))
+    .silent()
+    .expected("comparison operator")
+}
+
+fn term_<Input>() -> impl Parser<Input, Output = Term>
+where
+    Input: Stream<Token = char>,
+{
+    (conjunction(), optional(operator("∨", "or").with(term()))).map(|(lhs, rhs)| match rhs {
+        None => lhs,
+        Some(rhs) => Term::or(lhs, rhs),
+    })
+}
+
+parser! {
+    fn term[Input]()(Input) -> Term
+    where [Input: Stream<Token = char>]
+    {
+        term_()
+    }
+}
+
+fn conjunction_<Input>() -> impl Parser<Input, Output = Term>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        negation(),
+        optional(operator("∧", "and").with(conjunction())),
+    )
+        .map(|(lhs, rhs)| match rhs {
+            None => lhs,
+            Some(rhs) => Term::and(lhs, rhs),
+        })
+}
+
+parser! {
+    fn conjunction[Input]()(Input) -> Term
+    where [Input: Stream<Token = char>]
+    {
+        conjunction_()
+    }
+}
+
+fn negation_<Input>() -> impl Parser<Input, Output = Term>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        operator("¬", "not").with(negation()).map(Term::negate),
+        primary_term(),
+    ))
+}
+
+parser! {
+    fn negation[Input]()(Input) -> Term
+    where [Input: Stream<Token = char>]
+    {
+        negation_()
+    }
+}
+
+fn primary_term<Input>() -> impl Parser<Input, Output = Term>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        (attempt((scalar(), comparison_operator())), scalar())
+            .map(|((lhs, op), rhs)| Term::Atom(Atom::new(lhs, op, rhs))),
+        (
+            attempt(scalar().skip(operator("∈", "in"))),
+            between(symbol('{'), symbol('}'), sep_by1(scalar(), symbol(','))),
+        )
+            .map(|(value, list)| Term::In(value, list)),
+        (
+            attempt(scalar().skip(keyword("between"))),
+            scalar(),
+            operator("∧", "and"),
+            scalar(),
+        )
+            .map(|(value, low, _, high)| Term::Between(value, low, high)),
+        between(symbol('('), symbol(')'), term()),
+        symbol('?').with(attribute()).map(Term::Exists),
+        (
+            attempt((attribute(), keyword("is"))),
+            optional(keyword("not")),
+            keyword("null"),
+        )
+            .map(|((attribute, _), not, _)| match not {
+                None => Term::IsNull(attribute),
+                Some(_) => Term::IsNotNull(attribute),
+            }),
+        value().map(Term::Constant),
+    ))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Relational Operations
+
+fn rename_list<Input>() -> impl Parser<Input, Output = Vec<(Attribute, Option<Name>)>>
+where
+    Input: Stream<Token = char>,
+{
+    sep_by1(
+        (attribute(), optional(symbol('/').with(identifier()))),
+        symbol(','),
+    )
+}
+
+fn make_rename<Input>(
+    renames: Vec<(Attribute, Option<Name>)>,
+    rhs: RelationalOp,
+) -> std::result::Result<RelationalOp, StreamErrorFor<Input>>
+where
+    Input: Stream<Token = char>,
+{
+    let unique_names: HashSet<&Name> = renames
+        .iter()
+        .filter_map(|(attribute, name)| name.as_ref().or_else(|| attribute.as_name()))
+        .collect();
+    if unique_names.len() != renames.len()
+        && renames
+            .iter()
+            .all(|(attribute, name)| name.is_some() || attribute.is_name())
+    {
+        return Err(StreamErrorFor::<Input>::message_static_message(
+            "rename targets must be unique names",
+        ));
+    }
+    let result = if renames.iter().all(|(_, name)| name.is_some()) {
+        Rename::new(
+            renames
+                .into_iter()
+                .map(|(attribute, name)| (attribute, name.unwrap()))
+                .collect::<HashMap<Attribute, Name>>(),
+            rhs,
+        )
+    } else if renames
+        .iter()
+        .all(|(attribute, name)| name.is_none() && attribute.is_name())
+    {
+        Rename::new_indexed(
+            renames
+                .into_iter()
+                .map(|(attribute, _)| attribute.as_name().unwrap().clone())
+                .collect(),
+            rhs,
+        )
+    } else {
+        return Err(StreamErrorFor::<Input>::message_static_message(
+            "rename requires either a list of names, or a list of `attribute/name` pairs",
+        ));
+    };
+    result
+        .map(RelationalOp::from)
+        .map_err(StreamErrorFor::<Input>::message_format)
+}
+
+fn sort_key<Input>() -> impl Parser<Input, Output = SortKey>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        attribute(),
+        optional(choice((
+            operator("↑", "asc").map(|_| SortDirection::Ascending),
+            operator("↓", "desc").map(|_| SortDirection::Descending),
+        ))),
+        optional(keyword("collate").with(identifier())),
+        optional(keyword("nulls").with(choice((
+            keyword("first").map(|_| NullOrdering::First),
+            keyword("last").map(|_| NullOrdering::Last),
+        )))),
+    )
+        .map(|(attribute, direction, collation, nulls)| {
+            let key = SortKey::new(attribute, direction.unwrap_or_default());
+            let key = match collation {
+                None => key,
+                Some(collation) => key.with_collation(collation),
+            };
+            match nulls {
+                None => key,
+                Some(nulls) => key.with_nulls(nulls),
+            }
+        })
+        .expected("sort key")
+}
+
+fn aggregate_function<Input>() -> impl Parser<Input, Output = AggregateFunction>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        keyword("count").map(|_| AggregateFunction::Count),
+        keyword("sum").map(|_| AggregateFunction::Sum),
+        keyword("avg").map(|_| AggregateFunction::Average),
+        keyword("min").map(|_| AggregateFunction::Minimum),
+        keyword("max").map(|_| AggregateFunction::Maximum),
+    ))
+}
+
+fn aggregate<Input>() -> impl Parser<Input, Output = Aggregate>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        attempt(aggregate_function().skip(symbol('('))),
+        choice((
+            symbol('*').map(|_| None),
+            (optional(keyword("distinct")), attribute()).map(Some),
+        )),
+        symbol(')'),
+        optional(symbol('/').with(identifier())),
+    )
+        .and_then(|(function, argument, _, name)| {
+            let aggregate = match argument {
+                None if function == AggregateFunction::Count => Aggregate::count_all(),
+                None => {
+                    return Err(StreamErrorFor::<Input>::message_format(format!(
+                        "only count may be applied to `*`, not {}",
+                        function
+                    )))
+                }
+                Some((None, argument)) => Aggregate::new(function, argument),
+                Some((Some(_), argument)) => Aggregate::new_distinct(function, argument),
+            };
+            Ok(match name {
+                None => aggregate,
+                Some(name) => aggregate.named(name),
+            })
+        })
+        .expected("aggregate")
+}
+
+fn group_list<Input>() -> impl Parser<Input, Output = (Vec<Attribute>, Vec<Aggregate>)>
+where
+    Input: Stream<Token = char>,
+{
+    sep_by1::<Vec<_>, _, _, _>(
+        choice((
+            aggregate().map(GroupItem::Aggregate),
+            attribute().map(GroupItem::Attribute),
+        )),
+        symbol(','),
+    )
+    .and_then(|items| {
+        let mut attributes: Vec<Attribute> = Default::default();
+        let mut aggregates: Vec<Aggregate> = Default::default();
+        for item in items {
+            match item {
+                GroupItem::Attribute(attribute) if aggregates.is_empty() => {
+                    attributes.push(attribute)
+                }
+                GroupItem::Attribute(_) => {
+                    return Err(StreamErrorFor::<Input>::message_static_message(
+                        "grouping attributes must precede aggregates",
+                    ))
+                }
+                GroupItem::Aggregate(aggregate) => aggregates.push(aggregate),
+            }
+        }
+        Ok((attributes, aggregates))
+    })
+}
+
+fn prefix_operator<Input>(
+    unicode: &'static str,
+    ascii: &'static str,
+) -> impl Parser<Input, Output = &'static str>
+where
+    Input: Stream<Token = char>,
+{
+    attempt(operator(unicode, ascii).skip(symbol('['))).silent()
+}
+
+fn prefix_operation<Input>() -> impl Parser<Input, Output = RelationalOp>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        (
+            prefix_operator("σ", "select"),
+            term(),
+            symbol(']'),
+            operand(),
+        )
+            .map(|(_, criteria, _, rhs)| RelationalOp::select(criteria, rhs)),
+        (
+            prefix_operator("π", "project"),
+            sep_by1(computed_attribute(), symbol(',')),
+            symbol(']'),
+            operand(),
+        )
+            .map(|(_, attributes, _, rhs)| RelationalOp::project(attributes, rhs)),
+        (
+            prefix_operator("ρ", "rename"),
+            rename_list(),
+            symbol(']'),
+            operand(),
+        )
+            .and_then(|(_, renames, _, rhs)| make_rename::<Input>(renames, rhs)),
+        (
+            prefix_operator("τ", "sort"),
+            sep_by1(sort_key(), symbol(',')),
+            symbol(']'),
+            operand(),
+        )
+            .map(|(_, keys, _, rhs)| RelationalOp::sort_by_keys(keys, rhs)),
+        (
+            prefix_operator("γ", "group"),
+            group_list(),
+            symbol(']'),
+            operand(),
+        )
+            .map(|(_, (attributes, aggregates), _, rhs)| {
+                RelationalOp::aggregate(attributes, aggregates, rhs)
+            }),
+    ))
+}
+
+fn operand_<Input>() -> impl Parser<Input, Output = RelationalOp>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        between(symbol('('), symbol(')'), relational()),
+        prefix_operation(),
+        identifier().map(RelationalOp::Relation),
+    ))
+    .expected("relation")
+}
+
+parser! {
+    fn operand[Input]()(Input) -> RelationalOp
+    where [Input: Stream<Token = char>]
+    {
+        operand_()
+    }
+}
+
+fn infix_operator<Input>() -> impl Parser<Input, Output = Infix>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        operator("∪", "union").map(|_| Infix::Set(SetOperator::Union)),
+        operator("∩", "intersect").map(|_| Infix::Set(SetOperator::Intersection)),
+        operator("∖", "difference").map(|_| Infix::Set(SetOperator::Difference)),
+        operator("△", "symdifference").map(|_| Infix::Set(SetOperator::SymmetricDifference)),
+        operator("×", "product").map(|_| Infix::Set(SetOperator::CartesianProduct)),
+        keyword("theta").with(bracketed(term())).map(Infix::Theta),
+        keyword("join").map(|_| Infix::Natural),
+        symbol('⨝')
+            .with(optional(bracketed(term())))
+            .map(|criteria| match criteria {
+                None => Infix::Natural,
+                Some(criteria) => Infix::Theta(criteria),
+            }),
+        choice((
+            operator("⟕", "leftjoin").map(|_| OuterJoinKind::Left),
+            operator("⟖", "rightjoin").map(|_| OuterJoinKind::Right),
+            operator("⟗", "fulljoin").map(|_| OuterJoinKind::Full),
+        ))
+        .and(optional(bracketed(term())))
+        .map(|(kind, criteria)| Infix::Outer(kind, criteria)),
+        choice((
+            operator("⋉", "leftsemijoin").map(|_| SemiJoinKind::Left),
+            operator("⋊", "rightsemijoin").map(|_| SemiJoinKind::Right),
+        ))
+        .and(optional(bracketed(term())))
+        .map(|(kind, criteria)| Infix::Semi(kind, criteria)),
+        operator("▷", "antijoin")
+            .with(optional(bracketed(term())))
+            .map(Infix::Anti),
+        operator("÷", "divide").map(|_| Infix::Division),
+    ))
+    .expected("binary operator")
+}
+
+fn relational_<Input>() -> impl Parser<Input, Output = RelationalOp>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        operand(),
+        many::<Vec<_>, _, _>(
+            infix_operator().then(|op| operand().map(move |rhs| (op.clone(), rhs))),
+        ),
+    )
+        .map(|(first, rest)| {
+            rest.into_iter().fold(first, |lhs, (op, rhs)| match op {
+                Infix::Set(op) => SetOperation::new(lhs, op, rhs).into(),
+                Infix::Natural => RelationalOp::natural_join(lhs, rhs),
+                Infix::Theta(criteria) => RelationalOp::theta_join(lhs, criteria, rhs),
+                Infix::Outer(kind, None) => RelationalOp::outer_join(lhs, kind, rhs),
+                Infix::Outer(kind, Some(criteria)) => {
+                    RelationalOp::outer_theta_join(lhs, kind, criteria, rhs)
+                }
+                Infix::Semi(kind, None) => RelationalOp::semi_join(lhs, kind, rhs),
+                Infix::Semi(kind, Some(criteria)) => {
+                    RelationalOp::semi_theta_join(lhs, kind, criteria, rhs)
+                }
+                Infix::Anti(None) => RelationalOp::anti_join(lhs, rhs),
+                Infix::Anti(Some(criteria)) => RelationalOp::anti_theta_join(lhs, criteria, rhs),
+                Infix::Division => RelationalOp::division(lhs, rhs),
+            })
+        })
+}
+
+parser! {
+    fn relational[Input]()(Input) -> RelationalOp
+    where [Input: Stream<Token = char>]
+    {
+        relational_()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Expressions
+
+fn expression<Input>() -> impl Parser<Input, Output = Expression>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        optional(attempt(
+            identifier().skip(lex(choice((string("≔"), string(":="))))),
+        )),
+        relational(),
+    )
+        .map(|(name, expr)| match name {
+            None => Expression::new(expr),
+            Some(name) => Expression::named(name, expr),
+        })
+}
+
+fn expression_list<Input>() -> impl Parser<Input, Output = ExpressionList>
+where
+    Input: Stream<Token = char>,
+{
+    sep_end_by::<Vec<Expression>, _, _, _>(expression(), symbol(';')).map(ExpressionList::from)
+}
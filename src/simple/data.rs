@@ -5,10 +5,11 @@ Provides a [`Relation`] and [`Tuple`] implementation for a simple in-memory data
 
  */
 
-use crate::data::{Relation, Tuple, Value};
+use crate::data::{Relation, Statistics, Tuple, Value};
+use crate::error::{incompatible_arity, invalid_value, Result};
 use crate::simple::sort::SimpleRelationSchema;
-use crate::sort::RelationSchema;
-use std::{collections::HashSet, fmt::Display};
+use crate::sort::{AttributeSchema, RelationSchema};
+use std::{collections::HashSet, fmt::Display, hash::Hash};
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -18,18 +19,40 @@ use std::{collections::HashSet, fmt::Display};
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+///
+/// A relation held in memory; tuples are unique, and are returned in the order in which they
+/// were inserted.
+///
 #[derive(Clone, Debug)]
 pub struct SimpleRelation {
     schema: SimpleRelationSchema,
-    tuples: HashSet<SimpleTuple>,
+    tuples: Vec<SimpleTuple>,
+    members: HashSet<SimpleTuple>,
+}
+
+///
+/// A snapshot of the [`Statistics`] for a relation; this may be computed from a [`SimpleRelation`]
+/// or constructed from statistics held elsewhere.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimpleStatistics {
+    cardinality: usize,
+    attributes: Vec<SimpleAttributeStatistics>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimpleAttributeStatistics {
+    distinct_count: Option<usize>,
+    min_value: Option<Value>,
+    max_value: Option<Value>,
 }
 
 #[derive(Debug)]
 pub struct Tuples<'a> {
-    iter: std::collections::hash_set::Iter<'a, SimpleTuple>,
+    iter: std::slice::Iter<'a, SimpleTuple>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct SimpleTuple(Vec<Value>);
 
 #[derive(Debug)]
@@ -70,6 +93,155 @@ impl Relation for SimpleRelation {
     }
 }
 
+impl Statistics for SimpleRelation {
+    fn cardinality(&self) -> usize {
+        self.len()
+    }
+
+    fn distinct_count(&self, attribute: usize) -> Option<usize> {
+        if attribute < self.schema.len() {
+            Some(
+                self.tuples
+                    .iter()
+                    .map(|t| &t.0[attribute])
+                    .collect::<HashSet<&Value>>()
+                    .len(),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn min_value(&self, attribute: usize) -> Option<Value> {
+        self.attribute_values(attribute).min().cloned()
+    }
+
+    fn max_value(&self, attribute: usize) -> Option<Value> {
+        self.attribute_values(attribute).max().cloned()
+    }
+}
+
+impl SimpleRelation {
+    pub fn new(schema: SimpleRelationSchema) -> Self {
+        Self {
+            schema,
+            tuples: Default::default(),
+            members: Default::default(),
+        }
+    }
+
+    ///
+    /// Create a relation from the provided tuples, each of which is checked against the schema.
+    ///
+    pub fn from_tuples<I>(schema: SimpleRelationSchema, tuples: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = SimpleTuple>,
+    {
+        let mut relation = Self::new(schema);
+        for tuple in tuples {
+            let _ = relation.insert(tuple)?;
+        }
+        Ok(relation)
+    }
+
+    ///
+    /// Create a copy of any other [`Relation`] implementation.
+    ///
+    pub fn from_relation<R>(relation: &R) -> Self
+    where
+        R: Relation,
+    {
+        let mut result = Self::new(SimpleRelationSchema::from_schema(relation.schema()));
+        for tuple in relation.tuples() {
+            result.insert_unchecked(SimpleTuple::from(
+                tuple.values().cloned().collect::<Vec<Value>>(),
+            ));
+        }
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.tuples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tuples.is_empty()
+    }
+
+    pub fn contains(&self, tuple: &SimpleTuple) -> bool {
+        self.members.contains(tuple)
+    }
+
+    ///
+    /// Add a tuple to this relation, checking that it conforms to the relation's schema; a null
+    /// value conforms to any nullable attribute. This returns `true` if the tuple was added, or `false` if
+    /// the relation already contained it.
+    ///
+    pub fn insert(&mut self, tuple: SimpleTuple) -> Result<bool> {
+        if tuple.len() != self.schema.len() {
+            return Err(incompatible_arity(self.schema.len(), tuple.len()));
+        }
+        for (attribute, value) in self.schema.attributes().zip(tuple.values()) {
+            let conforms = if value.is_null() {
+                attribute.is_nullable()
+            } else {
+                value.data_type() == *attribute.domain()
+            };
+            if !conforms {
+                return Err(invalid_value(*attribute.domain(), value.clone()));
+            }
+        }
+        Ok(self.insert_unchecked(tuple))
+    }
+
+    ///
+    /// Add a tuple to this relation without checking the schema; this returns `true` if the
+    /// tuple was added, or `false` if the relation already contained it.
+    ///
+    pub fn insert_unchecked(&mut self, tuple: SimpleTuple) -> bool {
+        if self.members.insert(tuple.clone()) {
+            self.tuples.push(tuple);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_schema_name(&mut self, name: crate::Name) {
+        self.schema.set_name(name);
+    }
+
+    ///
+    /// Compute a snapshot of the statistics for this relation.
+    ///
+    pub fn statistics(&self) -> SimpleStatistics {
+        SimpleStatistics::new(
+            self.len(),
+            (0..self.schema.len())
+                .map(|i| {
+                    SimpleAttributeStatistics::new(
+                        self.distinct_count(i),
+                        self.min_value(i),
+                        self.max_value(i),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// The non-null values of `attribute`, as used for its minimum and maximum.
+    fn attribute_values(&self, attribute: usize) -> impl Iterator<Item = &Value> {
+        self.tuples
+            .iter()
+            .filter_map(move |t| t.0.get(attribute))
+            .filter(|v| !v.is_null())
+    }
+
+    pub fn into_parts(self) -> (SimpleRelationSchema, Vec<SimpleTuple>) {
+        (self.schema, self.tuples)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 impl<'a> Iterator for Tuples<'a> {
@@ -82,6 +254,71 @@ impl<'a> Iterator for Tuples<'a> {
 
 // ------------------------------------------------------------------------------------------------
 
+impl Statistics for SimpleStatistics {
+    fn cardinality(&self) -> usize {
+        self.cardinality
+    }
+
+    fn distinct_count(&self, attribute: usize) -> Option<usize> {
+        self.attributes
+            .get(attribute)
+            .and_then(|a| a.distinct_count)
+    }
+
+    fn min_value(&self, attribute: usize) -> Option<Value> {
+        self.attributes
+            .get(attribute)
+            .and_then(|a| a.min_value.clone())
+    }
+
+    fn max_value(&self, attribute: usize) -> Option<Value> {
+        self.attributes
+            .get(attribute)
+            .and_then(|a| a.max_value.clone())
+    }
+}
+
+impl SimpleStatistics {
+    pub fn new(cardinality: usize, attributes: Vec<SimpleAttributeStatistics>) -> Self {
+        Self {
+            cardinality,
+            attributes,
+        }
+    }
+
+    pub fn attributes(&self) -> impl Iterator<Item = &SimpleAttributeStatistics> {
+        self.attributes.iter()
+    }
+}
+
+impl SimpleAttributeStatistics {
+    pub fn new(
+        distinct_count: Option<usize>,
+        min_value: Option<Value>,
+        max_value: Option<Value>,
+    ) -> Self {
+        Self {
+            distinct_count,
+            min_value,
+            max_value,
+        }
+    }
+
+    pub fn distinct_count(&self) -> Option<usize> {
+        self.distinct_count
+    }
+
+    pub fn min_value(&self) -> Option<&Value> {
+        self.min_value.as_ref()
+    }
+
+    pub fn max_value(&self) -> Option<&Value> {
+        self.max_value.as_ref()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl Display for SimpleTuple {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -95,6 +332,24 @@ impl Display for SimpleTuple {
     }
 }
 
+impl From<Vec<Value>> for SimpleTuple {
+    fn from(v: Vec<Value>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<SimpleTuple> for Vec<Value> {
+    fn from(v: SimpleTuple) -> Self {
+        v.0
+    }
+}
+
+impl FromIterator<Value> for SimpleTuple {
+    fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl Tuple for SimpleTuple {
     fn len(&self) -> usize {
         self.0.len()
@@ -0,0 +1,206 @@
+/*!
+Provides a small schema definition language, parsed into a [`SimpleSchema`],
+[`SimpleRelationSchema`], or [`SimpleAttributeSchema`]; each of these is written in the same
+form by its `Display` implementation, so a parsed schema may be written back out as text.
+
+A schema is the keyword `schema`, its name, and a list of relation schemas, each terminated by
+`;`, between braces; the terminator on the last relation is optional. A relation schema is a
+name followed by a parenthesized, comma-separated list of attributes, each written as a name,
+`:`, and the name of its domain, followed by `?` if the attribute is nullable.
+
+The domain names are those written by [`Domain`]'s `Display` implementation: `null`, `boolean`,
+`byte`, `unsigned`, `integer`, `float`, `char`, `string`, `binary`, `decimal`, `date`, `time`,
+`timestamp`, `timestamptz`, and `interval`.
+
+The names of relations within a schema, and of attributes within a relation, must be unique;
+a parse error for a repeated name points at the repetition. A relation may have no attributes,
+as in `unit()`, as the relations with no attributes are well-defined in the algebra, and a
+schema may have no relations.
+
+# Example
+
+```rust
+use relational_algebra::simple::sort::SimpleSchema;
+use relational_algebra::sort::{RelationSchema, Schema};
+use relational_algebra::Name;
+use std::str::FromStr;
+
+let schema = SimpleSchema::from_str(
+    "schema uni {
+        student(id: integer, name: string, born: date?);
+        course(code: string, credits: unsigned);
+    }",
+)
+.unwrap();
+assert_eq!(schema.len(), 2);
+assert_eq!(
+    schema.relation(&Name::new_unchecked("course")).unwrap().len(),
+    2
+);
+assert_eq!(
+    schema.to_string(),
+    "schema uni { student(id: integer, name: string, born: date?); course(code: string, credits: unsigned) }"
+);
+```
+
+ */
+
+use crate::error::{Error, ParseKind, Result};
+use crate::parse::{identifier, keyword, parse_all, symbol};
+use crate::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use crate::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use crate::Name;
+use combine::error::StreamError;
+use combine::parser::char::spaces;
+use combine::stream::StreamErrorFor;
+use combine::{optional, sep_by, sep_end_by, Parser, Stream};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a schema, as in `schema uni { course(code: string, credits: unsigned) }`.
+///
+pub fn parse_schema(s: &str) -> Result<SimpleSchema> {
+    parse_all(s, ParseKind::Schema, spaces().with(schema()))
+}
+
+///
+/// Parse a single relation schema, as in `course(code: string, credits: unsigned)`.
+///
+pub fn parse_relation_schema(s: &str) -> Result<SimpleRelationSchema> {
+    parse_all(s, ParseKind::Schema, spaces().with(relation_schema()))
+}
+
+///
+/// Parse a single attribute schema, as in `born: date?`.
+///
+pub fn parse_attribute_schema(s: &str) -> Result<SimpleAttributeSchema> {
+    parse_all(s, ParseKind::Schema, spaces().with(attribute_schema()))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FromStr for SimpleSchema {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_schema(s)
+    }
+}
+
+impl FromStr for SimpleRelationSchema {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_relation_schema(s)
+    }
+}
+
+impl FromStr for SimpleAttributeSchema {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_attribute_schema(s)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn domain<Input>() -> impl Parser<Input, Output = Domain>
+where
+    Input: Stream<Token = char>,
+{
+    identifier()
+        .and_then(|name: Name| {
+            Domain::from_str(name.as_ref()).map_err(|_| {
+                StreamErrorFor::<Input>::message_format(format!("unknown domain `{}`", name))
+            })
+        })
+        .expected("domain")
+}
+
+fn attribute_schema<Input>() -> impl Parser<Input, Output = SimpleAttributeSchema>
+where
+    Input: Stream<Token = char>,
+{
+    (identifier(), symbol(':'), domain(), optional(symbol('?'))).map(
+        |(name, _, domain, nullable)| {
+            SimpleAttributeSchema::new(name, domain).with_nullable(nullable.is_some())
+        },
+    )
+}
+
+fn relation_schema<Input>() -> impl Parser<Input, Output = SimpleRelationSchema>
+where
+    Input: Stream<Token = char>,
+{
+    identifier()
+        .skip(symbol('('))
+        .then(|name: Name| {
+            // each attribute is checked as it is parsed, so that an error points at the
+            // duplicate.
+            let relation = name.clone();
+            let mut names: Vec<Name> = Default::default();
+            sep_by::<Vec<SimpleAttributeSchema>, _, _, _>(
+                attribute_schema().and_then(move |attribute: SimpleAttributeSchema| {
+                    if names.contains(attribute.name()) {
+                        return Err(StreamErrorFor::<Input>::message_format(format!(
+                            "duplicate attribute `{}` in relation `{}`",
+                            attribute.name(),
+                            relation
+                        )));
+                    }
+                    names.push(attribute.name().clone());
+                    Ok(attribute)
+                }),
+                symbol(','),
+            )
+            .skip(symbol(')'))
+            .map(move |attributes| (name.clone(), attributes))
+        })
+        .and_then(|(name, attributes)| {
+            SimpleRelationSchema::new(name, attributes)
+                .map_err(StreamErrorFor::<Input>::message_format)
+        })
+}
+
+fn schema<Input>() -> impl Parser<Input, Output = SimpleSchema>
+where
+    Input: Stream<Token = char>,
+{
+    keyword("schema")
+        .with(identifier())
+        .skip(symbol('{'))
+        .then(|name: Name| {
+            // each relation is checked as it is parsed, so that an error points at the
+            // duplicate.
+            let schema = name.clone();
+            let mut names: Vec<Name> = Default::default();
+            sep_end_by::<Vec<SimpleRelationSchema>, _, _, _>(
+                relation_schema().and_then(move |relation: SimpleRelationSchema| {
+                    if names.contains(relation.name()) {
+                        return Err(StreamErrorFor::<Input>::message_format(format!(
+                            "duplicate relation `{}` in schema `{}`",
+                            relation.name(),
+                            schema
+                        )));
+                    }
+                    names.push(relation.name().clone());
+                    Ok(relation)
+                }),
+                symbol(';'),
+            )
+            .skip(symbol('}'))
+            .map(move |relations| (name.clone(), relations))
+        })
+        .and_then(|(name, relations)| {
+            SimpleSchema::new(name, relations).map_err(StreamErrorFor::<Input>::message_format)
+        })
+}
@@ -1,13 +1,62 @@
 /*!
-One-line description.
+Provides reading of CSV files into a [`SimpleRelation`], and the writing of any [`Relation`] as
+CSV.
 
-More detailed description, with
+A CSV file is read against a [`SimpleRelationSchema`], each field being parsed, by
+[`Value::parse`], as a value of its attribute's domain; a field that cannot be parsed is reported
+as [`Error::InvalidValue`] with the row, and column, at which it was found. Values are written in
+the same form, so that a relation written as CSV may be read back using the same schema.
+
+A null value is written as an unquoted empty field, and an empty string, or binary value, as a
+quoted one, `""`; when read, only an unquoted empty field is null. The delimiter, quote character,
+quoting style, handling of a header row, and treatment of empty fields are all set by
+[`CsvOptions`]; the default is a comma-delimited file whose first row names the columns.
 
 # Example
 
+```rust
+use relational_algebra::data::{Relation, Value};
+use relational_algebra::simple::io::csv::{read_relation, write_relation, CsvOptions};
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema};
+use relational_algebra::Name;
+
+let schema = SimpleRelationSchema::new(
+    Name::new_unchecked("people"),
+    [
+        SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+        SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+    ],
+)
+.unwrap();
+
+// columns are matched to attributes by the names in the header row.
+let text = "age,name\n42,alice\n7,\"bob, jr.\"\n";
+let relation = read_relation(text.as_bytes(), schema, &CsvOptions::default()).unwrap();
+assert_eq!(relation.len(), 2);
+
+let mut output: Vec<u8> = Vec::new();
+write_relation(&mut output, &relation, &CsvOptions::default()).unwrap();
+assert_eq!(
+    String::from_utf8(output).unwrap(),
+    "name,age\nalice,42\n\"bob, jr.\",7\n"
+);
+```
+
  */
 
-// use ...
+use crate::data::{Relation, Tuple, Value};
+use crate::error::{
+    attribute_does_not_exist, incompatible_arity_at, invalid_value_at, Error, Result,
+};
+use crate::simple::data::{SimpleRelation, SimpleTuple};
+use crate::simple::io::infer::{column_names, Inference, InferredSchema};
+use crate::simple::sort::SimpleRelationSchema;
+use crate::sort::{AttributeSchema, RelationSchema};
+use crate::Name;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -17,10 +66,266 @@ More detailed description, with
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+///
+/// The options used when reading, or writing, CSV; see [`CsvOptions::default`] for the default
+/// values.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    delimiter: u8,
+    quote: u8,
+    quoting: Quoting,
+    header: Header,
+    empty_fields: EmptyFields,
+}
+
+///
+/// When fields are quoted as they are written; any quoted field is accepted when reading.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quoting {
+    /// Only fields containing a delimiter, quote, or line break are quoted.
+    #[default]
+    Necessary,
+    /// Every field is quoted.
+    Always,
+    /// Every field that is not a number is quoted.
+    NonNumeric,
+    /// No field is quoted, even where this results in a file that cannot be read back, or an
+    /// empty string that is read back as null.
+    Never,
+}
+
+///
+/// How the first row of a file is treated.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Header {
+    /// The first row names the columns, and each attribute is read from the column of the same
+    /// name, or that is named for it as by [`infer_schema`], whatever its position; any other
+    /// column is ignored. A header row is written.
+    #[default]
+    Named,
+    /// The first row is a header, which is skipped, and columns are read in the order of the
+    /// schema's attributes. A header row is written.
+    Ignored,
+    /// There is no header row, and columns are read in the order of the schema's attributes. No
+    /// header row is written.
+    Absent,
+}
+
+///
+/// How an empty field is read; a null value is always written as an unquoted empty field.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EmptyFields {
+    /// An unquoted empty field is null if its attribute is nullable, and is otherwise parsed as
+    /// any other field would be, as an empty string for a string attribute; a quoted empty field,
+    /// `""`, is never null.
+    #[default]
+    Null,
+    /// An empty field is always parsed as any other field would be; it is an empty string for a
+    /// string attribute, and is invalid for most other domains.
+    Parse,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Read the CSV from `reader` into a new relation with the given `schema`; an empty input is an
+/// empty relation, even where a header row is expected.
+///
+pub fn read_relation<R>(
+    reader: R,
+    schema: SimpleRelationSchema,
+    options: &CsvOptions,
+) -> Result<SimpleRelation>
+where
+    R: Read,
+{
+    let input = read_input(reader)?;
+    let mut reader = csv_reader(input.as_slice(), options);
+
+    let columns: Vec<usize> = if options.header == Header::Named {
+        let headers = reader.headers().map_err(csv_error)?;
+        if headers.is_empty() {
+            // an empty file has no header row, and so no records.
+            return Ok(SimpleRelation::new(schema));
+        }
+        let names = column_names(headers.iter());
+        schema
+            .attributes()
+            .map(|attribute| {
+                headers
+                    .iter()
+                    .position(|header| header == attribute.name().as_ref())
+                    .or_else(|| names.iter().position(|name| name == attribute.name()))
+                    .ok_or_else(|| attribute_does_not_exist(attribute.name().clone()))
+            })
+            .collect::<Result<Vec<usize>>>()?
+    } else {
+        (0..schema.len()).collect()
+    };
+    let width = if options.header == Header::Named {
+        reader.headers().map_err(csv_error)?.len()
+    } else {
+        schema.len()
+    };
+
+    let mut relation = SimpleRelation::new(schema.clone());
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(csv_error)?;
+        let row = record
+            .position()
+            .map(|position| position.line() as usize)
+            .unwrap_or(index + 1);
+        if record.len() != width {
+            return Err(incompatible_arity_at(width, record.len(), row));
+        }
+        let mut quoted: Option<Vec<bool>> = None;
+        let tuple = schema
+            .attributes()
+            .zip(columns.iter())
+            .map(|(attribute, column)| {
+                let field = &record[*column];
+                if field.is_empty()
+                    && attribute.is_nullable()
+                    && !is_quoted(&mut quoted, &input, &record, *column, options)
+                {
+                    Ok(Value::Null)
+                } else {
+                    Value::parse(*attribute.domain(), field)
+                        .map_err(|_| invalid_value_at(*attribute.domain(), field, row, column + 1))
+                }
+            })
+            .collect::<Result<Vec<Value>>>()?;
+        let _ = relation.insert(SimpleTuple::from(tuple))?;
+    }
+    Ok(relation)
+}
+
+///
+/// Read the CSV file at `path` into a new relation with the given `schema`.
+///
+pub fn read_relation_from_path<P>(
+    path: P,
+    schema: SimpleRelationSchema,
+    options: &CsvOptions,
+) -> Result<SimpleRelation>
+where
+    P: AsRef<Path>,
+{
+    read_relation(File::open(path)?, schema, options)
+}
+
+///
+/// Infer the schema of a relation, named `name`, from at most `sample_size` records of the CSV
+/// read from `reader`; see [`infer`](super::infer) for how each column's domain is chosen. A
+/// column without a header is named for its position, as in `column_1`.
+///
+pub fn infer_schema<R>(
+    reader: R,
+    name: Name,
+    options: &CsvOptions,
+    sample_size: usize,
+) -> Result<InferredSchema>
+where
+    R: Read,
+{
+    let input = read_input(reader)?;
+    let mut reader = csv_reader(input.as_slice(), options);
+    let mut inference = Inference::default();
+    if options.header != Header::Absent {
+        for label in reader.headers().map_err(csv_error)?.iter() {
+            inference.add_column(label);
+        }
+    }
+
+    let mut records = reader.records();
+    while inference.records() < sample_size {
+        let record = match records.next() {
+            Some(record) => record.map_err(csv_error)?,
+            None => return inference.finish(name, true),
+        };
+        if options.header == Header::Absent && inference.records() == 0 {
+            for _ in 0..record.len() {
+                inference.add_column(String::new());
+            }
+        }
+        if record.len() != inference.columns() {
+            let row = record
+                .position()
+                .map(|position| position.line() as usize)
+                .unwrap_or(inference.records() + 1);
+            return Err(incompatible_arity_at(
+                inference.columns(),
+                record.len(),
+                row,
+            ));
+        }
+        inference.add_record();
+        let mut quoted: Option<Vec<bool>> = None;
+        for (column, field) in record.iter().enumerate() {
+            inference.observe(
+                column,
+                if field.is_empty() && !is_quoted(&mut quoted, &input, &record, column, options) {
+                    None
+                } else {
+                    Some(field)
+                },
+            );
+        }
+    }
+    let complete = records.next().is_none();
+    inference.finish(name, complete)
+}
+
+///
+/// Write the tuples of `relation` to `writer` as CSV, preceded by a header row of attribute
+/// names unless the header is [`Header::Absent`].
+///
+pub fn write_relation<W, R>(writer: W, relation: &R, options: &CsvOptions) -> Result<()>
+where
+    W: Write,
+    R: Relation,
+{
+    // fields are quoted here, rather than by the writer, as only an empty string, and never a
+    // null, is quoted when empty.
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .quote_style(csv::QuoteStyle::Never)
+        .from_writer(writer);
+    if options.header != Header::Absent {
+        writer
+            .write_record(
+                relation.schema().attributes().map(|attribute| {
+                    quote_field(&Value::from(attribute.name().to_string()), options)
+                }),
+            )
+            .map_err(csv_error)?;
+    }
+    for tuple in relation.tuples() {
+        writer
+            .write_record(tuple.values().map(|value| quote_field(value, options)))
+            .map_err(csv_error)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+///
+/// Write the tuples of `relation` as CSV to a file at `path`, replacing any existing file.
+///
+pub fn write_relation_to_path<P, R>(path: P, relation: &R, options: &CsvOptions) -> Result<()>
+where
+    P: AsRef<Path>,
+    R: Relation,
+{
+    write_relation(File::create(path)?, relation, options)
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
@@ -29,10 +334,188 @@ More detailed description, with
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
+impl Default for CsvOptions {
+    ///
+    /// Comma-delimited, with `"` for quotes only where necessary, a header row naming the
+    /// columns, and empty fields null where allowed.
+    ///
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            quoting: Default::default(),
+            header: Default::default(),
+            empty_fields: Default::default(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn with_delimiter(self, delimiter: u8) -> Self {
+        Self { delimiter, ..self }
+    }
+
+    pub fn with_quote(self, quote: u8) -> Self {
+        Self { quote, ..self }
+    }
+
+    pub fn with_quoting(self, quoting: Quoting) -> Self {
+        Self { quoting, ..self }
+    }
+
+    pub fn with_header(self, header: Header) -> Self {
+        Self { header, ..self }
+    }
+
+    pub fn with_empty_fields(self, empty_fields: EmptyFields) -> Self {
+        Self {
+            empty_fields,
+            ..self
+        }
+    }
+
+    pub fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    pub fn quote(&self) -> u8 {
+        self.quote
+    }
+
+    pub fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
+    pub fn header(&self) -> Header {
+        self.header
+    }
+
+    pub fn empty_fields(&self) -> EmptyFields {
+        self.empty_fields
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+fn csv_reader<R>(reader: R, options: &CsvOptions) -> csv::Reader<R>
+where
+    R: Read,
+{
+    csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .has_headers(options.header != Header::Absent)
+        .flexible(true)
+        .from_reader(reader)
+}
+
+fn read_input<R>(mut reader: R) -> Result<Vec<u8>>
+where
+    R: Read,
+{
+    let mut input: Vec<u8> = Default::default();
+    let _ = reader.read_to_end(&mut input)?;
+    Ok(input)
+}
+
+///
+/// Returns `true` if the field at `column` of `record` was quoted in `input`; the reader does not
+/// distinguish a quoted empty field, `""`, from an unquoted one, and so the record is scanned
+/// again, once, the first time this is asked.
+///
+fn is_quoted(
+    quoted: &mut Option<Vec<bool>>,
+    input: &[u8],
+    record: &csv::StringRecord,
+    column: usize,
+    options: &CsvOptions,
+) -> bool {
+    if options.empty_fields == EmptyFields::Parse {
+        return true;
+    }
+    let start = record
+        .position()
+        .map(|position| position.byte() as usize)
+        .unwrap_or_default();
+    quoted
+        .get_or_insert_with(|| quoted_fields(input, start, options))
+        .get(column)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn quoted_fields(input: &[u8], start: usize, options: &CsvOptions) -> Vec<bool> {
+    let mut quoted: Vec<bool> = Default::default();
+    let mut i = if start == 0 && input.starts_with(b"\xef\xbb\xbf") {
+        3
+    } else {
+        start
+    };
+    loop {
+        let is_quoted = input.get(i) == Some(&options.quote);
+        if is_quoted {
+            i += 1;
+            while let Some(b) = input.get(i) {
+                i += 1;
+                if *b == options.quote {
+                    if input.get(i) == Some(&options.quote) {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+        while let Some(b) = input.get(i) {
+            if *b == options.delimiter || *b == b'\n' || *b == b'\r' {
+                break;
+            }
+            i += 1;
+        }
+        quoted.push(is_quoted);
+        if input.get(i) == Some(&options.delimiter) {
+            i += 1;
+        } else {
+            return quoted;
+        }
+    }
+}
+
+///
+/// Returns `value` as a field, quoted if required by the quoting style; a null value is always
+/// an unquoted empty field, and so an empty string is quoted unless quoting is [`Quoting::Never`].
+///
+fn quote_field(value: &Value, options: &CsvOptions) -> String {
+    let field = match value {
+        Value::Null => return String::new(),
+        _ => value.to_plain_string(),
+    };
+    let quote = options.quote as char;
+    let required = match options.quoting {
+        Quoting::Always => true,
+        Quoting::Never => false,
+        Quoting::NonNumeric => !value.data_type().is_numeric(),
+        Quoting::Necessary => {
+            field.is_empty()
+                || field.contains(|c| {
+                    c == quote || c == options.delimiter as char || c == '\n' || c == '\r'
+                })
+        }
+    };
+    if required {
+        let escaped = field.replace(quote, &format!("{}{}", quote, quote));
+        format!("{}{}{}", quote, escaped, quote)
+    } else {
+        field
+    }
+}
+
+fn csv_error(e: csv::Error) -> Error {
+    Error::from(std::io::Error::from(e))
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,463 @@
+/*!
+Provides the reading of a directory of relation files into a [`Catalog`], a [`SimpleSchema`]
+together with the relations it describes, and the writing of a catalog back to a directory.
+
+Each file with the extension `csv`, `json`, or `jsonl` (JSON Lines) is read as a relation named
+for the file's stem, made a valid name by [`sanitize_name`] where necessary; other files, and
+sub-directories, are ignored. The schema of each relation is taken from the directory's manifest,
+if it has one, and is otherwise inferred from the file, see [`infer`](super::infer).
+
+# Manifest
+
+The manifest, by default the file `_schema.json`, is a JSON object with the name of the schema,
+and an object for each relation mapping attribute names, in order, to domains; a nullable
+attribute's domain is followed by `?`. A relation in the manifest without a file is empty.
+
+```json
+{
+  "name": "uni",
+  "relations": {
+    "student": { "id": "integer", "name": "string", "born": "date?" }
+  }
+}
+```
+
+Without a manifest the schema is named for the directory. A manifest is always written, with
+the relations in order of name, so that a catalog may be read back with the same schema whatever
+the format of its files.
+
+# Example
+
+```rust,no_run
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::eval::evaluate_relational;
+use relational_algebra::simple::io::directory::{read_directory, DirectoryOptions};
+use std::str::FromStr;
+
+let catalog = read_directory("fixtures/uni", &DirectoryOptions::default()).unwrap();
+let query = RelationalOp::from_str("π[name](student)").unwrap();
+let result = evaluate_relational(&query, catalog.schema(), &catalog).unwrap();
+```
+
+ */
+
+use crate::data::{Database, Relation};
+use crate::error::{invalid_name_value, Result};
+use crate::simple::data::SimpleRelation;
+use crate::simple::io::csv::{self, CsvOptions};
+use crate::simple::io::infer::sanitize_name;
+use crate::simple::io::json::{self, invalid_data, json_error, JsonFormat, JsonOptions};
+use crate::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use crate::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use crate::Name;
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A schema together with a relation for each of its relation schemas; a catalog is a
+/// [`Database`] and so may be queried by the evaluator.
+///
+#[derive(Clone, Debug)]
+pub struct Catalog {
+    schema: SimpleSchema,
+    relations: HashMap<Name, SimpleRelation>,
+}
+
+///
+/// The options used when reading, or writing, a directory.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectoryOptions {
+    manifest: String,
+    format: FileFormat,
+    csv: CsvOptions,
+    sample_size: usize,
+}
+
+///
+/// The format in which relations are written to a directory.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FileFormat {
+    /// CSV, in files with the extension `csv`.
+    #[default]
+    Csv,
+    /// A JSON array of objects, in files with the extension `json`.
+    Json,
+    /// JSON Lines, in files with the extension `jsonl`.
+    JsonLines,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Read each relation file in the directory at `path`, and its manifest if present, into a new
+/// catalog.
+///
+pub fn read_directory<P>(path: P, options: &DirectoryOptions) -> Result<Catalog>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let manifest_path = path.join(&options.manifest);
+    let manifest = if manifest_path.is_file() {
+        Some(read_manifest(&manifest_path)?)
+    } else {
+        None
+    };
+
+    let mut files: Vec<(Name, PathBuf, FileFormat)> = Default::default();
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    entries.sort();
+    for file_path in entries {
+        if !file_path.is_file() || file_path == manifest_path {
+            continue;
+        }
+        let format = match file_path.extension().and_then(|s| s.to_str()) {
+            Some("csv") => FileFormat::Csv,
+            Some("json") => FileFormat::Json,
+            Some("jsonl") => FileFormat::JsonLines,
+            _ => continue,
+        };
+        let stem = file_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = sanitize_name(&stem).ok_or_else(|| invalid_name_value(stem))?;
+        if files.iter().any(|(other, _, _)| other == &name) {
+            return Err(invalid_data(format!(
+                "more than one file in the directory is named for the relation `{}`",
+                name
+            )));
+        }
+        files.push((name, file_path, format));
+    }
+
+    let (schema_name, mut schemas) = match manifest {
+        Some((name, schemas)) => (name, schemas),
+        None => (
+            path.file_name()
+                .and_then(|s| sanitize_name(&s.to_string_lossy()))
+                .unwrap_or_else(|| Name::new_unchecked("database")),
+            Default::default(),
+        ),
+    };
+
+    let mut relations: HashMap<Name, SimpleRelation> = Default::default();
+    for (name, file_path, format) in files {
+        let content = fs::read(&file_path)?;
+        let schema = match schemas.iter().position(|schema| schema.name() == &name) {
+            Some(index) => schemas.remove(index),
+            None => infer_schema(&content, name.clone(), format, options)?,
+        };
+        let relation = match format {
+            FileFormat::Csv => csv::read_relation(content.as_slice(), schema, &options.csv)?,
+            _ => json::read_relation(content.as_slice(), schema, &json_options(format))?,
+        };
+        let _ = relations.insert(name, relation);
+    }
+    for schema in schemas {
+        let _ = relations.insert(schema.name().clone(), SimpleRelation::new(schema));
+    }
+
+    Catalog::from_relations(schema_name, relations.into_values())
+}
+
+///
+/// Write each relation in `catalog` to a file, named for the relation, in the directory at
+/// `path`, together with a manifest; the directory is created if it does not exist, and existing
+/// files of the same names are replaced.
+///
+pub fn write_directory<P>(path: P, catalog: &Catalog, options: &DirectoryOptions) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    fs::create_dir_all(path)?;
+
+    let mut relations: Vec<&SimpleRelation> = catalog.relations.values().collect();
+    relations.sort_by(|lhs, rhs| lhs.schema().name().cmp(rhs.schema().name()));
+    for relation in &relations {
+        let file_path = path.join(format!(
+            "{}.{}",
+            relation.schema().name(),
+            options.format.extension()
+        ));
+        match options.format {
+            FileFormat::Csv => csv::write_relation_to_path(file_path, *relation, &options.csv)?,
+            format => json::write_relation_to_path(file_path, *relation, &json_options(format))?,
+        }
+    }
+
+    let manifest = JsonValue::Object(Map::from_iter([
+        (
+            "name".to_string(),
+            JsonValue::String(catalog.schema.name().to_string()),
+        ),
+        (
+            "relations".to_string(),
+            JsonValue::Object(
+                relations
+                    .iter()
+                    .map(|relation| {
+                        (
+                            relation.schema().name().to_string(),
+                            JsonValue::Object(
+                                relation
+                                    .schema()
+                                    .attributes()
+                                    .map(|attribute| {
+                                        (
+                                            attribute.name().to_string(),
+                                            JsonValue::String(format!(
+                                                "{}{}",
+                                                attribute.domain(),
+                                                if attribute.is_nullable() { "?" } else { "" }
+                                            )),
+                                        )
+                                    })
+                                    .collect(),
+                            ),
+                        )
+                    })
+                    .collect(),
+            ),
+        ),
+    ]));
+    let mut writer = BufWriter::new(File::create(path.join(&options.manifest))?);
+    serde_json::to_writer_pretty(&mut writer, &manifest).map_err(json_error)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Database for Catalog {
+    type Item = SimpleRelation;
+
+    fn relation(&self, name: &Name) -> Option<&Self::Item> {
+        self.relations.get(name)
+    }
+}
+
+impl Catalog {
+    ///
+    /// Create a catalog, with a schema named `name`, from the given relations; a later relation
+    /// replaces an earlier one of the same name, and the schema lists relations in order of name.
+    ///
+    pub fn from_relations<I>(name: Name, relations: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = SimpleRelation>,
+    {
+        let relations: HashMap<Name, SimpleRelation> = relations
+            .into_iter()
+            .map(|relation| (relation.schema().name().clone(), relation))
+            .collect();
+        let mut schemas: Vec<SimpleRelationSchema> = relations
+            .values()
+            .map(|relation| relation.schema().clone())
+            .collect();
+        schemas.sort_by(|lhs, rhs| lhs.name().cmp(rhs.name()));
+        Ok(Self {
+            schema: SimpleSchema::new(name, schemas)?,
+            relations,
+        })
+    }
+
+    pub fn schema(&self) -> &SimpleSchema {
+        &self.schema
+    }
+
+    pub fn len(&self) -> usize {
+        self.relations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.relations.is_empty()
+    }
+
+    pub fn relations(&self) -> impl Iterator<Item = &SimpleRelation> {
+        self.relations.values()
+    }
+
+    pub fn into_parts(self) -> (SimpleSchema, HashMap<Name, SimpleRelation>) {
+        (self.schema, self.relations)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for DirectoryOptions {
+    ///
+    /// A manifest named `_schema.json`, relations written as CSV with the default
+    /// [`CsvOptions`], and every record of a file sampled when inferring its schema.
+    ///
+    fn default() -> Self {
+        Self {
+            manifest: String::from("_schema.json"),
+            format: Default::default(),
+            csv: Default::default(),
+            sample_size: usize::MAX,
+        }
+    }
+}
+
+impl DirectoryOptions {
+    pub fn with_manifest<S>(self, manifest: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            manifest: manifest.into(),
+            ..self
+        }
+    }
+
+    pub fn with_format(self, format: FileFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    ///
+    /// The options used to read, and write, CSV files; the JSON format used is determined by the
+    /// extension of each file.
+    ///
+    pub fn with_csv_options(self, csv: CsvOptions) -> Self {
+        Self { csv, ..self }
+    }
+
+    ///
+    /// The number of records sampled when inferring the schema of a file not in the manifest.
+    ///
+    pub fn with_sample_size(self, sample_size: usize) -> Self {
+        Self {
+            sample_size,
+            ..self
+        }
+    }
+
+    pub fn manifest(&self) -> &str {
+        &self.manifest
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    pub fn csv_options(&self) -> &CsvOptions {
+        &self.csv
+    }
+
+    pub fn sample_size(&self) -> usize {
+        self.sample_size
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::JsonLines => "jsonl",
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn read_manifest(path: &Path) -> Result<(Name, Vec<SimpleRelationSchema>)> {
+    let manifest: JsonValue =
+        serde_json::from_reader(BufReader::new(File::open(path)?)).map_err(json_error)?;
+    let name = match manifest.get("name") {
+        Some(JsonValue::String(name)) => Name::from_str(name)?,
+        _ => return Err(invalid_data("the manifest has no schema `name`")),
+    };
+    let relations = match manifest.get("relations") {
+        Some(JsonValue::Object(relations)) => relations,
+        _ => return Err(invalid_data("the manifest has no `relations` object")),
+    };
+    let schemas = relations
+        .iter()
+        .map(|(relation, attributes)| {
+            let attributes = match attributes {
+                JsonValue::Object(attributes) => attributes,
+                _ => {
+                    return Err(invalid_data(format!(
+                        "the manifest entry for `{}` is not an object",
+                        relation
+                    )))
+                }
+            };
+            SimpleRelationSchema::new(
+                Name::from_str(relation)?,
+                attributes
+                    .iter()
+                    .map(|(attribute, domain)| {
+                        let domain = domain.as_str().unwrap_or_default();
+                        let (domain, nullable) = match domain.strip_suffix('?') {
+                            Some(domain) => (domain, true),
+                            None => (domain, false),
+                        };
+                        Ok(SimpleAttributeSchema::new(
+                            Name::from_str(attribute)?,
+                            Domain::from_str(domain)?,
+                        )
+                        .with_nullable(nullable))
+                    })
+                    .collect::<Result<Vec<SimpleAttributeSchema>>>()?,
+            )
+        })
+        .collect::<Result<Vec<SimpleRelationSchema>>>()?;
+    Ok((name, schemas))
+}
+
+fn infer_schema(
+    content: &[u8],
+    name: Name,
+    format: FileFormat,
+    options: &DirectoryOptions,
+) -> Result<SimpleRelationSchema> {
+    Ok(match format {
+        FileFormat::Csv => csv::infer_schema(content, name, &options.csv, options.sample_size)?,
+        _ => json::infer_schema(content, name, &json_options(format), options.sample_size)?,
+    }
+    .into_schema())
+}
+
+fn json_options(format: FileFormat) -> JsonOptions {
+    JsonOptions::default().with_format(if format == FileFormat::JsonLines {
+        JsonFormat::Lines
+    } else {
+        JsonFormat::Array
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
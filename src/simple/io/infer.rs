@@ -0,0 +1,378 @@
+/*!
+Provides the inference of a [`SimpleRelationSchema`] from a sample of the records in a file, see
+[`csv::infer_schema`](super::csv::infer_schema) and [`json::infer_schema`](super::json::infer_schema).
+
+Each column is given the narrowest domain that can represent every value sampled for it, chosen
+from, in order, `boolean`, `integer`, `unsigned`, `float`, `date`, `time`, `timestamp`,
+`timestamptz`, and `string`; so a column of whole numbers is an `integer` unless a value is too
+large, and a column with any value that is not written as a number, including `NaN` or `inf`, is
+a `string`. A column with any null, or empty, value is nullable, and a column with no values at
+all is a nullable `string`. Column names are taken from the file and, where they are not valid
+names, are sanitized by [`sanitize_name`].
+
+As only a sample is read a later record may not fit the inferred schema; the result, an
+[`InferredSchema`], reports whether the whole file was read, and, for each column, a confidence
+that is the proportion of sampled records that provided a value for it.
+
+# Example
+
+```rust
+use relational_algebra::simple::io::csv::{infer_schema, CsvOptions};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema};
+use relational_algebra::Name;
+
+let text = "Item ID,Price,On Sale\n1,2.50,true\n2,,false\n";
+let inferred = infer_schema(
+    text.as_bytes(),
+    Name::new_unchecked("items"),
+    &CsvOptions::default(),
+    100,
+)
+.unwrap();
+assert!(inferred.is_complete());
+
+let schema = inferred.schema();
+assert_eq!(
+    schema.to_string(),
+    "items(Item_ID: integer, Price: float?, On_Sale: boolean)"
+);
+assert_eq!(inferred.columns()[1].confidence(), 0.5);
+```
+
+ */
+
+use crate::data::Value;
+use crate::error::Result;
+use crate::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema};
+use crate::sort::{AttributeSchema, Domain, RelationSchema};
+use crate::Name;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A schema inferred from a sample of the records in a file, with the evidence for each column.
+///
+#[derive(Clone, Debug)]
+pub struct InferredSchema {
+    schema: SimpleRelationSchema,
+    columns: Vec<InferredColumn>,
+    records: usize,
+    complete: bool,
+}
+
+///
+/// The evidence for the inferred attribute of a single column.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct InferredColumn {
+    label: String,
+    domain: Domain,
+    nullable: bool,
+    values: usize,
+    confidence: f64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns a valid [`Name`] for the label `s`; each run of characters that may not appear in a
+/// name is replaced by a single `_`, a name that would start with a digit is prefixed with `_`,
+/// and a long name is truncated. Returns `None` if `s` has no letters, digits, or underscores.
+///
+pub fn sanitize_name(s: &str) -> Option<Name> {
+    lazy_static! {
+        static ref INVALID_CHARACTERS: Regex = Regex::new(r"[^\p{L}\p{Nd}_]+").unwrap();
+    }
+
+    let mut name = INVALID_CHARACTERS
+        .split(s)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_");
+    if name.is_empty() {
+        return None;
+    }
+    if name.starts_with(|c: char| c.is_numeric()) {
+        name.insert(0, '_');
+    }
+    while name.len() >= MAX_NAME_LENGTH {
+        let _ = name.pop();
+    }
+    Name::from_str(&name).ok()
+}
+
+///
+/// Returns the attribute name for each of the column `labels` of a file, in order; each label is
+/// sanitized, a column with no usable label is named for its 1-based position, as `column_3`,
+/// and a name already taken by an earlier column is given a suffix, as `a_2`. Inference names
+/// columns this way, and the CSV reader resolves columns the same way, so that a schema inferred
+/// from a file can be used to read it.
+///
+pub(crate) fn column_names<'a, I>(labels: I) -> Vec<Name>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut names: Vec<Name> = Default::default();
+    for (index, label) in labels.into_iter().enumerate() {
+        let base = sanitize_name(label)
+            .unwrap_or_else(|| Name::new_unchecked(&format!("column_{}", index + 1)));
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while names.contains(&name) {
+            suffix += 1;
+            name = Name::new_unchecked(&format!("{}_{}", base, suffix));
+        }
+        names.push(name);
+    }
+    names
+}
+
+///
+/// Returns `true` if the `label` of a column in a file identifies the attribute named `name`,
+/// either exactly or once sanitized; so a schema inferred from a file can be used to read it.
+///
+pub(crate) fn label_matches(label: &str, name: &Name) -> bool {
+    label == name.as_ref() || sanitize_name(label).as_ref() == Some(name)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The accumulated observations of each column while a sample is read.
+///
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Inference {
+    columns: Vec<Observations>,
+    records: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Observations {
+    label: String,
+    candidates: Vec<Domain>,
+    nulls: usize,
+    values: usize,
+}
+
+///
+/// The domains that may be inferred, from narrowest to widest; `string` accepts any value.
+///
+const CANDIDATE_DOMAINS: [Domain; 9] = [
+    Domain::Boolean,
+    Domain::Integer,
+    Domain::UnsignedInteger,
+    Domain::Float,
+    Domain::Date,
+    Domain::Time,
+    Domain::Timestamp,
+    Domain::TimestampTz,
+    Domain::String,
+];
+
+const MAX_NAME_LENGTH: usize = 128;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl InferredSchema {
+    ///
+    /// The inferred schema, which may be used to read the file that was sampled.
+    ///
+    pub fn schema(&self) -> &SimpleRelationSchema {
+        &self.schema
+    }
+
+    pub fn into_schema(self) -> SimpleRelationSchema {
+        self.schema
+    }
+
+    ///
+    /// The evidence for each column, in the order of the schema's attributes.
+    ///
+    pub fn columns(&self) -> &[InferredColumn] {
+        &self.columns
+    }
+
+    ///
+    /// The number of records that were sampled.
+    ///
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    ///
+    /// Returns `true` if every record in the file was sampled, in which case the whole file is
+    /// known to be readable with the inferred schema.
+    ///
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    ///
+    /// The lowest confidence of any column, or `1.0` if there are no columns.
+    ///
+    pub fn confidence(&self) -> f64 {
+        self.columns
+            .iter()
+            .map(InferredColumn::confidence)
+            .fold(1.0, f64::min)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl InferredColumn {
+    ///
+    /// The label of this column in the file, from which its attribute's name was derived.
+    ///
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    ///
+    /// The number of sampled records that provided a value, that was not null, for this column.
+    ///
+    pub fn values(&self) -> usize {
+        self.values
+    }
+
+    ///
+    /// The proportion, from `0.0` to `1.0`, of sampled records that provided a value for this
+    /// column; a column with few values may have been given a narrower domain than later records
+    /// would allow.
+    ///
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Inference {
+    pub(crate) fn add_column<S>(&mut self, label: S)
+    where
+        S: Into<String>,
+    {
+        self.columns.push(Observations {
+            label: label.into(),
+            candidates: CANDIDATE_DOMAINS.to_vec(),
+            nulls: 0,
+            values: 0,
+        });
+    }
+
+    pub(crate) fn columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub(crate) fn records(&self) -> usize {
+        self.records
+    }
+
+    ///
+    /// Record the start of a new record; the values of the record are then observed in turn.
+    ///
+    pub(crate) fn add_record(&mut self) {
+        self.records += 1;
+    }
+
+    ///
+    /// Observe the value, in its plain string form, of the 0-based `column` in the current
+    /// record; `None` is a null value.
+    ///
+    pub(crate) fn observe(&mut self, column: usize, value: Option<&str>) {
+        let observations = &mut self.columns[column];
+        match value {
+            None => observations.nulls += 1,
+            Some(value) => {
+                observations.values += 1;
+                observations
+                    .candidates
+                    .retain(|domain| is_candidate(*domain, value));
+            }
+        }
+    }
+
+    pub(crate) fn finish(self, name: Name, complete: bool) -> Result<InferredSchema> {
+        let records = self.records;
+        let names = column_names(self.columns.iter().map(|column| column.label.as_str()));
+        let columns: Vec<InferredColumn> = self
+            .columns
+            .into_iter()
+            .map(|observations| InferredColumn {
+                label: observations.label,
+                domain: if observations.values == 0 {
+                    Domain::String
+                } else {
+                    observations.candidates[0]
+                },
+                nullable: observations.nulls > 0 || observations.values == 0,
+                values: observations.values,
+                confidence: if records == 0 {
+                    0.0
+                } else {
+                    observations.values as f64 / records as f64
+                },
+            })
+            .collect();
+        let schema = SimpleRelationSchema::new(
+            name,
+            names.iter().zip(columns.iter()).map(|(name, column)| {
+                SimpleAttributeSchema::new(name.clone(), column.domain)
+                    .with_nullable(column.nullable)
+            }),
+        )?;
+        Ok(InferredSchema {
+            schema,
+            columns,
+            records,
+            complete,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns `true` if `value` may be a value of `domain`; a float must be written as a number,
+/// so that words such as `nan` or `inf`, which parse as floats, are left as strings.
+///
+fn is_candidate(domain: Domain, value: &str) -> bool {
+    if domain == Domain::Float
+        && !value
+            .trim()
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+    {
+        return false;
+    }
+    Value::parse(domain, value).is_ok()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,459 @@
+/*!
+Provides reading of JSON files into a [`SimpleRelation`], and the writing of any [`Relation`] as
+JSON.
+
+A relation is represented as a sequence of objects, one for each tuple, whose keys are the names
+of the relation's attributes; the sequence is either a single array, [`JsonFormat::Array`], or
+one object per line, [`JsonFormat::Lines`], as in the [JSON Lines](https://jsonlines.org/) format.
+
+When reading, each object must have a key for every attribute of the schema, and no others;
+otherwise [`Error::MissingKey`], or [`Error::UnexpectedKey`], is returned with the row at which
+the object was found. Values are coerced to the domain of their attribute as follows.
+
+| JSON      | Domain                                                          |
+|-----------|-----------------------------------------------------------------|
+| `null`    | any nullable attribute.                                         |
+| `boolean` | `boolean`.                                                      |
+| `number`  | `byte`, `unsigned`, `integer`, `float`, or `decimal`, in range. |
+| `string`  | any domain, parsed by [`Value::parse`].                         |
+
+A value that cannot be coerced is reported as [`Error::InvalidValue`], with the row of the object
+and the 1-based position of the attribute in the schema. When writing, booleans, integers, and finite
+floats are written as JSON booleans and numbers, null as `null`, and all other values as strings
+in the form read by [`Value::parse`]; decimals are therefore written as strings so that they
+retain their precision.
+
+# Example
+
+```rust
+use relational_algebra::data::Relation;
+use relational_algebra::simple::io::json::{read_relation, write_relation, JsonFormat, JsonOptions};
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema};
+use relational_algebra::Name;
+
+let schema = SimpleRelationSchema::new(
+    Name::new_unchecked("people"),
+    [
+        SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+        SimpleAttributeSchema::new(Name::new_unchecked("born"), Domain::Date).with_nullable(true),
+    ],
+)
+.unwrap();
+
+let text = r#"[{"born": "1970-01-01", "name": "alice"}, {"name": "bob", "born": null}]"#;
+let relation = read_relation(text.as_bytes(), schema, &JsonOptions::default()).unwrap();
+assert_eq!(relation.len(), 2);
+
+let mut output: Vec<u8> = Vec::new();
+let options = JsonOptions::default().with_format(JsonFormat::Lines);
+write_relation(&mut output, &relation, &options).unwrap();
+assert_eq!(
+    String::from_utf8(output).unwrap(),
+    "{\"name\":\"alice\",\"born\":\"1970-01-01\"}\n{\"name\":\"bob\",\"born\":null}\n"
+);
+```
+
+ */
+
+use crate::data::{Relation, Tuple, Value};
+use crate::error::{invalid_value_at, missing_key, unexpected_key, Error, Result};
+use crate::simple::data::{SimpleRelation, SimpleTuple};
+use crate::simple::io::infer::{label_matches, sanitize_name, Inference, InferredSchema};
+use crate::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema};
+use crate::sort::{AttributeSchema, Domain, RelationSchema};
+use crate::Name;
+use serde_json::{Map, Value as JsonValue};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options used when reading, or writing, JSON; the default is a single, compact, array of
+/// objects.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    format: JsonFormat,
+    pretty: bool,
+}
+
+///
+/// How the objects representing tuples are arranged in a file.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// A single array containing an object for each tuple; the row of an object is its 1-based
+    /// position in the array.
+    #[default]
+    Array,
+    /// An object for each tuple on its own line, blank lines are ignored; the row of an object is
+    /// the line on which it was found.
+    Lines,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Read the JSON from `reader` into a new relation with the given `schema`.
+///
+pub fn read_relation<R>(
+    reader: R,
+    schema: SimpleRelationSchema,
+    options: &JsonOptions,
+) -> Result<SimpleRelation>
+where
+    R: Read,
+{
+    let mut relation = SimpleRelation::new(schema.clone());
+    match options.format {
+        JsonFormat::Array => {
+            let records =
+                match serde_json::from_reader(BufReader::new(reader)).map_err(json_error)? {
+                    JsonValue::Array(records) => records,
+                    _ => return Err(invalid_data("the document is not an array of objects")),
+                };
+            for (index, record) in records.into_iter().enumerate() {
+                let _ = relation.insert(record_to_tuple(&schema, record, index + 1)?)?;
+            }
+        }
+        JsonFormat::Lines => {
+            for (index, line) in BufReader::new(reader).lines().enumerate() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    let record = parse_line(&line, index + 1)?;
+                    let _ = relation.insert(record_to_tuple(&schema, record, index + 1)?)?;
+                }
+            }
+        }
+    }
+    Ok(relation)
+}
+
+///
+/// Read the JSON file at `path` into a new relation with the given `schema`.
+///
+pub fn read_relation_from_path<P>(
+    path: P,
+    schema: SimpleRelationSchema,
+    options: &JsonOptions,
+) -> Result<SimpleRelation>
+where
+    P: AsRef<Path>,
+{
+    read_relation(File::open(path)?, schema, options)
+}
+
+///
+/// Infer the schema of a relation, named `name`, from at most `sample_size` records of the JSON
+/// read from `reader`; see [`infer`](super::infer) for how each column's domain is chosen. The
+/// columns are the keys of the first record, in order, and every other sampled record must have
+/// the same keys.
+///
+pub fn infer_schema<R>(
+    reader: R,
+    name: Name,
+    options: &JsonOptions,
+    sample_size: usize,
+) -> Result<InferredSchema>
+where
+    R: Read,
+{
+    let mut inference = Inference::default();
+    let mut keys: Vec<String> = Vec::new();
+    let complete = match options.format {
+        JsonFormat::Array => {
+            let records =
+                match serde_json::from_reader(BufReader::new(reader)).map_err(json_error)? {
+                    JsonValue::Array(records) => records,
+                    _ => return Err(invalid_data("the document is not an array of objects")),
+                };
+            let complete = records.len() <= sample_size;
+            for (index, record) in records.into_iter().take(sample_size).enumerate() {
+                observe_record(&mut inference, &mut keys, record, index + 1)?;
+            }
+            complete
+        }
+        JsonFormat::Lines => {
+            let mut lines = BufReader::new(reader).lines().enumerate();
+            loop {
+                match lines.next() {
+                    None => break true,
+                    Some((index, line)) => {
+                        let line = line?;
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        if inference.records() == sample_size {
+                            break false;
+                        }
+                        let record = parse_line(&line, index + 1)?;
+                        observe_record(&mut inference, &mut keys, record, index + 1)?;
+                    }
+                }
+            }
+        }
+    };
+    inference.finish(name, complete)
+}
+
+///
+/// Write the tuples of `relation` to `writer` as JSON, with the keys of each object in the order
+/// of the relation's attributes.
+///
+pub fn write_relation<W, R>(writer: W, relation: &R, options: &JsonOptions) -> Result<()>
+where
+    W: Write,
+    R: Relation,
+{
+    let names: Vec<String> = relation
+        .schema()
+        .attributes()
+        .map(|attribute| attribute.name().to_string())
+        .collect();
+    let records = relation.tuples().map(|tuple| {
+        JsonValue::Object(
+            names
+                .iter()
+                .cloned()
+                .zip(tuple.values().map(value_to_json))
+                .collect::<Map<String, JsonValue>>(),
+        )
+    });
+
+    let mut writer = BufWriter::new(writer);
+    match options.format {
+        JsonFormat::Array => {
+            let records = JsonValue::Array(records.collect());
+            if options.pretty {
+                serde_json::to_writer_pretty(&mut writer, &records).map_err(json_error)?;
+            } else {
+                serde_json::to_writer(&mut writer, &records).map_err(json_error)?;
+            }
+            writeln!(writer)?;
+        }
+        JsonFormat::Lines => {
+            for record in records {
+                serde_json::to_writer(&mut writer, &record).map_err(json_error)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+///
+/// Write the tuples of `relation` as JSON to a file at `path`, replacing any existing file.
+///
+pub fn write_relation_to_path<P, R>(path: P, relation: &R, options: &JsonOptions) -> Result<()>
+where
+    P: AsRef<Path>,
+    R: Relation,
+{
+    write_relation(File::create(path)?, relation, options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl JsonOptions {
+    pub fn with_format(self, format: JsonFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    ///
+    /// Write an array indented, with one key per line; this has no effect on JSON Lines.
+    ///
+    pub fn with_pretty(self, pretty: bool) -> Self {
+        Self { pretty, ..self }
+    }
+
+    pub fn format(&self) -> JsonFormat {
+        self.format
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.pretty
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn record_to_tuple(
+    schema: &SimpleRelationSchema,
+    record: JsonValue,
+    row: usize,
+) -> Result<SimpleTuple> {
+    let mut record = match record {
+        JsonValue::Object(record) => record,
+        _ => {
+            return Err(invalid_data(format!(
+                "the record at row {} is not an object",
+                row
+            )))
+        }
+    };
+    if let Some(key) = record.keys().find(|key| {
+        !schema
+            .attributes()
+            .any(|attribute| label_matches(key, attribute.name()))
+    }) {
+        return Err(unexpected_key(key.as_str(), row));
+    }
+    let values = schema
+        .attributes()
+        .enumerate()
+        .map(|(index, attribute)| {
+            let value = match record.remove(attribute.name().as_ref()) {
+                Some(value) => Some(value),
+                None => record
+                    .keys()
+                    .find(|key| label_matches(key, attribute.name()))
+                    .cloned()
+                    .and_then(|key| record.remove(&key)),
+            }
+            .ok_or_else(|| missing_key(attribute.name().clone(), row))?;
+            json_to_value(attribute, value, row, index + 1)
+        })
+        .collect::<Result<Vec<Value>>>()?;
+    Ok(SimpleTuple::from(values))
+}
+
+fn observe_record(
+    inference: &mut Inference,
+    keys: &mut Vec<String>,
+    record: JsonValue,
+    row: usize,
+) -> Result<()> {
+    let mut record = match record {
+        JsonValue::Object(record) => record,
+        _ => {
+            return Err(invalid_data(format!(
+                "the record at row {} is not an object",
+                row
+            )))
+        }
+    };
+    if inference.records() == 0 {
+        for key in record.keys() {
+            inference.add_column(key.as_str());
+            keys.push(key.clone());
+        }
+    } else if let Some(key) = record.keys().find(|key| !keys.contains(key)) {
+        return Err(unexpected_key(key.as_str(), row));
+    }
+
+    inference.add_record();
+    for (column, key) in keys.iter().enumerate() {
+        match record.remove(key) {
+            None => {
+                return Err(missing_key(
+                    sanitize_name(key).unwrap_or_else(|| Name::new_unchecked(key)),
+                    row,
+                ))
+            }
+            Some(JsonValue::Null) => inference.observe(column, None),
+            Some(JsonValue::String(value)) => inference.observe(column, Some(&value)),
+            Some(value @ (JsonValue::Bool(_) | JsonValue::Number(_))) => {
+                inference.observe(column, Some(&value.to_string()))
+            }
+            Some(value) => {
+                return Err(invalid_value_at(
+                    Domain::String,
+                    value.to_string(),
+                    row,
+                    column + 1,
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn json_to_value(
+    attribute: &SimpleAttributeSchema,
+    value: JsonValue,
+    row: usize,
+    column: usize,
+) -> Result<Value> {
+    let domain = *attribute.domain();
+    match (&value, domain) {
+        (JsonValue::Null, Domain::Null) => Some(Value::Null),
+        (JsonValue::Null, _) if attribute.is_nullable() => Some(Value::Null),
+        (JsonValue::Bool(v), Domain::Boolean) => Some(Value::Boolean(*v)),
+        (JsonValue::Number(v), Domain::Byte) => v
+            .as_u64()
+            .and_then(|v| u8::try_from(v).ok())
+            .map(Value::Byte),
+        (JsonValue::Number(v), Domain::UnsignedInteger) => v.as_u64().map(Value::UnsignedInteger),
+        (JsonValue::Number(v), Domain::Integer) => v.as_i64().map(Value::Integer),
+        (JsonValue::Number(v), Domain::Float) => v.as_f64().map(Value::Float),
+        (JsonValue::Number(v), Domain::Decimal) => Value::parse(domain, &v.to_string()).ok(),
+        (JsonValue::String(v), _) => Value::parse(domain, v).ok(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        let given = match value {
+            JsonValue::String(v) => v,
+            _ => value.to_string(),
+        };
+        invalid_value_at(domain, given, row, column)
+    })
+}
+
+fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Null => JsonValue::Null,
+        Value::Boolean(v) => JsonValue::Bool(*v),
+        Value::Byte(v) => JsonValue::from(*v),
+        Value::UnsignedInteger(v) => JsonValue::from(*v),
+        Value::Integer(v) => JsonValue::from(*v),
+        Value::Float(v) if v.is_finite() => JsonValue::from(*v),
+        _ => JsonValue::String(value.to_plain_string()),
+    }
+}
+
+fn parse_line(line: &str, row: usize) -> Result<JsonValue> {
+    serde_json::from_str(line).map_err(|e| {
+        invalid_data(format!(
+            "the record at row {} is not valid JSON; {}",
+            row, e
+        ))
+    })
+}
+
+pub(crate) fn invalid_data<S>(message: S) -> Error
+where
+    S: Into<String>,
+{
+    Error::from(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message.into(),
+    ))
+}
+
+pub(crate) fn json_error(e: serde_json::Error) -> Error {
+    Error::from(std::io::Error::from(e))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -1,10 +1,6 @@
 /*!
-One-line description.
-
-More detailed description, with
-
-# Example
-
+Provides the reading, and writing, of relations in common file formats, and the inference of
+schemas from samples of those files.
  */
 
 // use ...
@@ -38,3 +34,9 @@ More detailed description, with
 // ------------------------------------------------------------------------------------------------
 
 pub mod csv;
+
+pub mod directory;
+
+pub mod infer;
+
+pub mod json;
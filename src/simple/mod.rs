@@ -39,4 +39,9 @@ pub mod sort;
 
 pub mod data;
 
+pub mod ddl;
+
 pub mod io;
+
+#[cfg(feature = "evaluation")]
+pub mod ops;
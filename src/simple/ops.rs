@@ -0,0 +1,1079 @@
+/*!
+Provides implementations of the [`crate::eval::ops`] operator traits for [`SimpleRelation`].
+
+Each operation consumes its operand(s) and returns a new relation, along with a schema describing
+the result. Binary operations name their result after the left-hand operand, unary operations
+retain the name of their operand.
+
+# Example
+
+```rust
+use relational_algebra::ast::Term;
+use relational_algebra::data::Value;
+use relational_algebra::eval::ops::{NaturalJoin, Select};
+use relational_algebra::simple::data::{SimpleRelation, SimpleTuple};
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema};
+use relational_algebra::Name;
+
+let people = SimpleRelation::from_tuples(
+    SimpleRelationSchema::new(
+        Name::new_unchecked("people"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+            SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+        ],
+    )
+    .unwrap(),
+    [SimpleTuple::from(vec![Value::from(1i64), Value::from("alice")])],
+)
+.unwrap();
+let ages = SimpleRelation::from_tuples(
+    SimpleRelationSchema::new(
+        Name::new_unchecked("ages"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+            SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+        ],
+    )
+    .unwrap(),
+    [SimpleTuple::from(vec![Value::from(1i64), Value::from(32i64)])],
+)
+.unwrap();
+
+let adults = people
+    .natural_join(ages)
+    .unwrap()
+    .select(Term::greater_than_or_equal(
+        Name::new_unchecked("age"),
+        Value::from(18i64),
+    ))
+    .unwrap();
+assert_eq!(adults.len(), 1);
+```
+
+ */
+
+use crate::{
+    ast::{
+        Aggregate, AggregateFunction, Attribute, OuterJoinKind, ProjectedAttribute, SortKey, Term,
+    },
+    data::{Decimal, Relation, Tuple, Value},
+    error::{
+        arithmetic_overflow, attribute_does_not_exist, duplicate_attribute, incompatible_arity,
+        incompatible_types, nullary_facts_not_allowed, unsupported_collation, Result,
+    },
+    eval::{
+        attribute_to_index, compare_values, numeric_as_decimal, numeric_as_float,
+        numeric_as_integer,
+        ops::{
+            AntiJoin, CartesianProduct, Difference, Divide, Group, Intersect, NaturalJoin,
+            OuterJoin, Project, Rename, Select, SemiJoin, Sort, ThetaJoin, Union,
+        },
+        prepare_term, projected_attribute_schema, scalar_value, Patterns,
+    },
+    simple::{
+        data::{SimpleRelation, SimpleTuple},
+        sort::{SimpleAttributeSchema, SimpleRelationSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema},
+    Name,
+};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The collations supported when sorting; `binary` compares values directly, and `nocase`
+/// compares strings and characters ignoring case.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Collation {
+    Binary,
+    NoCase,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Union for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn union(self, rhs: Self) -> Self::Output {
+        check_union_compatible(self.schema(), rhs.schema())?;
+        let (schema, tuples) = self.into_parts();
+        Ok(from_unique_tuples(
+            schema,
+            tuples.into_iter().chain(rhs.into_parts().1),
+        ))
+    }
+}
+
+impl Intersect for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn intersect(self, rhs: Self) -> Self::Output {
+        check_union_compatible(self.schema(), rhs.schema())?;
+        let (schema, tuples) = self.into_parts();
+        Ok(from_unique_tuples(
+            schema,
+            tuples.into_iter().filter(|t| rhs.contains(t)),
+        ))
+    }
+}
+
+impl Difference for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn difference(self, rhs: Self) -> Self::Output {
+        check_union_compatible(self.schema(), rhs.schema())?;
+        let (schema, tuples) = self.into_parts();
+        Ok(from_unique_tuples(
+            schema,
+            tuples.into_iter().filter(|t| !rhs.contains(t)),
+        ))
+    }
+}
+
+impl CartesianProduct for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn cartesian_product(self, rhs: Self) -> Self::Output {
+        let mut result = SimpleRelation::new(product_schema(self.schema(), rhs.schema())?);
+        for lhs_tuple in self.tuples() {
+            for rhs_tuple in rhs.tuples() {
+                let _ = result.insert_unchecked(concat_tuples(lhs_tuple, rhs_tuple));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Select for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn select(self, criteria: Term) -> Self::Output {
+        let mut result = SimpleRelation::new(self.schema().clone());
+        let predicate = prepare_term(&criteria, result.schema())?;
+        for tuple in self.into_parts().1 {
+            if predicate.is_satisfied(result.schema(), &tuple)? {
+                let _ = result.insert_unchecked(tuple);
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Project for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn project(self, attributes: &[Attribute]) -> Self::Output {
+        let attributes: Vec<ProjectedAttribute> = attributes
+            .iter()
+            .map(|a| match a {
+                Attribute::Index(v) => ProjectedAttribute::Index(*v),
+                Attribute::Name(v) => ProjectedAttribute::Name(v.clone()),
+            })
+            .collect();
+        self.extended_project(&attributes)
+    }
+}
+
+impl Sort for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn sort(self, keys: &[SortKey]) -> Self::Output {
+        let keys = keys
+            .iter()
+            .map(|key| {
+                Ok((
+                    attribute_to_index(key.attribute(), self.schema())?,
+                    key.is_descending(),
+                    key.is_nulls_first(),
+                    collation(key.collation())?,
+                ))
+            })
+            .collect::<Result<Vec<(usize, bool, bool, Collation)>>>()?;
+        let (schema, mut tuples) = self.into_parts();
+
+        let mut error = None;
+        tuples.sort_by(|lhs, rhs| {
+            for (index, descending, nulls_first, collation) in &keys {
+                let (lhs, rhs) = (lhs.value(*index).unwrap(), rhs.value(*index).unwrap());
+                // nulls are placed by the key's null ordering, whatever its direction.
+                match (lhs.is_null(), rhs.is_null()) {
+                    (true, true) => continue,
+                    (true, false) if *nulls_first => return Ordering::Less,
+                    (true, false) => return Ordering::Greater,
+                    (false, true) if *nulls_first => return Ordering::Greater,
+                    (false, true) => return Ordering::Less,
+                    (false, false) => {}
+                }
+                match collation.compare(lhs, rhs) {
+                    Ok(Ordering::Equal) => {}
+                    Ok(ordering) if *descending => return ordering.reverse(),
+                    Ok(ordering) => return ordering,
+                    Err(e) => {
+                        let _ = error.get_or_insert(e);
+                        return Ordering::Equal;
+                    }
+                }
+            }
+            Ordering::Equal
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(from_unique_tuples(schema, tuples)),
+        }
+    }
+}
+
+impl Group for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn group(self, attributes: &[Attribute], aggregates: &[Aggregate]) -> Self::Output {
+        let indices = attribute_indices(attributes, self.schema())?;
+        let schema = group_schema(self.schema(), &indices, aggregates)?;
+        let arguments = aggregates
+            .iter()
+            .map(|aggregate| {
+                aggregate
+                    .argument()
+                    .map(|argument| attribute_to_index(argument, self.schema()))
+                    .transpose()
+            })
+            .collect::<Result<Vec<Option<usize>>>>()?;
+
+        // groups are kept in the order in which their first tuple appears.
+        let mut keys: Vec<SimpleTuple> = Default::default();
+        let mut groups: HashMap<SimpleTuple, Vec<&SimpleTuple>> = Default::default();
+        for tuple in self.tuples() {
+            let key: SimpleTuple = indices
+                .iter()
+                .map(|i| tuple.value(*i).unwrap().clone())
+                .collect();
+            if !groups.contains_key(&key) {
+                keys.push(key.clone());
+            }
+            groups.entry(key).or_default().push(tuple);
+        }
+        // without grouping attributes the whole relation, even when empty, is a single group.
+        if indices.is_empty() && keys.is_empty() {
+            let key: SimpleTuple = Vec::<Value>::new().into();
+            let _ = groups.insert(key.clone(), Default::default());
+            keys.push(key);
+        }
+
+        let mut result = SimpleRelation::new(schema);
+        for key in keys {
+            let group = &groups[&key];
+            let mut values: Vec<Value> = key.into();
+            for ((aggregate, argument), attribute) in aggregates
+                .iter()
+                .zip(&arguments)
+                .zip(result.schema().attributes().skip(indices.len()))
+            {
+                values.push(aggregate_group(
+                    aggregate,
+                    *argument,
+                    *attribute.domain(),
+                    group,
+                )?);
+            }
+            let _ = result.insert_unchecked(values.into());
+        }
+        Ok(result)
+    }
+}
+
+impl NaturalJoin for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn natural_join(self, rhs: Self) -> Self::Output {
+        let common = common_attributes(self.schema(), rhs.schema())?;
+        let rhs_retained = retained_attributes(rhs.schema(), &common);
+        let mut result =
+            SimpleRelation::new(natural_join_schema(self.schema(), rhs.schema(), &common)?);
+
+        let (lhs_common, rhs_common): (Vec<usize>, Vec<usize>) = common.iter().cloned().unzip();
+        let rhs_tuples: Vec<&SimpleTuple> = rhs.tuples().collect();
+        let rhs_index = join_index(&rhs_tuples, &rhs_common);
+        for lhs_tuple in self.tuples() {
+            if let Some(matches) = join_key(lhs_tuple, &lhs_common).and_then(|k| rhs_index.get(&k))
+            {
+                for i in matches {
+                    let _ = result.insert_unchecked(
+                        lhs_tuple
+                            .values()
+                            .cloned()
+                            .chain(
+                                rhs_retained
+                                    .iter()
+                                    .map(|r| rhs_tuples[*i].value(*r).unwrap().clone()),
+                            )
+                            .collect(),
+                    );
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl ThetaJoin for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn theta_join(self, criteria: Term, rhs: Self) -> Self::Output {
+        let mut result = SimpleRelation::new(product_schema(self.schema(), rhs.schema())?);
+        let predicate = prepare_term(&criteria, result.schema())?;
+        for lhs_tuple in self.tuples() {
+            for rhs_tuple in rhs.tuples() {
+                let tuple = concat_tuples(lhs_tuple, rhs_tuple);
+                if predicate.is_satisfied(result.schema(), &tuple)? {
+                    let _ = result.insert_unchecked(tuple);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl OuterJoin for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn left_outer_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        outer_join(self, OuterJoinKind::Left, criteria, rhs)
+    }
+
+    fn right_outer_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        outer_join(self, OuterJoinKind::Right, criteria, rhs)
+    }
+
+    fn full_outer_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        outer_join(self, OuterJoinKind::Full, criteria, rhs)
+    }
+}
+
+impl SemiJoin for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn left_semi_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        semi_join(self, criteria, rhs, true, false)
+    }
+
+    fn right_semi_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        semi_join(self, criteria, rhs, false, false)
+    }
+}
+
+impl AntiJoin for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn anti_join(self, criteria: Option<Term>, rhs: Self) -> Self::Output {
+        semi_join(self, criteria, rhs, true, true)
+    }
+}
+
+impl Divide for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    ///
+    /// Divide in a single pass over `self`, recording for each candidate quotient tuple which of
+    /// the tuples of `rhs` it has been seen with; a candidate is in the result if it has been
+    /// seen with all of them. As in a join, a null value matches nothing.
+    ///
+    fn divide(self, rhs: Self) -> Self::Output {
+        let (quotient, divisor) = division_attributes(self.schema(), rhs.schema())?;
+        let schema = division_schema(self.schema(), &quotient)?;
+        let divisor_index: HashMap<SimpleTuple, usize> = rhs
+            .tuples()
+            .enumerate()
+            .map(|(i, tuple)| (tuple.clone(), i))
+            .collect();
+
+        let mut candidates: Vec<(SimpleTuple, Vec<bool>, usize)> = Default::default();
+        let mut candidate_index: HashMap<SimpleTuple, usize> = Default::default();
+        let key_indices: Vec<usize> = quotient.iter().chain(&divisor).cloned().collect();
+        for tuple in self.tuples() {
+            let candidate: SimpleTuple = quotient
+                .iter()
+                .map(|i| tuple.value(*i).unwrap().clone())
+                .collect();
+            let c = *candidate_index.entry(candidate.clone()).or_insert_with(|| {
+                candidates.push((candidate, vec![false; divisor_index.len()], 0));
+                candidates.len() - 1
+            });
+            if let Some(key) = join_key(tuple, &key_indices) {
+                let key: SimpleTuple = key.values().skip(quotient.len()).cloned().collect();
+                if let Some(d) = divisor_index.get(&key) {
+                    let (_, seen, count) = &mut candidates[c];
+                    if !seen[*d] {
+                        seen[*d] = true;
+                        *count += 1;
+                    }
+                }
+            }
+        }
+        Ok(from_unique_tuples(
+            schema,
+            candidates
+                .into_iter()
+                .filter(|(_, _, count)| *count == divisor_index.len())
+                .map(|(candidate, _, _)| candidate),
+        ))
+    }
+}
+
+impl Rename for SimpleRelation {
+    type Output = Result<SimpleRelation>;
+
+    fn rename(self, source: Attribute, target: Name) -> Self::Output {
+        self.rename_all([(source, target)].into_iter().collect())
+    }
+
+    fn rename_all(self, names: HashMap<Attribute, Name>) -> Self::Output {
+        let (mut schema, tuples) = self.into_parts();
+        rename_attributes(&mut schema, &names)?;
+        Ok(from_unique_tuples(schema, tuples))
+    }
+}
+
+impl SimpleRelation {
+    ///
+    /// Project the attributes of this relation, where each projected attribute may also be a
+    /// constant value or a computed scalar expression. A constant, or an unnamed computed
+    /// attribute, in position `i` will result in an attribute named `_i`.
+    ///
+    pub fn extended_project(self, attributes: &[ProjectedAttribute]) -> Result<SimpleRelation> {
+        let mut sources: Vec<ProjectedAttribute> = Default::default();
+        let mut result_attributes: Vec<SimpleAttributeSchema> = Default::default();
+        let mut patterns = Patterns::default();
+        for (i, projected) in attributes.iter().enumerate() {
+            let attribute = match projected {
+                ProjectedAttribute::Index(v) => Attribute::Index(*v),
+                ProjectedAttribute::Name(v) => Attribute::Name(v.clone()),
+                ProjectedAttribute::Constant(value) => {
+                    result_attributes.push(constant_attribute_schema(i, value));
+                    sources.push(projected.clone());
+                    continue;
+                }
+                ProjectedAttribute::Computed(_, _) => {
+                    result_attributes.push(projected_attribute_schema(
+                        i,
+                        projected,
+                        self.schema(),
+                        &mut patterns,
+                    )?);
+                    sources.push(projected.clone());
+                    continue;
+                }
+            };
+            let index = attribute_to_index(&attribute, self.schema())?;
+            result_attributes.push(self.schema().attribute(index).unwrap().clone());
+            sources.push(ProjectedAttribute::Index(index));
+        }
+
+        let mut result = SimpleRelation::new(SimpleRelationSchema::new(
+            self.schema().name().clone(),
+            result_attributes,
+        )?);
+        for tuple in self.tuples() {
+            let _ = result.insert_unchecked(
+                sources
+                    .iter()
+                    .map(|source| match source {
+                        ProjectedAttribute::Index(index) => {
+                            Ok(tuple.value(*index).unwrap().clone())
+                        }
+                        ProjectedAttribute::Constant(value) => Ok(value.clone()),
+                        ProjectedAttribute::Computed(expression, _) => {
+                            scalar_value(expression, self.schema(), tuple, &patterns)
+                        }
+                        ProjectedAttribute::Name(_) => unreachable!(),
+                    })
+                    .collect::<Result<Vec<Value>>>()?
+                    .into(),
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl Collation {
+    fn compare(&self, lhs: &Value, rhs: &Value) -> Result<Ordering> {
+        match (self, lhs, rhs) {
+            (Self::NoCase, Value::String(l), Value::String(r)) => {
+                Ok(l.to_lowercase().cmp(&r.to_lowercase()))
+            }
+            (Self::NoCase, Value::Char(l), Value::Char(r)) => {
+                Ok(l.to_lowercase().cmp(r.to_lowercase()))
+            }
+            _ => compare_values(lhs, rhs),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn check_union_compatible(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+) -> Result<()> {
+    if lhs.len() != rhs.len() {
+        return Err(incompatible_arity(lhs.len(), rhs.len()));
+    }
+    for (l, r) in lhs.attributes().zip(rhs.attributes()) {
+        if l.domain() != r.domain() {
+            return Err(incompatible_types(*l.domain(), *r.domain()));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn product_schema(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+) -> Result<SimpleRelationSchema> {
+    SimpleRelationSchema::new(
+        lhs.name().clone(),
+        lhs.attributes().chain(rhs.attributes()).cloned(),
+    )
+}
+
+///
+/// Returns the pairs of `(lhs, rhs)` attribute indices that share a name, and will therefore be
+/// joined on in a natural join.
+///
+pub(crate) fn common_attributes(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+) -> Result<Vec<(usize, usize)>> {
+    let mut common: Vec<(usize, usize)> = Default::default();
+    for (lhs_index, attribute) in lhs.attributes().enumerate() {
+        if let Some(rhs_index) = rhs.attribute_index(attribute.name()) {
+            let rhs_domain = rhs.attribute(rhs_index).unwrap().domain();
+            if attribute.domain() != rhs_domain {
+                return Err(incompatible_types(*attribute.domain(), *rhs_domain));
+            }
+            common.push((lhs_index, rhs_index));
+        }
+    }
+    Ok(common)
+}
+
+///
+/// Returns the schema of an outer join of `lhs` and `rhs`, naturally on the `common` attributes
+/// if any, or otherwise their product, where the attributes of each operand that may be padded
+/// with null values for `kind` are nullable.
+///
+pub(crate) fn outer_join_schema(
+    lhs: &SimpleRelationSchema,
+    kind: OuterJoinKind,
+    common: Option<&[(usize, usize)]>,
+    rhs: &SimpleRelationSchema,
+) -> Result<SimpleRelationSchema> {
+    let mut result = match common {
+        None => product_schema(lhs, rhs)?,
+        Some(common) => natural_join_schema(lhs, rhs, common)?,
+    };
+    for index in 0..result.len() {
+        let padded = if index < lhs.len() {
+            // an unmatched tuple of a natural join's rhs provides the common attributes.
+            kind.retains_rhs() && !common.unwrap_or_default().iter().any(|(l, _)| *l == index)
+        } else {
+            kind.retains_lhs()
+        };
+        if padded {
+            result.attribute_mut(index).unwrap().set_nullable(true);
+        }
+    }
+    Ok(result)
+}
+
+pub(crate) fn natural_join_schema(
+    lhs: &SimpleRelationSchema,
+    rhs: &SimpleRelationSchema,
+    common: &[(usize, usize)],
+) -> Result<SimpleRelationSchema> {
+    SimpleRelationSchema::new(
+        lhs.name().clone(),
+        lhs.attributes().cloned().chain(
+            retained_attributes(rhs, common)
+                .into_iter()
+                .map(|i| rhs.attribute(i).unwrap().clone()),
+        ),
+    )
+}
+
+///
+/// Returns the indices of the attributes of `dividend` which form the quotient of a division,
+/// and the index in `dividend` of each attribute of `divisor`, matched by name.
+///
+pub(crate) fn division_attributes(
+    dividend: &SimpleRelationSchema,
+    divisor: &SimpleRelationSchema,
+) -> Result<(Vec<usize>, Vec<usize>)> {
+    let mut divisor_indices: Vec<usize> = Default::default();
+    for attribute in divisor.attributes() {
+        let index = dividend
+            .attribute_index(attribute.name())
+            .ok_or_else(|| attribute_does_not_exist(attribute.name().clone()))?;
+        let domain = dividend.attribute(index).unwrap().domain();
+        if attribute.domain() != domain {
+            return Err(incompatible_types(*domain, *attribute.domain()));
+        }
+        divisor_indices.push(index);
+    }
+    let quotient: Vec<usize> = (0..dividend.len())
+        .filter(|i| !divisor_indices.contains(i))
+        .collect();
+    if quotient.is_empty() {
+        return Err(nullary_facts_not_allowed());
+    }
+    Ok((quotient, divisor_indices))
+}
+
+pub(crate) fn division_schema(
+    dividend: &SimpleRelationSchema,
+    quotient: &[usize],
+) -> Result<SimpleRelationSchema> {
+    SimpleRelationSchema::new(
+        dividend.name().clone(),
+        quotient
+            .iter()
+            .map(|i| dividend.attribute(*i).unwrap().clone()),
+    )
+}
+
+///
+/// Rename the attributes of `schema`; every attribute is resolved before any is renamed, so that
+/// names may be exchanged, and no renamed attribute may share its name with another attribute.
+///
+pub(crate) fn rename_attributes<'a, I>(schema: &mut SimpleRelationSchema, renames: I) -> Result<()>
+where
+    I: IntoIterator<Item = (&'a Attribute, &'a Name)>,
+{
+    let renames = renames
+        .into_iter()
+        .map(|(attribute, name)| Ok((attribute_to_index(attribute, schema)?, name)))
+        .collect::<Result<Vec<(usize, &Name)>>>()?;
+    for (index, name) in &renames {
+        schema
+            .attribute_mut(*index)
+            .unwrap()
+            .set_name((*name).clone());
+    }
+    for (index, name) in renames {
+        if schema
+            .attributes()
+            .enumerate()
+            .any(|(i, a)| i != index && a.name() == name)
+        {
+            return Err(duplicate_attribute(name.clone()));
+        }
+    }
+    Ok(())
+}
+
+///
+/// Returns the values of `tuple` for the attributes at `indices`, as used to match the tuples of
+/// a natural join, or `None` if any value is null, as a null value matches nothing.
+///
+fn join_key(tuple: &SimpleTuple, indices: &[usize]) -> Option<SimpleTuple> {
+    let key: SimpleTuple = indices
+        .iter()
+        .map(|i| tuple.value(*i).unwrap().clone())
+        .collect();
+    if key.values().any(Value::is_null) {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+///
+/// Index the position of each tuple in `tuples` by its [`join_key`].
+///
+fn join_index(tuples: &[&SimpleTuple], indices: &[usize]) -> HashMap<SimpleTuple, Vec<usize>> {
+    let mut index: HashMap<SimpleTuple, Vec<usize>> = Default::default();
+    for (i, tuple) in tuples.iter().enumerate() {
+        if let Some(key) = join_key(tuple, indices) {
+            index.entry(key).or_default().push(i);
+        }
+    }
+    index
+}
+
+///
+/// Join `lhs` and `rhs`, naturally if there are no criteria, and then add those tuples of either
+/// operand retained by `kind` that matched nothing, padded with null values. An unmatched tuple
+/// of `rhs` in a natural join provides the values of the common attributes.
+///
+fn outer_join(
+    lhs: SimpleRelation,
+    kind: OuterJoinKind,
+    criteria: Option<Term>,
+    rhs: SimpleRelation,
+) -> Result<SimpleRelation> {
+    let rhs_tuples: Vec<&SimpleTuple> = rhs.tuples().collect();
+    let mut rhs_matched = vec![false; rhs_tuples.len()];
+    match criteria {
+        None => {
+            let common = common_attributes(lhs.schema(), rhs.schema())?;
+            let rhs_retained = retained_attributes(rhs.schema(), &common);
+            let mut result = SimpleRelation::new(outer_join_schema(
+                lhs.schema(),
+                kind,
+                Some(&common),
+                rhs.schema(),
+            )?);
+            let (lhs_common, rhs_common): (Vec<usize>, Vec<usize>) = common.iter().cloned().unzip();
+            let rhs_index = join_index(&rhs_tuples, &rhs_common);
+            for lhs_tuple in lhs.tuples() {
+                let matches = join_key(lhs_tuple, &lhs_common).and_then(|k| rhs_index.get(&k));
+                for i in matches.into_iter().flatten() {
+                    rhs_matched[*i] = true;
+                    let _ = result.insert_unchecked(
+                        lhs_tuple
+                            .values()
+                            .cloned()
+                            .chain(
+                                rhs_retained
+                                    .iter()
+                                    .map(|r| rhs_tuples[*i].value(*r).unwrap().clone()),
+                            )
+                            .collect(),
+                    );
+                }
+                if matches.is_none() && kind.retains_lhs() {
+                    let _ = result.insert_unchecked(
+                        lhs_tuple
+                            .values()
+                            .cloned()
+                            .chain(nulls(rhs_retained.len()))
+                            .collect(),
+                    );
+                }
+            }
+            if kind.retains_rhs() {
+                for (rhs_tuple, _) in rhs_tuples.iter().zip(&rhs_matched).filter(|(_, m)| !**m) {
+                    let _ = result.insert_unchecked(
+                        (0..lhs.schema().len())
+                            .map(|l| match common.iter().find(|(cl, _)| *cl == l) {
+                                Some((_, r)) => rhs_tuple.value(*r).unwrap().clone(),
+                                None => Value::Null,
+                            })
+                            .chain(
+                                rhs_retained
+                                    .iter()
+                                    .map(|r| rhs_tuple.value(*r).unwrap().clone()),
+                            )
+                            .collect(),
+                    );
+                }
+            }
+            Ok(result)
+        }
+        Some(criteria) => {
+            let mut result =
+                SimpleRelation::new(outer_join_schema(lhs.schema(), kind, None, rhs.schema())?);
+            let predicate = prepare_term(&criteria, result.schema())?;
+            for lhs_tuple in lhs.tuples() {
+                let mut matched = false;
+                for (i, rhs_tuple) in rhs_tuples.iter().enumerate() {
+                    let tuple = concat_tuples(lhs_tuple, rhs_tuple);
+                    if predicate.is_satisfied(result.schema(), &tuple)? {
+                        matched = true;
+                        rhs_matched[i] = true;
+                        let _ = result.insert_unchecked(tuple);
+                    }
+                }
+                if !matched && kind.retains_lhs() {
+                    let _ = result.insert_unchecked(
+                        lhs_tuple
+                            .values()
+                            .cloned()
+                            .chain(nulls(rhs.schema().len()))
+                            .collect(),
+                    );
+                }
+            }
+            if kind.retains_rhs() {
+                for (rhs_tuple, _) in rhs_tuples.iter().zip(&rhs_matched).filter(|(_, m)| !**m) {
+                    let _ = result.insert_unchecked(
+                        nulls(lhs.schema().len())
+                            .chain(rhs_tuple.values().cloned())
+                            .collect(),
+                    );
+                }
+            }
+            Ok(result)
+        }
+    }
+}
+
+///
+/// Filter the tuples of `lhs`, or of `rhs` if not `retain_lhs`, to those with a match in the
+/// other operand, or to those without one if `anti`; naturally if there are no criteria. The
+/// joined tuples are never collected, and matching stops at the first match for each tuple.
+///
+fn semi_join(
+    lhs: SimpleRelation,
+    criteria: Option<Term>,
+    rhs: SimpleRelation,
+    retain_lhs: bool,
+    anti: bool,
+) -> Result<SimpleRelation> {
+    match criteria {
+        None => {
+            let common = common_attributes(lhs.schema(), rhs.schema())?;
+            let (lhs_common, rhs_common): (Vec<usize>, Vec<usize>) = common.iter().cloned().unzip();
+            let (retained, retained_common, other, other_common) = if retain_lhs {
+                (lhs, lhs_common, rhs, rhs_common)
+            } else {
+                (rhs, rhs_common, lhs, lhs_common)
+            };
+            let other_keys: HashSet<SimpleTuple> = other
+                .tuples()
+                .filter_map(|tuple| join_key(tuple, &other_common))
+                .collect();
+            let (schema, tuples) = retained.into_parts();
+            Ok(from_unique_tuples(
+                schema,
+                tuples.into_iter().filter(|tuple| {
+                    join_key(tuple, &retained_common).is_some_and(|key| other_keys.contains(&key))
+                        != anti
+                }),
+            ))
+        }
+        Some(criteria) => {
+            let schema = product_schema(lhs.schema(), rhs.schema())?;
+            let predicate = prepare_term(&criteria, &schema)?;
+            let mut result = SimpleRelation::new(if retain_lhs {
+                lhs.schema().clone()
+            } else {
+                rhs.schema().clone()
+            });
+            let (retained, other) = if retain_lhs {
+                (&lhs, &rhs)
+            } else {
+                (&rhs, &lhs)
+            };
+            for tuple in retained.tuples() {
+                let mut matched = false;
+                for other_tuple in other.tuples() {
+                    let joined = if retain_lhs {
+                        concat_tuples(tuple, other_tuple)
+                    } else {
+                        concat_tuples(other_tuple, tuple)
+                    };
+                    if predicate.is_satisfied(&schema, &joined)? {
+                        matched = true;
+                        break;
+                    }
+                }
+                if matched != anti {
+                    let _ = result.insert_unchecked(tuple.clone());
+                }
+            }
+            Ok(result)
+        }
+    }
+}
+
+fn nulls(count: usize) -> impl Iterator<Item = Value> {
+    std::iter::repeat_n(Value::Null, count)
+}
+
+fn retained_attributes(rhs: &SimpleRelationSchema, common: &[(usize, usize)]) -> Vec<usize> {
+    (0..rhs.len())
+        .filter(|i| !common.iter().any(|(_, r)| r == i))
+        .collect()
+}
+
+///
+/// The name given to an attribute created by projecting a constant value in position `index`.
+///
+pub(crate) fn constant_attribute_name(index: usize) -> Name {
+    Name::new_unchecked(&format!("_{}", index))
+}
+
+///
+/// The schema of an attribute created by projecting the constant `value` in position `index`,
+/// which is nullable only if the value is null.
+///
+pub(crate) fn constant_attribute_schema(index: usize, value: &Value) -> SimpleAttributeSchema {
+    SimpleAttributeSchema::new(constant_attribute_name(index), value.data_type())
+        .with_nullable(value.is_null())
+}
+
+///
+/// Returns the collation named `name`, or the default `binary` collation if there is no name.
+///
+pub(crate) fn collation(name: Option<&Name>) -> Result<Collation> {
+    match name.map(Name::as_ref) {
+        None | Some("binary") => Ok(Collation::Binary),
+        Some("nocase") => Ok(Collation::NoCase),
+        Some(_) => Err(unsupported_collation(name.unwrap().clone())),
+    }
+}
+
+///
+/// Returns the schema of a group; the attributes at `indices` followed by one attribute per
+/// aggregate. An unnamed aggregate is named for its function and argument, as `sum_a`, or just
+/// `count` for `count(*)`; every aggregate but `count` is nullable.
+///
+pub(crate) fn group_schema(
+    schema: &SimpleRelationSchema,
+    indices: &[usize],
+    aggregates: &[Aggregate],
+) -> Result<SimpleRelationSchema> {
+    let mut attributes: Vec<SimpleAttributeSchema> = indices
+        .iter()
+        .map(|i| schema.attribute(*i).unwrap().clone())
+        .collect();
+    for aggregate in aggregates {
+        let argument = match aggregate.argument() {
+            None => None,
+            Some(argument) => Some(
+                schema
+                    .attribute(attribute_to_index(argument, schema)?)
+                    .unwrap(),
+            ),
+        };
+        let domain = match (aggregate.function(), argument.map(|a| *a.domain())) {
+            (AggregateFunction::Count, _) => Domain::Integer,
+            (AggregateFunction::Sum, Some(Domain::Byte | Domain::UnsignedInteger)) => {
+                Domain::UnsignedInteger
+            }
+            (AggregateFunction::Sum, Some(Domain::Integer)) => Domain::Integer,
+            (AggregateFunction::Sum, Some(Domain::Float)) => Domain::Float,
+            (AggregateFunction::Sum | AggregateFunction::Average, Some(Domain::Decimal)) => {
+                Domain::Decimal
+            }
+            (AggregateFunction::Average, Some(domain)) if domain.is_numeric() => Domain::Float,
+            (AggregateFunction::Sum | AggregateFunction::Average, Some(domain)) => {
+                return Err(incompatible_types(Domain::Float, domain))
+            }
+            (AggregateFunction::Minimum | AggregateFunction::Maximum, Some(domain)) => domain,
+            (_, None) => unreachable!(),
+        };
+        let name = match (aggregate.name(), argument) {
+            (Some(name), _) => name.clone(),
+            (None, None) => Name::new_unchecked(&aggregate.function().to_string()),
+            (None, Some(argument)) => {
+                Name::new_unchecked(&format!("{}_{}", aggregate.function(), argument.name()))
+            }
+        };
+        // any aggregate but count is null over a group with only null values, or none.
+        attributes.push(
+            SimpleAttributeSchema::new(name, domain)
+                .with_nullable(aggregate.function() != AggregateFunction::Count),
+        );
+    }
+    SimpleRelationSchema::new(schema.name().clone(), attributes)
+}
+
+///
+/// Calculate the value of `aggregate` over the tuples in `group`, where `argument` is the index of
+/// the aggregate's argument and `domain` the domain of its result. Null values are ignored, and
+/// any aggregate other than `count` over only null values is itself null.
+///
+fn aggregate_group(
+    aggregate: &Aggregate,
+    argument: Option<usize>,
+    domain: Domain,
+    group: &[&SimpleTuple],
+) -> Result<Value> {
+    let argument = match argument {
+        None => return Ok(Value::Integer(group.len() as i64)),
+        Some(argument) => argument,
+    };
+    let mut values: Vec<&Value> = group
+        .iter()
+        .map(|tuple| tuple.value(argument).unwrap())
+        .filter(|value| !value.is_null())
+        .collect();
+    if aggregate.is_distinct() {
+        let mut seen: HashSet<&Value> = Default::default();
+        values.retain(|v| seen.insert(*v));
+    }
+    if values.is_empty() && aggregate.function() != AggregateFunction::Count {
+        return Ok(Value::Null);
+    }
+    Ok(match aggregate.function() {
+        AggregateFunction::Count => Value::Integer(values.len() as i64),
+        AggregateFunction::Sum | AggregateFunction::Average if domain == Domain::Decimal => {
+            let mut sum = Decimal::ZERO;
+            for value in &values {
+                sum = sum
+                    .checked_add(numeric_as_decimal(value))
+                    .ok_or_else(|| arithmetic_overflow(aggregate.to_string()))?;
+            }
+            match aggregate.function() {
+                AggregateFunction::Sum => Value::Decimal(sum),
+                _ => Value::Decimal(sum / Decimal::from(values.len())),
+            }
+        }
+        AggregateFunction::Sum => match domain {
+            Domain::Float => Value::Float(values.iter().map(|v| numeric_as_float(v)).sum()),
+            _ => {
+                let sum: i128 = values.iter().map(|v| numeric_as_integer(v).unwrap()).sum();
+                match domain {
+                    Domain::Integer => i64::try_from(sum).ok().map(Value::Integer),
+                    _ => u64::try_from(sum).ok().map(Value::UnsignedInteger),
+                }
+                .ok_or_else(|| arithmetic_overflow(aggregate.to_string()))?
+            }
+        },
+        AggregateFunction::Average => Value::Float(
+            values.iter().map(|v| numeric_as_float(v)).sum::<f64>() / values.len() as f64,
+        ),
+        AggregateFunction::Minimum | AggregateFunction::Maximum => {
+            let wanted = if aggregate.function() == AggregateFunction::Minimum {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+            let mut values = values.into_iter();
+            let mut result = values.next().unwrap();
+            for value in values {
+                if compare_values(value, result)? == wanted {
+                    result = value;
+                }
+            }
+            result.clone()
+        }
+    })
+}
+
+fn concat_tuples(lhs: &SimpleTuple, rhs: &SimpleTuple) -> SimpleTuple {
+    lhs.values().chain(rhs.values()).cloned().collect()
+}
+
+pub(crate) fn attribute_indices(
+    attributes: &[Attribute],
+    schema: &SimpleRelationSchema,
+) -> Result<Vec<usize>> {
+    attributes
+        .iter()
+        .map(|a| attribute_to_index(a, schema))
+        .collect()
+}
+
+fn from_unique_tuples<I>(schema: SimpleRelationSchema, tuples: I) -> SimpleRelation
+where
+    I: IntoIterator<Item = SimpleTuple>,
+{
+    let mut result = SimpleRelation::new(schema);
+    for tuple in tuples {
+        let _ = result.insert_unchecked(tuple);
+    }
+    result
+}
@@ -8,10 +8,11 @@ More detailed description, with
  */
 
 use crate::{
+    error::duplicate_relation,
     sort::{AttributeSchema, Domain, RelationSchema, Schema},
     Name,
 };
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -24,15 +25,15 @@ use std::{collections::HashMap, fmt::Display};
 #[derive(Clone, Debug)]
 pub struct SimpleSchema {
     name: Name,
-    relations: HashMap<Name, SimpleRelationSchema>,
+    relations: Vec<SimpleRelationSchema>,
 }
 
 #[derive(Debug)]
 pub struct Relations<'a> {
-    iter: std::collections::hash_map::Values<'a, Name, SimpleRelationSchema>,
+    iter: std::slice::Iter<'a, SimpleRelationSchema>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SimpleRelationSchema {
     name: Name,
     attributes: Vec<SimpleAttributeSchema>,
@@ -43,10 +44,11 @@ pub struct Attributes<'a> {
     iter: std::slice::Iter<'a, SimpleAttributeSchema>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SimpleAttributeSchema {
     name: Name,
     data_type: Domain,
+    nullable: bool,
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -63,15 +65,19 @@ pub struct SimpleAttributeSchema {
 
 impl Display for SimpleSchema {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}:{{{}}}",
-            self.name(),
-            self.relations()
-                .map(|r| r.name().to_string())
-                .collect::<Vec<String>>()
-                .join(", ")
-        )
+        if self.is_empty() {
+            write!(f, "schema {} {{}}", self.name())
+        } else {
+            write!(
+                f,
+                "schema {} {{ {} }}",
+                self.name(),
+                self.relations()
+                    .map(SimpleRelationSchema::to_string)
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            )
+        }
     }
 }
 
@@ -83,10 +89,18 @@ impl Schema for SimpleSchema {
         I: IntoIterator<Item = Self::Item>,
         Self: Sized,
     {
-        Ok(Self {
+        // relations are kept in the order given, and their names must be unique.
+        let mut schema = Self {
             name,
-            relations: HashMap::from_iter(relations.into_iter().map(|r| (r.name().clone(), r))),
-        })
+            relations: Default::default(),
+        };
+        for relation in relations {
+            if schema.relation(relation.name()).is_some() {
+                return Err(duplicate_relation(relation.name().clone()));
+            }
+            schema.relations.push(relation);
+        }
+        Ok(schema)
     }
 
     fn len(&self) -> usize {
@@ -98,12 +112,12 @@ impl Schema for SimpleSchema {
     }
 
     fn relation(&self, name: &Name) -> Option<&Self::Item> {
-        self.relations.get(name)
+        self.relations.iter().find(|r| r.name() == name)
     }
 
     fn relations(&self) -> Box<dyn Iterator<Item = &Self::Item> + '_> {
         Box::new(Relations {
-            iter: self.relations.values(),
+            iter: self.relations.iter(),
         })
     }
 }
@@ -167,6 +181,35 @@ impl RelationSchema for SimpleRelationSchema {
     }
 }
 
+impl SimpleRelationSchema {
+    ///
+    /// Create a copy of any other [`RelationSchema`] implementation.
+    ///
+    pub fn from_schema<S>(schema: &S) -> Self
+    where
+        S: RelationSchema,
+    {
+        Self {
+            name: schema.name().clone(),
+            attributes: schema
+                .attributes()
+                .map(|a| {
+                    SimpleAttributeSchema::new(a.name().clone(), *a.domain())
+                        .with_nullable(a.is_nullable())
+                })
+                .collect(),
+        }
+    }
+
+    pub fn set_name(&mut self, name: Name) {
+        self.name = name;
+    }
+
+    pub fn attribute_mut(&mut self, index: usize) -> Option<&mut SimpleAttributeSchema> {
+        self.attributes.get_mut(index)
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 
 impl<'a> Iterator for Attributes<'a> {
@@ -181,7 +224,13 @@ impl<'a> Iterator for Attributes<'a> {
 
 impl Display for SimpleAttributeSchema {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}", self.name(), self.domain())
+        write!(
+            f,
+            "{}: {}{}",
+            self.name(),
+            self.domain(),
+            if self.nullable { "?" } else { "" }
+        )
     }
 }
 
@@ -190,7 +239,11 @@ impl AttributeSchema for SimpleAttributeSchema {
     where
         Self: Sized,
     {
-        Self { name, data_type }
+        Self {
+            name,
+            data_type,
+            nullable: false,
+        }
     }
 
     fn name(&self) -> &Name {
@@ -200,6 +253,27 @@ impl AttributeSchema for SimpleAttributeSchema {
     fn domain(&self) -> &Domain {
         &self.data_type
     }
+
+    fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    fn with_nullable(self, nullable: bool) -> Self
+    where
+        Self: Sized,
+    {
+        Self { nullable, ..self }
+    }
+}
+
+impl SimpleAttributeSchema {
+    pub fn set_name(&mut self, name: Name) {
+        self.name = name;
+    }
+
+    pub fn set_nullable(&mut self, nullable: bool) {
+        self.nullable = nullable;
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
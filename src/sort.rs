@@ -7,8 +7,11 @@ in some supported store.
 
  */
 
-use crate::{error::Error, Name};
-use std::fmt::Display;
+use crate::{
+    error::{invalid_domain, Error},
+    Name,
+};
+use std::{fmt::Display, str::FromStr};
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -24,6 +27,8 @@ use std::fmt::Display;
 ///
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Domain {
+    /// The domain of the `null` value alone, such as a projected `null` constant.
+    Null,
     Boolean,
     Byte,
     UnsignedInteger,
@@ -32,6 +37,13 @@ pub enum Domain {
     Char,
     String,
     Binary,
+    Decimal,
+    Date,
+    Time,
+    Timestamp,
+    /// A timestamp with a time zone, recorded as an offset from UTC.
+    TimestampTz,
+    Interval,
 }
 
 ///
@@ -108,6 +120,26 @@ pub trait AttributeSchema {
     fn name(&self) -> &Name;
 
     fn domain(&self) -> &Domain;
+
+    ///
+    /// Returns `true` if this attribute may hold a null value in place of a value of its domain;
+    /// attributes are not nullable unless marked so.
+    ///
+    fn is_nullable(&self) -> bool {
+        false
+    }
+
+    ///
+    /// Returns this attribute, marked as nullable or not. The default, for an implementation that
+    /// does not support nullable attributes, returns this attribute unchanged.
+    ///
+    fn with_nullable(self, nullable: bool) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = nullable;
+        self
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -122,12 +154,29 @@ pub trait AttributeSchema {
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
+impl Domain {
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::Byte | Self::UnsignedInteger | Self::Integer | Self::Float | Self::Decimal
+        )
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            Self::Date | Self::Time | Self::Timestamp | Self::TimestampTz | Self::Interval
+        )
+    }
+}
+
 impl Display for Domain {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}",
             match self {
+                Self::Null => "null",
                 Self::Boolean => "boolean",
                 Self::Byte => "byte",
                 Self::UnsignedInteger => "unsigned",
@@ -136,11 +185,45 @@ impl Display for Domain {
                 Self::Char => "char",
                 Self::String => "string",
                 Self::Binary => "binary",
+                Self::Decimal => "decimal",
+                Self::Date => "date",
+                Self::Time => "time",
+                Self::Timestamp => "timestamp",
+                Self::TimestampTz => "timestamptz",
+                Self::Interval => "interval",
             }
         )
     }
 }
 
+impl FromStr for Domain {
+    type Err = Error;
+
+    ///
+    /// Parses the name of a domain, in the form written by its `Display` implementation.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "null" => Ok(Self::Null),
+            "boolean" => Ok(Self::Boolean),
+            "byte" => Ok(Self::Byte),
+            "unsigned" => Ok(Self::UnsignedInteger),
+            "integer" => Ok(Self::Integer),
+            "float" => Ok(Self::Float),
+            "char" => Ok(Self::Char),
+            "string" => Ok(Self::String),
+            "binary" => Ok(Self::Binary),
+            "decimal" => Ok(Self::Decimal),
+            "date" => Ok(Self::Date),
+            "time" => Ok(Self::Time),
+            "timestamp" => Ok(Self::Timestamp),
+            "timestamptz" => Ok(Self::TimestampTz),
+            "interval" => Ok(Self::Interval),
+            _ => Err(invalid_domain(s)),
+        }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,1701 @@
+/*!
+Provides a translation from relational algebra expressions to SQL `SELECT` statements.
+
+| Algebra                    | SQL                                                     |
+|----------------------------|---------------------------------------------------------|
+| relation $R$               | `SELECT * FROM R`                                       |
+| selection $\sigma$         | `WHERE`                                                 |
+| projection $\pi$           | `SELECT DISTINCT`, a constant `c` at index `i` is `c AS _i` |
+| scalar expressions         | `+`, `-`, `*`, `/`, `%`, `||`, functions, and `CASE`     |
+| `∈`, `between`, `like`     | `IN`, `BETWEEN … AND …`, `LIKE`, and `ILIKE`             |
+| rename $\rho$              | `AS` in the select list                                 |
+| order $\tau$               | `ORDER BY`, with `DESC`, `COLLATE`, and `NULLS FIRST`    |
+| group $\gamma$             | `GROUP BY`, with aggregates such as `COUNT(*)`           |
+| $\cup$, $\cap$, $\setminus$ | `UNION`, `INTERSECT`, `EXCEPT`                         |
+| $\triangle$                | `(R EXCEPT S) UNION (S EXCEPT R)`                       |
+| $\times$                   | `CROSS JOIN`                                            |
+| natural join               | `NATURAL JOIN`                                          |
+| theta join                 | `JOIN ... ON`                                           |
+| outer joins ⟕, ⟖, ⟗        | `LEFT`, `RIGHT`, or `FULL JOIN`, natural or `ON`        |
+| semi-joins ⋉, ⋊            | `WHERE EXISTS` with a correlated subquery               |
+| anti-join ▷                | `WHERE NOT EXISTS` with a correlated subquery           |
+| division ÷                 | `SELECT DISTINCT` with two nested `NOT EXISTS`           |
+
+Each named expression in an [`ExpressionList`] becomes a common table expression, and is included
+in the `WITH` clause of any later statement which references it.
+
+Attributes may be referenced by index in the algebra, but only by name in SQL, so expressions
+are analyzed against a schema, as in [`crate::eval::analyze_expression`], before translation.
+Where an operation cannot be expressed in the same `SELECT` as its operand, for example a
+selection over a union, the operand becomes a derived table with a generated alias of the form
+`t1`. String matching, `~` and `!~`, is translated to the POSIX regular expression operators
+used by PostgreSQL.
+
+The reverse translation, from a subset of SQL into the algebra, is provided by the [`parse`]
+module.
+
+# Example
+
+```rust
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use relational_algebra::sql::relational_to_sql;
+use relational_algebra::Name;
+use std::str::FromStr;
+
+let schema = SimpleSchema::new(
+    Name::new_unchecked("example"),
+    [SimpleRelationSchema::new(
+        Name::new_unchecked("people"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+            SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+        ],
+    )
+    .unwrap()],
+)
+.unwrap();
+
+let query = RelationalOp::from_str("π[name](σ[age≥18i64]people)").unwrap();
+assert_eq!(
+    relational_to_sql(&query, &schema).unwrap(),
+    "SELECT DISTINCT name FROM people WHERE age >= 18"
+);
+```
+
+ */
+
+use crate::{
+    ast::{
+        AggregateFunction, Attribute, ComparisonOperator, Expression, ExpressionList, Group, Join,
+        NullOrdering, Order, OuterJoinKind, ProjectedAttribute, Projection, RelationalOp, Rename,
+        ScalarExpression, ScalarFunction, ScalarOperator, Selection, SemiJoinKind, SetOperation,
+        SetOperator, Term,
+    },
+    data::Value,
+    error::Result,
+    eval::{attribute_to_index, Analyzer},
+    simple::{
+        ops::{common_attributes, constant_attribute_name, division_attributes, product_schema},
+        sort::SimpleRelationSchema,
+    },
+    sort::{AttributeSchema, RelationSchema, Schema},
+    Name,
+};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Translate an expression list into a list of SQL statements. Each unnamed expression, and the
+/// last expression in the list if it is named, becomes a statement; named expressions become
+/// common table expressions in the statements which reference them.
+///
+pub fn expression_list_to_sql<S>(list: &ExpressionList, schema: &S) -> Result<Vec<String>>
+where
+    S: Schema,
+{
+    let mut generator = Generator::new(schema);
+    let mut statements: Vec<String> = Default::default();
+    let mut expressions = list.as_ref().iter().peekable();
+    while let Some(expression) = expressions.next() {
+        match expression.name() {
+            Some(name) => {
+                generator.define(expression)?;
+                if expressions.peek().is_none() {
+                    statements.push(generator.statement(&RelationalOp::Relation(name.clone()))?);
+                }
+            }
+            None => statements.push(generator.statement(expression.expression())?),
+        }
+    }
+    Ok(statements)
+}
+
+///
+/// Translate a single expression into a SQL statement; the name of a named expression is not
+/// included in the statement.
+///
+pub fn expression_to_sql<S>(expression: &Expression, schema: &S) -> Result<String>
+where
+    S: Schema,
+{
+    relational_to_sql(expression.expression(), schema)
+}
+
+///
+/// Translate a single relational operation into a SQL statement.
+///
+pub fn relational_to_sql<S>(op: &RelationalOp, schema: &S) -> Result<String>
+where
+    S: Schema,
+{
+    Generator::new(schema).statement(op)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+struct Generator<'a, S>
+where
+    S: Schema,
+{
+    analyzer: Analyzer<'a, S>,
+    ctes: Vec<CommonTableExpression>,
+    next_alias: usize,
+}
+
+struct CommonTableExpression {
+    name: Name,
+    columns: Vec<String>,
+    query: String,
+    references: HashSet<Name>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Query {
+    Select(Box<Select>),
+    Compound(Box<Query>, &'static str, Box<Query>),
+}
+
+///
+/// A single `SELECT`; `columns` are the columns produced, in the order of the algebra, and
+/// `from_columns` are those produced by the `FROM` clause, in SQL order, which are selected by
+/// `*`.
+///
+#[derive(Clone, Debug, PartialEq)]
+struct Select {
+    distinct: bool,
+    columns: Vec<Column>,
+    source: Source,
+    from_columns: Vec<Column>,
+    criteria: Option<SqlExpression>,
+    grouped: bool,
+    group_by: Vec<SqlExpression>,
+    order_by: Vec<OrderTerm>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct OrderTerm {
+    expression: SqlExpression,
+    descending: bool,
+    collation: Option<Name>,
+    nulls: Option<NullOrdering>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Column {
+    expression: SqlExpression,
+    name: Name,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Source {
+    Table {
+        name: Name,
+        alias: Option<String>,
+    },
+    Derived {
+        query: Box<Query>,
+        columns: Vec<String>,
+        alias: String,
+    },
+    Join {
+        lhs: Box<Source>,
+        kind: JoinKind,
+        rhs: Box<Source>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum JoinKind {
+    Cross,
+    Natural(Option<OuterJoinKind>),
+    On(Option<OuterJoinKind>, SqlExpression),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SqlExpression {
+    Column {
+        qualifier: String,
+        name: String,
+    },
+    Literal(String),
+    Comparison(Box<SqlExpression>, &'static str, Box<SqlExpression>),
+    InList(Box<SqlExpression>, Vec<SqlExpression>),
+    Between(Box<SqlExpression>, Box<SqlExpression>, Box<SqlExpression>),
+    Arithmetic(Box<SqlExpression>, &'static str, Box<SqlExpression>),
+    Function(&'static str, Vec<SqlExpression>),
+    Case(
+        Vec<(SqlExpression, SqlExpression)>,
+        Option<Box<SqlExpression>>,
+    ),
+    Aggregate {
+        function: &'static str,
+        distinct: bool,
+        argument: Option<Box<SqlExpression>>,
+    },
+    Coalesce(Box<SqlExpression>, Box<SqlExpression>),
+    IsNull(Box<SqlExpression>),
+    IsNotNull(Box<SqlExpression>),
+    Exists {
+        negated: bool,
+        subquery: Box<Select>,
+    },
+    Not(Box<SqlExpression>),
+    And(Box<SqlExpression>, Box<SqlExpression>),
+    Or(Box<SqlExpression>, Box<SqlExpression>),
+}
+
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CHECK",
+    "CREATE",
+    "CROSS",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "TABLE",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, S> Generator<'a, S>
+where
+    S: Schema,
+{
+    fn new(schema: &'a S) -> Self {
+        Self {
+            analyzer: Analyzer::new(schema),
+            ctes: Default::default(),
+            next_alias: 0,
+        }
+    }
+
+    fn define(&mut self, expression: &Expression) -> Result<()> {
+        let name = expression.name().unwrap().clone();
+        let schema = self.analyzer.expression(expression)?;
+        let query = self.relational(expression.expression())?;
+        let columns = unique_names(&query.column_names());
+        let mut references: HashSet<Name> = Default::default();
+        relation_names(expression.expression(), &mut references);
+        self.ctes.push(CommonTableExpression {
+            name: name.clone(),
+            query: query.render(&columns),
+            columns,
+            references,
+        });
+        let _ = self.analyzer.views.insert(name, schema);
+        Ok(())
+    }
+
+    fn statement(&mut self, op: &RelationalOp) -> Result<String> {
+        let _ = self.analyzer.relational(op)?;
+        let query = self.relational(op)?;
+        let columns: Vec<String> = query
+            .column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let body = query.render(&columns);
+
+        // a common table expression may only reference those defined before it.
+        let mut references: HashSet<Name> = Default::default();
+        relation_names(op, &mut references);
+        let mut included = vec![false; self.ctes.len()];
+        for (i, cte) in self.ctes.iter().enumerate().rev() {
+            if references.contains(&cte.name) {
+                included[i] = true;
+                references.extend(cte.references.iter().cloned());
+            }
+        }
+        let ctes: Vec<String> = self
+            .ctes
+            .iter()
+            .zip(included)
+            .filter(|(_, included)| *included)
+            .map(|(cte, _)| format!("{} AS ({})", identifier(cte.name.as_ref()), cte.query))
+            .collect();
+        Ok(if ctes.is_empty() {
+            body
+        } else {
+            format!("WITH {} {}", ctes.join(", "), body)
+        })
+    }
+
+    fn relational(&mut self, op: &RelationalOp) -> Result<Query> {
+        Ok(match op {
+            RelationalOp::Relation(v) => self.relation(v)?.into(),
+            RelationalOp::SetOperation(v) => self.set_operation(v)?,
+            RelationalOp::Selection(v) => self.selection(v)?.into(),
+            RelationalOp::Projection(v) => self.projection(v)?.into(),
+            RelationalOp::Rename(v) => self.rename(v)?.into(),
+            RelationalOp::Order(v) => self.order(v)?.into(),
+            RelationalOp::Group(v) => self.group(v)?.into(),
+            RelationalOp::Join(Join::Natural(v)) => {
+                self.natural_join(v.lhs(), v.rhs(), None)?.into()
+            }
+            RelationalOp::Join(Join::Theta(v)) => self
+                .theta_join(op, v.lhs(), v.criteria(), v.rhs(), None)?
+                .into(),
+            RelationalOp::Join(Join::Outer(v)) => match v.criteria() {
+                None => self.natural_join(v.lhs(), v.rhs(), Some(v.kind()))?.into(),
+                Some(criteria) => self
+                    .theta_join(op, v.lhs(), criteria, v.rhs(), Some(v.kind()))?
+                    .into(),
+            },
+            RelationalOp::SemiJoin(v) => self
+                .semi_join(
+                    v.lhs(),
+                    v.criteria(),
+                    v.rhs(),
+                    v.kind() == SemiJoinKind::Left,
+                    false,
+                )?
+                .into(),
+            RelationalOp::AntiJoin(v) => self
+                .semi_join(v.lhs(), v.criteria(), v.rhs(), true, true)?
+                .into(),
+            RelationalOp::Division(v) => self.division(v.lhs(), v.rhs())?.into(),
+        })
+    }
+
+    fn select(&mut self, op: &RelationalOp) -> Result<Select> {
+        Ok(match self.relational(op)? {
+            Query::Select(select) => *select,
+            compound => self.wrap(compound),
+        })
+    }
+
+    fn relation(&mut self, name: &Name) -> Result<Select> {
+        let schema = self
+            .analyzer
+            .relational(&RelationalOp::Relation(name.clone()))?;
+        let sql_names: Vec<String> = match self.ctes.iter().rev().find(|cte| &cte.name == name) {
+            Some(cte) => cte.columns.clone(),
+            None => schema
+                .attributes()
+                .map(|attribute| attribute.name().to_string())
+                .collect(),
+        };
+        let columns = schema
+            .attributes()
+            .zip(sql_names)
+            .map(|(attribute, sql_name)| Column {
+                expression: SqlExpression::column(name.as_ref(), &sql_name),
+                name: attribute.name().clone(),
+            })
+            .collect();
+        Ok(Select::new(
+            Source::Table {
+                name: name.clone(),
+                alias: None,
+            },
+            columns,
+        ))
+    }
+
+    fn set_operation(&mut self, op: &SetOperation) -> Result<Query> {
+        let keyword = match op.operator() {
+            SetOperator::CartesianProduct => {
+                let (lhs, rhs) = self.join_operands(op.lhs(), op.rhs(), false, false)?;
+                let columns = lhs.columns.iter().chain(&rhs.columns).cloned().collect();
+                let from_columns = lhs
+                    .from_columns
+                    .iter()
+                    .chain(&rhs.from_columns)
+                    .cloned()
+                    .collect();
+                return Ok(Select::join(lhs, JoinKind::Cross, rhs, columns, from_columns).into());
+            }
+            SetOperator::Union => "UNION",
+            SetOperator::Intersection => "INTERSECT",
+            SetOperator::Difference | SetOperator::SymmetricDifference => "EXCEPT",
+        };
+        let lhs = self.set_operand(op.lhs())?;
+        let rhs = self.set_operand(op.rhs())?;
+        Ok(if op.is_symmetric_difference() {
+            Query::compound(
+                Query::compound(lhs.clone(), keyword, rhs.clone()),
+                "UNION",
+                Query::compound(rhs, keyword, lhs),
+            )
+        } else {
+            Query::compound(lhs, keyword, rhs)
+        })
+    }
+
+    fn set_operand(&mut self, op: &RelationalOp) -> Result<Query> {
+        let mut query = self.relational(op)?;
+        // the order of an operand is not preserved by a set operation.
+        if let Query::Select(select) = &mut query {
+            select.order_by.clear();
+        }
+        Ok(query)
+    }
+
+    fn selection(&mut self, op: &Selection) -> Result<Select> {
+        let schema = self.analyzer.relational(op.rhs())?;
+        let mut select = self.select(op.rhs())?;
+        // a criteria over a group would otherwise apply to the ungrouped rows.
+        if select.grouped {
+            select = self.wrap(select.into());
+        }
+        let criteria = term_to_sql(op.criteria(), &schema, &select.columns)?;
+        select.add_criteria(criteria);
+        Ok(select)
+    }
+
+    fn projection(&mut self, op: &Projection) -> Result<Select> {
+        let schema = self.analyzer.relational(op.rhs())?;
+        let mut select = self.select(op.rhs())?;
+        let mut columns = projected_columns(op, &schema, &select.columns)?;
+        // with DISTINCT, any ORDER BY expression must also be selected.
+        if !select
+            .order_by
+            .iter()
+            .all(|term| columns.iter().any(|c| c.expression == term.expression))
+        {
+            select = self.wrap(select.into());
+            columns = projected_columns(op, &schema, &select.columns)?;
+        }
+        select.columns = columns;
+        select.distinct = true;
+        Ok(select)
+    }
+
+    fn rename(&mut self, op: &Rename) -> Result<Select> {
+        let schema = self.analyzer.relational(op.rhs())?;
+        let mut select = self.select(op.rhs())?;
+        for (attribute, name) in op.renames() {
+            let index = attribute_to_index(attribute, &schema)?;
+            select.columns[index].name = name.clone();
+        }
+        Ok(select)
+    }
+
+    fn order(&mut self, op: &Order) -> Result<Select> {
+        let schema = self.analyzer.relational(op.rhs())?;
+        let mut select = self.select(op.rhs())?;
+        let mut order_by: Vec<OrderTerm> = Default::default();
+        for key in op.keys() {
+            let expression =
+                &select.columns[attribute_to_index(key.attribute(), &schema)?].expression;
+            if !expression.is_literal() {
+                order_by.push(OrderTerm {
+                    expression: expression.clone(),
+                    descending: key.is_descending(),
+                    collation: key.collation().cloned(),
+                    nulls: key.nulls(),
+                });
+            }
+        }
+        // the order of the operand remains for any rows equal in this order.
+        order_by.append(&mut select.order_by);
+        select.order_by = order_by;
+        Ok(select)
+    }
+
+    fn group(&mut self, op: &Group) -> Result<Select> {
+        let schema = self.analyzer.relational(op.rhs())?;
+        let result = self.analyzer.relational(&RelationalOp::Group(op.clone()))?;
+        let mut select = self.select(op.rhs())?;
+        if !select.order_by.is_empty() || select.grouped {
+            select = self.wrap(select.into());
+        }
+        let mut columns = op
+            .attributes()
+            .map(|attribute| {
+                attribute_to_index(attribute, &schema).map(|index| select.columns[index].clone())
+            })
+            .collect::<Result<Vec<Column>>>()?;
+        for (aggregate, attribute) in op.aggregates().zip(result.attributes().skip(columns.len())) {
+            let argument = match aggregate.argument() {
+                None => None,
+                Some(argument) => Some(Box::new(
+                    select.columns[attribute_to_index(argument, &schema)?]
+                        .expression
+                        .clone(),
+                )),
+            };
+            columns.push(Column {
+                expression: SqlExpression::Aggregate {
+                    function: aggregate_function(aggregate.function()),
+                    distinct: aggregate.is_distinct(),
+                    argument,
+                },
+                name: attribute.name().clone(),
+            });
+        }
+        select.group_by = column_expressions(op.attributes(), &schema, &select.columns)?;
+        select.columns = columns;
+        select.distinct = false;
+        select.grouped = true;
+        Ok(select)
+    }
+
+    fn natural_join(
+        &mut self,
+        lhs: &RelationalOp,
+        rhs: &RelationalOp,
+        outer: Option<OuterJoinKind>,
+    ) -> Result<Select> {
+        let lhs_schema = self.analyzer.relational(lhs)?;
+        let rhs_schema = self.analyzer.relational(rhs)?;
+        let common = common_attributes(&lhs_schema, &rhs_schema)?;
+        let common_names: HashSet<&Name> = common
+            .iter()
+            .map(|(i, _)| lhs_schema.attribute(*i).unwrap().name())
+            .collect();
+
+        let (mut lhs, rhs) = self.join_operands(lhs, rhs, true, outer.is_some())?;
+        // the value of a common column is taken from whichever side is retained.
+        for (l, r) in &common {
+            let (lhs_column, rhs_column) = (&lhs.columns[*l], &rhs.columns[*r]);
+            let expression = match outer {
+                None | Some(OuterJoinKind::Left) => continue,
+                Some(OuterJoinKind::Right) => rhs_column.expression.clone(),
+                Some(OuterJoinKind::Full) => SqlExpression::Coalesce(
+                    Box::new(lhs_column.expression.clone()),
+                    Box::new(rhs_column.expression.clone()),
+                ),
+            };
+            let column = Column {
+                expression,
+                name: lhs_column.name.clone(),
+            };
+            if let Some(from_column) = lhs.from_columns.iter_mut().find(|c| c.name == column.name) {
+                *from_column = column.clone();
+            }
+            lhs.columns[*l] = column;
+        }
+        let columns = lhs
+            .columns
+            .iter()
+            .cloned()
+            .chain(
+                rhs.columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !common.iter().any(|(_, r)| r == i))
+                    .map(|(_, column)| column.clone()),
+            )
+            .collect();
+        // SQL places the common columns first, then those remaining from each side.
+        let from_columns = lhs
+            .from_columns
+            .iter()
+            .filter(|c| common_names.contains(&c.name))
+            .chain(
+                lhs.from_columns
+                    .iter()
+                    .filter(|c| !common_names.contains(&c.name)),
+            )
+            .chain(
+                rhs.from_columns
+                    .iter()
+                    .filter(|c| !common_names.contains(&c.name)),
+            )
+            .cloned()
+            .collect();
+        Ok(Select::join(
+            lhs,
+            JoinKind::Natural(outer),
+            rhs,
+            columns,
+            from_columns,
+        ))
+    }
+
+    fn theta_join(
+        &mut self,
+        op: &RelationalOp,
+        lhs: &RelationalOp,
+        criteria: &Term,
+        rhs: &RelationalOp,
+        outer: Option<OuterJoinKind>,
+    ) -> Result<Select> {
+        let schema = self.analyzer.relational(op)?;
+        let (lhs, rhs) = self.join_operands(lhs, rhs, false, outer.is_some())?;
+        let columns: Vec<Column> = lhs.columns.iter().chain(&rhs.columns).cloned().collect();
+        let from_columns = lhs
+            .from_columns
+            .iter()
+            .chain(&rhs.from_columns)
+            .cloned()
+            .collect();
+        let criteria = term_to_sql(criteria, &schema, &columns)?;
+        Ok(Select::join(
+            lhs,
+            JoinKind::On(outer, criteria),
+            rhs,
+            columns,
+            from_columns,
+        ))
+    }
+
+    ///
+    /// Returns the two sides of a join, each as a `SELECT` whose `FROM` clause may be joined
+    /// directly; any qualifier used on both sides is replaced with an alias on the right.
+    ///
+    fn join_operands(
+        &mut self,
+        lhs: &RelationalOp,
+        rhs: &RelationalOp,
+        natural: bool,
+        outer: bool,
+    ) -> Result<(Select, Select)> {
+        let mut lhs = self.select(lhs)?;
+        if !lhs.is_joinable(natural, outer) {
+            lhs = self.wrap(lhs.into());
+        }
+        let mut rhs = self.select(rhs)?;
+        if !rhs.is_joinable(natural, outer) {
+            rhs = self.wrap(rhs.into());
+        }
+        self.disambiguate(lhs.source.qualifiers(), &mut rhs);
+        Ok((lhs, rhs))
+    }
+
+    ///
+    /// Translate a semi-join, or an anti-join if `negated`, into a `SELECT` of the retained side,
+    /// the left if `retain_lhs`, with a `WHERE [NOT] EXISTS` over the other side correlated
+    /// either on the common columns or by the criteria.
+    ///
+    fn semi_join(
+        &mut self,
+        lhs: &RelationalOp,
+        criteria: Option<&Term>,
+        rhs: &RelationalOp,
+        retain_lhs: bool,
+        negated: bool,
+    ) -> Result<Select> {
+        let lhs_schema = self.analyzer.relational(lhs)?;
+        let rhs_schema = self.analyzer.relational(rhs)?;
+        let (outer, inner) = if retain_lhs { (lhs, rhs) } else { (rhs, lhs) };
+        let mut outer = self.select(outer)?;
+        // a criteria over a group would otherwise apply to the ungrouped rows.
+        if outer.grouped {
+            outer = self.wrap(outer.into());
+        }
+        let mut inner = self.select(inner)?;
+        if inner.grouped {
+            inner = self.wrap(inner.into());
+        }
+        inner.order_by.clear();
+        self.disambiguate(outer.source.qualifiers(), &mut inner);
+
+        let (lhs, rhs) = if retain_lhs {
+            (&outer, &inner)
+        } else {
+            (&inner, &outer)
+        };
+        let correlation = match criteria {
+            None => common_attributes(&lhs_schema, &rhs_schema)?
+                .into_iter()
+                .map(|(l, r)| {
+                    SqlExpression::Comparison(
+                        Box::new(rhs.columns[r].expression.clone()),
+                        "=",
+                        Box::new(lhs.columns[l].expression.clone()),
+                    )
+                })
+                .reduce(|lhs, rhs| SqlExpression::And(Box::new(lhs), Box::new(rhs))),
+            Some(criteria) => {
+                let columns: Vec<Column> =
+                    lhs.columns.iter().chain(&rhs.columns).cloned().collect();
+                Some(term_to_sql(
+                    criteria,
+                    &product_schema(&lhs_schema, &rhs_schema)?,
+                    &columns,
+                )?)
+            }
+        };
+        if let Some(correlation) = correlation {
+            inner.add_criteria(correlation);
+        }
+        outer.add_criteria(inner.into_exists(negated));
+        Ok(outer)
+    }
+
+    ///
+    /// Translate a division into a `SELECT DISTINCT` of the quotient columns of the dividend for
+    /// which there is no row of the divisor without a matching row of the dividend.
+    ///
+    fn division(&mut self, lhs: &RelationalOp, rhs: &RelationalOp) -> Result<Select> {
+        let (quotient, divisor_indices) = division_attributes(
+            &self.analyzer.relational(lhs)?,
+            &self.analyzer.relational(rhs)?,
+        )?;
+        let mut outer = self.select(lhs)?;
+        if !outer.is_joinable(false, false) {
+            outer = self.wrap(outer.into());
+        }
+        let mut divisor = self.select(rhs)?;
+        if divisor.grouped {
+            divisor = self.wrap(divisor.into());
+        }
+        divisor.order_by.clear();
+        self.disambiguate(outer.source.qualifiers(), &mut divisor);
+        let mut dividend = self.select(lhs)?;
+        if dividend.grouped {
+            dividend = self.wrap(dividend.into());
+        }
+        dividend.order_by.clear();
+        let mut used = outer.source.qualifiers();
+        used.extend(divisor.source.qualifiers());
+        self.disambiguate(used, &mut dividend);
+
+        let correlation = quotient
+            .iter()
+            .map(|i| (&dividend.columns[*i], &outer.columns[*i]))
+            .chain(
+                divisor_indices
+                    .iter()
+                    .zip(&divisor.columns)
+                    .map(|(i, column)| (&dividend.columns[*i], column)),
+            )
+            .map(|(lhs, rhs)| {
+                SqlExpression::Comparison(
+                    Box::new(lhs.expression.clone()),
+                    "=",
+                    Box::new(rhs.expression.clone()),
+                )
+            })
+            .reduce(|lhs, rhs| SqlExpression::And(Box::new(lhs), Box::new(rhs)))
+            .unwrap();
+        dividend.add_criteria(correlation);
+        divisor.add_criteria(dividend.into_exists(true));
+        outer.add_criteria(divisor.into_exists(true));
+        outer.columns = quotient.iter().map(|i| outer.columns[*i].clone()).collect();
+        outer.distinct = true;
+        Ok(outer)
+    }
+
+    ///
+    /// Replace any qualifier of `rhs` that is already `used` with a new alias.
+    ///
+    fn disambiguate(&mut self, mut used: Vec<String>, rhs: &mut Select) {
+        for qualifier in rhs.source.qualifiers() {
+            if used.contains(&qualifier) {
+                let alias = loop {
+                    let alias = self.alias();
+                    if !used.contains(&alias) && !rhs.source.qualifiers().contains(&alias) {
+                        break alias;
+                    }
+                };
+                rhs.requalify(&qualifier, &alias);
+                used.push(alias);
+            } else {
+                used.push(qualifier);
+            }
+        }
+    }
+
+    ///
+    /// Wrap `query` as a derived table, returning a `SELECT` of all its columns.
+    ///
+    fn wrap(&mut self, query: Query) -> Select {
+        let names = query.column_names();
+        let sql_names = unique_names(&names);
+        let alias = self.alias();
+        let columns = names
+            .into_iter()
+            .zip(&sql_names)
+            .map(|(name, sql_name)| Column {
+                expression: SqlExpression::column(&alias, sql_name),
+                name,
+            })
+            .collect();
+        Select::new(
+            Source::Derived {
+                query: Box::new(query),
+                columns: sql_names,
+                alias,
+            },
+            columns,
+        )
+    }
+
+    fn alias(&mut self) -> String {
+        self.next_alias += 1;
+        format!("t{}", self.next_alias)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<Select> for Query {
+    fn from(v: Select) -> Self {
+        Self::Select(Box::new(v))
+    }
+}
+
+impl Query {
+    fn compound(lhs: Query, keyword: &'static str, rhs: Query) -> Self {
+        Self::Compound(Box::new(lhs), keyword, Box::new(rhs))
+    }
+
+    fn column_names(&self) -> Vec<Name> {
+        match self {
+            Self::Select(select) => select.columns.iter().map(|c| c.name.clone()).collect(),
+            Self::Compound(lhs, _, _) => lhs.column_names(),
+        }
+    }
+
+    fn render(&self, names: &[String]) -> String {
+        match self {
+            Self::Select(select) => select.render(names),
+            Self::Compound(lhs, keyword, rhs) => {
+                let rhs_names: Vec<String> =
+                    rhs.column_names().iter().map(|n| n.to_string()).collect();
+                format!(
+                    "{} {} {}",
+                    lhs.render_operand(names),
+                    keyword,
+                    rhs.render_operand(&rhs_names)
+                )
+            }
+        }
+    }
+
+    fn render_operand(&self, names: &[String]) -> String {
+        match self {
+            Self::Select(_) => self.render(names),
+            Self::Compound(_, _, _) => format!("({})", self.render(names)),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Select {
+    fn new(source: Source, columns: Vec<Column>) -> Self {
+        Self {
+            distinct: false,
+            from_columns: columns.clone(),
+            columns,
+            source,
+            criteria: None,
+            grouped: false,
+            group_by: Default::default(),
+            order_by: Default::default(),
+        }
+    }
+
+    fn join(
+        lhs: Select,
+        kind: JoinKind,
+        rhs: Select,
+        columns: Vec<Column>,
+        from_columns: Vec<Column>,
+    ) -> Self {
+        let mut select = Self::new(
+            Source::Join {
+                lhs: Box::new(lhs.source),
+                kind,
+                rhs: Box::new(rhs.source),
+            },
+            columns,
+        );
+        select.from_columns = from_columns;
+        select.criteria = lhs.criteria;
+        if let Some(criteria) = rhs.criteria {
+            select.add_criteria(criteria);
+        }
+        select
+    }
+
+    ///
+    /// Returns `[NOT] EXISTS` over this `SELECT`, which now selects `*` without `DISTINCT` as
+    /// neither the columns nor `DISTINCT` change the result.
+    ///
+    fn into_exists(mut self, negated: bool) -> SqlExpression {
+        self.columns = self.from_columns.clone();
+        self.distinct = false;
+        SqlExpression::Exists {
+            negated,
+            subquery: Box::new(self),
+        }
+    }
+
+    fn add_criteria(&mut self, criteria: SqlExpression) {
+        self.criteria = Some(match self.criteria.take() {
+            Some(existing) => SqlExpression::And(Box::new(existing), Box::new(criteria)),
+            None => criteria,
+        });
+    }
+
+    ///
+    /// Returns `true` if the `FROM` clause of this `SELECT` may be joined with another without
+    /// changing its meaning; a natural join also requires that every column is selected under
+    /// its own name, and an outer join that there is no `WHERE` clause.
+    ///
+    fn is_joinable(&self, natural: bool, outer: bool) -> bool {
+        !self.distinct
+            && !self.grouped
+            && self.order_by.is_empty()
+            && (!outer || self.criteria.is_none())
+            && (!natural
+                || (self.columns.len() == self.from_columns.len()
+                    && self.columns.iter().all(|column| {
+                        self.from_columns.contains(column)
+                            && column.has_sql_name(column.name.as_ref())
+                    })))
+    }
+
+    fn requalify(&mut self, from: &str, to: &str) {
+        self.source.requalify(from, to);
+        for expression in self
+            .columns
+            .iter_mut()
+            .chain(self.from_columns.iter_mut())
+            .map(|c| &mut c.expression)
+            .chain(self.criteria.iter_mut())
+            .chain(self.group_by.iter_mut())
+            .chain(self.order_by.iter_mut().map(|term| &mut term.expression))
+        {
+            expression.requalify(from, to);
+        }
+    }
+
+    fn render(&self, names: &[String]) -> String {
+        self.render_qualified(names, matches!(self.source, Source::Join { .. }))
+    }
+
+    ///
+    /// Render this `SELECT`, qualifying every column if `qualify`, as is required within a
+    /// subquery that references the columns of the enclosing query.
+    ///
+    fn render_qualified(&self, names: &[String], qualify: bool) -> String {
+        let is_star = self.columns == self.from_columns
+            && self
+                .columns
+                .iter()
+                .zip(names)
+                .all(|(column, name)| column.has_sql_name(name));
+        let columns = if is_star {
+            "*".to_string()
+        } else {
+            self.columns
+                .iter()
+                .zip(names)
+                .map(|(column, name)| {
+                    let expression = column.expression.render(qualify);
+                    if column.has_sql_name(name) {
+                        expression
+                    } else {
+                        format!("{} AS {}", expression, identifier(name))
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        let mut sql = format!(
+            "SELECT {}{} FROM {}",
+            if self.distinct { "DISTINCT " } else { "" },
+            columns,
+            self.source.render()
+        );
+        if let Some(criteria) = &self.criteria {
+            sql.push_str(&format!(" WHERE {}", criteria.render(qualify)));
+        }
+        if !self.group_by.is_empty() {
+            sql.push_str(&format!(
+                " GROUP BY {}",
+                render_list(&self.group_by, qualify)
+            ));
+        }
+        if !self.order_by.is_empty() {
+            sql.push_str(&format!(
+                " ORDER BY {}",
+                self.order_by
+                    .iter()
+                    .map(|term| term.render(qualify))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        sql
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl OrderTerm {
+    fn render(&self, qualify: bool) -> String {
+        let mut sql = self.expression.render(qualify);
+        if let Some(collation) = &self.collation {
+            sql.push_str(&format!(" COLLATE {}", identifier(collation.as_ref())));
+        }
+        if self.descending {
+            sql.push_str(" DESC");
+        }
+        match self.nulls {
+            Some(NullOrdering::First) => sql.push_str(" NULLS FIRST"),
+            Some(NullOrdering::Last) => sql.push_str(" NULLS LAST"),
+            None => {}
+        }
+        sql
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Column {
+    fn has_sql_name(&self, name: &str) -> bool {
+        matches!(&self.expression, SqlExpression::Column { name: sql_name, .. } if sql_name == name)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Source {
+    fn qualifiers(&self) -> Vec<String> {
+        match self {
+            Self::Table { name, alias } => vec![alias.clone().unwrap_or_else(|| name.to_string())],
+            Self::Derived { alias, .. } => vec![alias.clone()],
+            Self::Join { lhs, rhs, .. } => {
+                let mut qualifiers = lhs.qualifiers();
+                qualifiers.extend(rhs.qualifiers());
+                qualifiers
+            }
+        }
+    }
+
+    fn requalify(&mut self, from: &str, to: &str) {
+        match self {
+            Self::Table { name, alias } => {
+                if alias.as_deref().unwrap_or(name.as_ref()) == from {
+                    *alias = Some(to.to_string());
+                }
+            }
+            Self::Derived { alias, .. } => {
+                if alias == from {
+                    *alias = to.to_string();
+                }
+            }
+            Self::Join { lhs, kind, rhs } => {
+                lhs.requalify(from, to);
+                if let JoinKind::On(_, criteria) = kind {
+                    criteria.requalify(from, to);
+                }
+                rhs.requalify(from, to);
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Self::Table { name, alias: None } => identifier(name.as_ref()),
+            Self::Table {
+                name,
+                alias: Some(alias),
+            } => format!("{} AS {}", identifier(name.as_ref()), identifier(alias)),
+            Self::Derived {
+                query,
+                columns,
+                alias,
+            } => format!("({}) AS {}", query.render(columns), identifier(alias)),
+            Self::Join { lhs, kind, rhs } => {
+                let rhs = match rhs.as_ref() {
+                    Self::Join { .. } => format!("({})", rhs.render()),
+                    _ => rhs.render(),
+                };
+                match kind {
+                    JoinKind::Cross => format!("{} CROSS JOIN {}", lhs.render(), rhs),
+                    JoinKind::Natural(outer) => format!(
+                        "{} NATURAL {}JOIN {}",
+                        lhs.render(),
+                        outer_join_keyword(*outer),
+                        rhs
+                    ),
+                    JoinKind::On(outer, criteria) => format!(
+                        "{} {}JOIN {} ON {}",
+                        lhs.render(),
+                        outer_join_keyword(*outer),
+                        rhs,
+                        criteria.render(true)
+                    ),
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SqlExpression {
+    fn column(qualifier: &str, name: &str) -> Self {
+        Self::Column {
+            qualifier: qualifier.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal(_))
+    }
+
+    fn requalify(&mut self, from: &str, to: &str) {
+        match self {
+            Self::Column { qualifier, .. } => {
+                if qualifier == from {
+                    *qualifier = to.to_string();
+                }
+            }
+            Self::Literal(_) | Self::Aggregate { argument: None, .. } => {}
+            Self::Aggregate {
+                argument: Some(v), ..
+            } => v.requalify(from, to),
+            Self::Function(_, arguments) => {
+                for argument in arguments {
+                    argument.requalify(from, to);
+                }
+            }
+            Self::InList(v, list) => {
+                v.requalify(from, to);
+                for item in list {
+                    item.requalify(from, to);
+                }
+            }
+            Self::Between(v, low, high) => {
+                v.requalify(from, to);
+                low.requalify(from, to);
+                high.requalify(from, to);
+            }
+            Self::Case(branches, otherwise) => {
+                for (condition, value) in branches {
+                    condition.requalify(from, to);
+                    value.requalify(from, to);
+                }
+                if let Some(otherwise) = otherwise {
+                    otherwise.requalify(from, to);
+                }
+            }
+            Self::Comparison(lhs, _, rhs)
+            | Self::Arithmetic(lhs, _, rhs)
+            | Self::Coalesce(lhs, rhs)
+            | Self::And(lhs, rhs)
+            | Self::Or(lhs, rhs) => {
+                lhs.requalify(from, to);
+                rhs.requalify(from, to);
+            }
+            Self::IsNull(v) | Self::IsNotNull(v) | Self::Not(v) => v.requalify(from, to),
+            Self::Exists { subquery, .. } => subquery.requalify(from, to),
+        }
+    }
+
+    fn render(&self, qualify: bool) -> String {
+        match self {
+            Self::Column { qualifier, name } if qualify => {
+                format!("{}.{}", identifier(qualifier), identifier(name))
+            }
+            Self::Column { name, .. } => identifier(name),
+            Self::Literal(v) => v.clone(),
+            Self::Comparison(lhs, operator, rhs) => format!(
+                "{} {} {}",
+                lhs.render(qualify),
+                operator,
+                rhs.render(qualify)
+            ),
+            Self::Arithmetic(lhs, operator, rhs) => format!(
+                "{} {} {}",
+                lhs.render_operand(operator, false, qualify),
+                operator,
+                rhs.render_operand(operator, true, qualify)
+            ),
+            Self::Function(function, arguments) => format!(
+                "{}({})",
+                function,
+                arguments
+                    .iter()
+                    .map(|argument| argument.render(qualify))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::InList(v, list) => format!(
+                "{} IN ({})",
+                v.render(qualify),
+                list.iter()
+                    .map(|item| item.render(qualify))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Between(v, low, high) => format!(
+                "{} BETWEEN {} AND {}",
+                v.render(qualify),
+                low.render(qualify),
+                high.render(qualify)
+            ),
+            Self::Case(branches, otherwise) => {
+                let mut result = String::from("CASE");
+                for (condition, value) in branches {
+                    result.push_str(&format!(
+                        " WHEN {} THEN {}",
+                        condition.render(qualify),
+                        value.render(qualify)
+                    ));
+                }
+                if let Some(otherwise) = otherwise {
+                    result.push_str(&format!(" ELSE {}", otherwise.render(qualify)));
+                }
+                result.push_str(" END");
+                result
+            }
+            Self::Aggregate {
+                function,
+                distinct,
+                argument,
+            } => format!(
+                "{}({}{})",
+                function,
+                if *distinct { "DISTINCT " } else { "" },
+                match argument {
+                    None => "*".to_string(),
+                    Some(argument) => argument.render(qualify),
+                }
+            ),
+            Self::Coalesce(lhs, rhs) => {
+                format!("COALESCE({}, {})", lhs.render(qualify), rhs.render(qualify))
+            }
+            Self::IsNull(v) => format!("{} IS NULL", v.render(qualify)),
+            Self::IsNotNull(v) => format!("{} IS NOT NULL", v.render(qualify)),
+            Self::Exists { negated, subquery } => {
+                let names: Vec<Name> = subquery
+                    .columns
+                    .iter()
+                    .map(|column| column.name.clone())
+                    .collect();
+                format!(
+                    "{}EXISTS ({})",
+                    if *negated { "NOT " } else { "" },
+                    subquery.render_qualified(&unique_names(&names), true)
+                )
+            }
+            Self::Not(v) => format!("NOT ({})", v.render(qualify)),
+            Self::And(lhs, rhs) => format!(
+                "{} AND {}",
+                lhs.render_conjunct(qualify),
+                rhs.render_conjunct(qualify)
+            ),
+            Self::Or(lhs, rhs) => format!("{} OR {}", lhs.render(qualify), rhs.render(qualify)),
+        }
+    }
+
+    ///
+    /// Render an operand of the arithmetic `operator`, in parentheses if it is itself an
+    /// arithmetic operation that would otherwise bind differently; as the precedence of `||`
+    /// varies between databases it is always parenthesized when mixed with other operators.
+    ///
+    fn render_operand(&self, operator: &str, rhs: bool, qualify: bool) -> String {
+        let precedence = |operator: &str| match operator {
+            "*" | "/" | "%" => 2,
+            _ => 1,
+        };
+        match self {
+            Self::Arithmetic(_, inner, _)
+                if (*inner == "||") != (operator == "||")
+                    || precedence(inner) < precedence(operator)
+                    || (rhs && precedence(inner) == precedence(operator)) =>
+            {
+                format!("({})", self.render(qualify))
+            }
+            _ => self.render(qualify),
+        }
+    }
+
+    fn render_conjunct(&self, qualify: bool) -> String {
+        match self {
+            Self::Or(_, _) => format!("({})", self.render(qualify)),
+            _ => self.render(qualify),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn term_to_sql(
+    term: &Term,
+    schema: &SimpleRelationSchema,
+    columns: &[Column],
+) -> Result<SqlExpression> {
+    let column = |attribute: &Attribute| -> Result<SqlExpression> {
+        attribute_to_index(attribute, schema).map(|index| columns[index].expression.clone())
+    };
+    Ok(match term {
+        Term::Constant(v) => SqlExpression::Literal(value_to_sql(v)),
+        Term::Exists(v) | Term::IsNotNull(v) => SqlExpression::IsNotNull(Box::new(column(v)?)),
+        Term::IsNull(v) => SqlExpression::IsNull(Box::new(column(v)?)),
+        Term::Atom(atom) => SqlExpression::Comparison(
+            Box::new(scalar_to_sql(atom.lhs(), schema, columns)?),
+            comparison_operator(atom.operator()),
+            Box::new(scalar_to_sql(atom.rhs(), schema, columns)?),
+        ),
+        Term::In(v, list) => SqlExpression::InList(
+            Box::new(scalar_to_sql(v, schema, columns)?),
+            list.iter()
+                .map(|item| scalar_to_sql(item, schema, columns))
+                .collect::<Result<Vec<SqlExpression>>>()?,
+        ),
+        Term::Between(v, low, high) => SqlExpression::Between(
+            Box::new(scalar_to_sql(v, schema, columns)?),
+            Box::new(scalar_to_sql(low, schema, columns)?),
+            Box::new(scalar_to_sql(high, schema, columns)?),
+        ),
+        Term::Negate(v) => SqlExpression::Not(Box::new(term_to_sql(v, schema, columns)?)),
+        Term::And(lhs, rhs) => SqlExpression::And(
+            Box::new(term_to_sql(lhs, schema, columns)?),
+            Box::new(term_to_sql(rhs, schema, columns)?),
+        ),
+        Term::Or(lhs, rhs) => SqlExpression::Or(
+            Box::new(term_to_sql(lhs, schema, columns)?),
+            Box::new(term_to_sql(rhs, schema, columns)?),
+        ),
+    })
+}
+
+fn scalar_to_sql(
+    expression: &ScalarExpression,
+    schema: &SimpleRelationSchema,
+    columns: &[Column],
+) -> Result<SqlExpression> {
+    Ok(match expression {
+        ScalarExpression::Attribute(v) => {
+            columns[attribute_to_index(v, schema)?].expression.clone()
+        }
+        ScalarExpression::Constant(v) => SqlExpression::Literal(value_to_sql(v)),
+        ScalarExpression::Operation(lhs, op, rhs) => SqlExpression::Arithmetic(
+            Box::new(scalar_to_sql(lhs, schema, columns)?),
+            scalar_operator(*op),
+            Box::new(scalar_to_sql(rhs, schema, columns)?),
+        ),
+        ScalarExpression::Function(function, arguments) => SqlExpression::Function(
+            scalar_function(*function),
+            arguments
+                .iter()
+                .map(|argument| scalar_to_sql(argument, schema, columns))
+                .collect::<Result<Vec<SqlExpression>>>()?,
+        ),
+        ScalarExpression::Case(branches, otherwise) => SqlExpression::Case(
+            branches
+                .iter()
+                .map(|(condition, value)| {
+                    Ok((
+                        term_to_sql(condition, schema, columns)?,
+                        scalar_to_sql(value, schema, columns)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            match otherwise {
+                None => None,
+                Some(otherwise) => Some(Box::new(scalar_to_sql(otherwise, schema, columns)?)),
+            },
+        ),
+    })
+}
+
+fn scalar_operator(operator: ScalarOperator) -> &'static str {
+    match operator {
+        ScalarOperator::Add => "+",
+        ScalarOperator::Subtract => "-",
+        ScalarOperator::Multiply => "*",
+        ScalarOperator::Divide => "/",
+        ScalarOperator::Modulo => "%",
+        ScalarOperator::Concatenate => "||",
+    }
+}
+
+fn scalar_function(function: ScalarFunction) -> &'static str {
+    match function {
+        ScalarFunction::Upper => "UPPER",
+        ScalarFunction::Lower => "LOWER",
+        ScalarFunction::Length => "LENGTH",
+        ScalarFunction::Substring => "SUBSTR",
+        ScalarFunction::Absolute => "ABS",
+        ScalarFunction::Round => "ROUND",
+        ScalarFunction::Coalesce => "COALESCE",
+    }
+}
+
+fn comparison_operator(operator: ComparisonOperator) -> &'static str {
+    match operator {
+        ComparisonOperator::Equal => "=",
+        ComparisonOperator::NotEqual => "<>",
+        ComparisonOperator::LessThan => "<",
+        ComparisonOperator::LessThanOrEqual => "<=",
+        ComparisonOperator::GreaterThan => ">",
+        ComparisonOperator::GreaterThanOrEqual => ">=",
+        ComparisonOperator::StringMatch => "~",
+        ComparisonOperator::StringNotMatch => "!~",
+        ComparisonOperator::Like => "LIKE",
+        ComparisonOperator::NotLike => "NOT LIKE",
+        ComparisonOperator::ILike => "ILIKE",
+        ComparisonOperator::NotILike => "NOT ILIKE",
+    }
+}
+
+fn aggregate_function(function: AggregateFunction) -> &'static str {
+    match function {
+        AggregateFunction::Count => "COUNT",
+        AggregateFunction::Sum => "SUM",
+        AggregateFunction::Average => "AVG",
+        AggregateFunction::Minimum => "MIN",
+        AggregateFunction::Maximum => "MAX",
+    }
+}
+
+fn projected_columns(
+    op: &Projection,
+    schema: &SimpleRelationSchema,
+    columns: &[Column],
+) -> Result<Vec<Column>> {
+    op.attributes()
+        .enumerate()
+        .map(|(i, attribute)| {
+            let attribute = match attribute {
+                ProjectedAttribute::Index(v) => Attribute::Index(*v),
+                ProjectedAttribute::Name(v) => Attribute::Name(v.clone()),
+                ProjectedAttribute::Constant(v) => {
+                    return Ok(Column {
+                        expression: SqlExpression::Literal(value_to_sql(v)),
+                        name: constant_attribute_name(i),
+                    })
+                }
+                ProjectedAttribute::Computed(v, name) => {
+                    return Ok(Column {
+                        expression: scalar_to_sql(v, schema, columns)?,
+                        name: name.clone().unwrap_or_else(|| constant_attribute_name(i)),
+                    })
+                }
+            };
+            attribute_to_index(&attribute, schema).map(|index| columns[index].clone())
+        })
+        .collect()
+}
+
+///
+/// Returns the expression for each attribute, omitting literals which in `GROUP BY` and
+/// `ORDER BY` would instead be taken as column positions.
+///
+fn column_expressions<'b, I>(
+    attributes: I,
+    schema: &SimpleRelationSchema,
+    columns: &[Column],
+) -> Result<Vec<SqlExpression>>
+where
+    I: Iterator<Item = &'b Attribute>,
+{
+    let mut expressions: Vec<SqlExpression> = Default::default();
+    for attribute in attributes {
+        let expression = &columns[attribute_to_index(attribute, schema)?].expression;
+        if !expression.is_literal() {
+            expressions.push(expression.clone());
+        }
+    }
+    Ok(expressions)
+}
+
+fn relation_names(op: &RelationalOp, names: &mut HashSet<Name>) {
+    match op {
+        RelationalOp::Relation(v) => {
+            let _ = names.insert(v.clone());
+        }
+        RelationalOp::SetOperation(v) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::Selection(v) => relation_names(v.rhs(), names),
+        RelationalOp::Projection(v) => relation_names(v.rhs(), names),
+        RelationalOp::Rename(v) => relation_names(v.rhs(), names),
+        RelationalOp::Order(v) => relation_names(v.rhs(), names),
+        RelationalOp::Group(v) => relation_names(v.rhs(), names),
+        RelationalOp::Join(Join::Natural(v)) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::Join(Join::Theta(v)) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::Join(Join::Outer(v)) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::SemiJoin(v) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::AntiJoin(v) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+        RelationalOp::Division(v) => {
+            relation_names(v.lhs(), names);
+            relation_names(v.rhs(), names);
+        }
+    }
+}
+
+///
+/// Returns a name for each column which is unique within the list, duplicates are given a numeric
+/// suffix.
+///
+fn unique_names(names: &[Name]) -> Vec<String> {
+    let mut used: HashSet<String> = names.iter().map(|name| name.to_string()).collect();
+    let mut seen: HashSet<&Name> = Default::default();
+    names
+        .iter()
+        .map(|name| {
+            if seen.insert(name) {
+                name.to_string()
+            } else {
+                let unique = (2..)
+                    .map(|i| format!("{}_{}", name, i))
+                    .find(|candidate| !used.contains(candidate))
+                    .unwrap();
+                let _ = used.insert(unique.clone());
+                unique
+            }
+        })
+        .collect()
+}
+
+fn render_list(expressions: &[SqlExpression], qualify: bool) -> String {
+    expressions
+        .iter()
+        .map(|expression| expression.render(qualify))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+///
+/// Quote an identifier if it would otherwise be folded to a different case, is not a simple
+/// identifier, or is a reserved word.
+///
+fn identifier(name: &str) -> String {
+    let is_simple = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !RESERVED_WORDS.contains(&name.to_ascii_uppercase().as_str());
+    if is_simple {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+fn value_to_sql(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Boolean(v) => if *v { "TRUE" } else { "FALSE" }.to_string(),
+        Value::Byte(v) => v.to_string(),
+        Value::UnsignedInteger(v) => v.to_string(),
+        Value::Integer(v) => v.to_string(),
+        Value::Float(v) if v.is_finite() => format!("{:?}", v),
+        Value::Float(v) => format!(
+            "CAST('{}' AS DOUBLE PRECISION)",
+            if v.is_nan() {
+                "NaN"
+            } else if v.is_sign_positive() {
+                "Infinity"
+            } else {
+                "-Infinity"
+            }
+        ),
+        Value::Char(v) => string_literal(&v.to_string()),
+        Value::String(v) => string_literal(v),
+        Value::Binary(v) => format!(
+            "X'{}'",
+            v.iter().map(|b| format!("{:02X}", b)).collect::<String>()
+        ),
+        Value::Decimal(v) => v.to_string(),
+        Value::Date(_) => format!("DATE '{}'", value),
+        Value::Time(_) => format!("TIME '{}'", value),
+        Value::Timestamp(_) => format!("TIMESTAMP '{}'", value),
+        Value::TimestampTz(_) => format!("TIMESTAMP WITH TIME ZONE '{}'", value),
+        Value::Interval(_) => format!("INTERVAL '{}'", value),
+    }
+}
+
+fn outer_join_keyword(outer: Option<OuterJoinKind>) -> &'static str {
+    match outer {
+        None => "",
+        Some(OuterJoinKind::Left) => "LEFT ",
+        Some(OuterJoinKind::Right) => "RIGHT ",
+        Some(OuterJoinKind::Full) => "FULL ",
+    }
+}
+
+fn string_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod parse;
@@ -0,0 +1,1326 @@
+/*!
+Provides a parser for a subset of SQL `SELECT` statements, translating each into the equivalent
+relational algebra.
+
+The supported subset comprises:
+
+* `SELECT` lists of `*`, `t.*`, columns, and literal values, each with an optional alias;
+  `DISTINCT` is accepted, but as the algebra has set semantics, duplicates are always removed.
+* `FROM` lists of tables, derived tables `(SELECT …) AS t`, and `CROSS JOIN`, `NATURAL JOIN`, or
+  `[INNER] JOIN … ON` joins, as well as the outer joins `LEFT`, `RIGHT`, and `FULL [OUTER] JOIN`,
+  either natural or with `ON`; a comma in a `FROM` list is a cartesian product.
+* `WHERE` conditions using `AND`, `OR`, `NOT`, `IS [NOT] NULL`, and comparisons `=`, `<>`, `!=`,
+  `<`, `<=`, `>`, `>=`, as well as `[NOT] IN (…)`, `[NOT] BETWEEN … AND …`, the patterns
+  `[NOT] LIKE` and `[NOT] ILIKE`, and the regular expression matches `~` and `!~`.
+* `GROUP BY` and `ORDER BY` lists of columns; an `ORDER BY` may also use a 1-based position,
+  and each of its keys may have a `COLLATE` clause, `ASC` or `DESC`, and `NULLS FIRST` or
+  `NULLS LAST`. As the result is sorted after it is projected, an `ORDER BY` column must be one
+  of those selected.
+* The aggregates `COUNT(*)`, and `COUNT`, `SUM`, `AVG`, `MIN`, and `MAX` of a column, optionally
+  `DISTINCT`, in a `SELECT` list; any column selected alongside them must be grouped.
+* `UNION`, `INTERSECT`, and `EXCEPT`, but not their `ALL` forms.
+* `WITH` clauses, each common table expression becoming a named expression.
+
+Keywords are case-insensitive, identifiers are case-sensitive and must be quoted, as `"order"`,
+if they are reserved words. Literal values are written as in SQL: `42`, `-4.2`, `'string'`,
+`TRUE`, `FALSE`, and `X'1F'` (binary).
+
+Columns are resolved by name, qualified or not, against a schema; each column becomes an
+attribute name where the name is unique in the relation, or otherwise an attribute index.
+
+# Example
+
+```rust
+use relational_algebra::ast::RelationalOp;
+use relational_algebra::simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema};
+use relational_algebra::sort::{AttributeSchema, Domain, RelationSchema, Schema};
+use relational_algebra::sql::parse::sql_to_relational;
+use relational_algebra::Name;
+use std::str::FromStr;
+
+let schema = SimpleSchema::new(
+    Name::new_unchecked("example"),
+    [SimpleRelationSchema::new(
+        Name::new_unchecked("people"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+            SimpleAttributeSchema::new(Name::new_unchecked("age"), Domain::Integer),
+        ],
+    )
+    .unwrap()],
+)
+.unwrap();
+
+let query = sql_to_relational("SELECT name FROM people p WHERE p.age >= 18", &schema).unwrap();
+assert_eq!(
+    query,
+    RelationalOp::from_str("π[name](σ[age≥18i64]people)").unwrap()
+);
+```
+
+ */
+
+use super::RESERVED_WORDS;
+use crate::{
+    ast::{
+        Aggregate, AggregateFunction, Atom, Attribute, ComparisonOperator, Expression,
+        ExpressionList, NullOrdering, OuterJoinKind, ProjectedAttribute, RelationalOp, Rename,
+        ScalarExpression, SetOperation, SetOperator, SortDirection, SortKey, Term,
+    },
+    data::Value,
+    error::{
+        ambiguous_attribute, attribute_does_not_exist, attribute_index_invalid,
+        attribute_not_grouped, attribute_not_selected, relation_does_not_exist, Error, ParseKind,
+        Result,
+    },
+    eval::Analyzer,
+    parse::{is_identifier_continue, is_identifier_start, lex, parse_all, symbol},
+    simple::sort::SimpleRelationSchema,
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    Name,
+};
+use combine::error::StreamError;
+use combine::parser::char::{char, digit, hex_digit, spaces, string};
+use combine::stream::StreamErrorFor;
+use combine::{
+    attempt, between, choice, look_ahead, many, many1, none_of, not_followed_by, optional, parser,
+    satisfy, sep_by1, sep_end_by, Parser, Stream,
+};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a list of SQL statements, each terminated by `;`, the terminator on the last statement
+/// is optional. The common table expressions of each statement become named expressions which
+/// precede the statement's own expression in the list.
+///
+pub fn sql_to_expression_list<S>(s: &str, schema: &S) -> Result<ExpressionList>
+where
+    S: Schema,
+{
+    let statements = parse_all(
+        s,
+        ParseKind::Sql,
+        spaces().with(sep_end_by::<Vec<Statement>, _, _, _>(
+            statement(),
+            symbol(';'),
+        )),
+    )?;
+    let mut translator = Translator::new(schema, false);
+    let mut expressions: Vec<Expression> = Default::default();
+    for statement in &statements {
+        expressions.extend(translator.statement(statement)?);
+    }
+    Ok(expressions.into())
+}
+
+///
+/// Parse a single SQL statement; any common table expressions are replaced by their
+/// definitions where they are referenced.
+///
+pub fn sql_to_relational<S>(s: &str, schema: &S) -> Result<RelationalOp>
+where
+    S: Schema,
+{
+    let statement = parse_all(
+        s,
+        ParseKind::Sql,
+        spaces().with(statement()).skip(optional(symbol(';'))),
+    )?;
+    let mut translator = Translator::new(schema, true);
+    Ok(translator
+        .statement(&statement)?
+        .pop()
+        .unwrap()
+        .expression()
+        .clone())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+struct Statement {
+    ctes: Vec<(Name, Query)>,
+    query: Query,
+}
+
+enum Query {
+    Select(Box<Select>),
+    Compound(Box<Query>, SetOperator, Box<Query>),
+    Ordered(Box<Query>, Vec<OrderItem>),
+}
+
+struct Select {
+    items: Vec<SelectItem>,
+    from: Vec<FromItem>,
+    criteria: Option<Condition>,
+    group_by: Vec<ColumnRef>,
+}
+
+enum SelectItem {
+    Wildcard(Option<Name>),
+    Operand(Operand, Option<Name>),
+    Aggregate(AggregateCall, Option<Name>),
+}
+
+struct AggregateCall {
+    function: AggregateFunction,
+    distinct: bool,
+    argument: Option<ColumnRef>,
+}
+
+enum FromItem {
+    Table(Name, Option<Name>),
+    Derived(Box<Query>, Option<Name>),
+    Join(Box<FromItem>, JoinKind, Box<FromItem>),
+}
+
+enum JoinKind {
+    Cross,
+    Natural(Option<OuterJoinKind>),
+    On(Option<OuterJoinKind>, Condition),
+}
+
+enum Condition {
+    Constant(bool),
+    IsNull(ColumnRef, bool),
+    Comparison(ColumnRef, ComparisonOperator, Operand),
+    In(ColumnRef, Vec<Operand>),
+    Between(ColumnRef, Operand, Operand),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+enum Operand {
+    Column(ColumnRef),
+    Constant(Value),
+}
+
+struct ColumnRef {
+    qualifier: Option<Name>,
+    name: Name,
+}
+
+enum OrderKey {
+    Column(ColumnRef),
+    Position(usize),
+}
+
+struct OrderItem {
+    key: OrderKey,
+    collation: Option<Name>,
+    direction: SortDirection,
+    nulls: Option<NullOrdering>,
+}
+
+///
+/// The qualifiers, table names or aliases, by which each column of a relation may be referenced.
+///
+type Scope = Vec<Vec<Name>>;
+
+struct Translator<'a, S>
+where
+    S: Schema,
+{
+    analyzer: Analyzer<'a, S>,
+    inline: bool,
+    ctes: HashMap<Name, RelationalOp>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, S> Translator<'a, S>
+where
+    S: Schema,
+{
+    fn new(schema: &'a S, inline: bool) -> Self {
+        Self {
+            analyzer: Analyzer::new(schema),
+            inline,
+            ctes: Default::default(),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<Vec<Expression>> {
+        let mut expressions: Vec<Expression> = Default::default();
+        for (name, query) in &statement.ctes {
+            let (op, _) = self.query(query)?;
+            let schema = self.analyzer.relational(&op)?;
+            let _ = self.analyzer.views.insert(name.clone(), schema);
+            if !self.inline {
+                expressions.push(Expression::named(name.clone(), op.clone()));
+            }
+            let _ = self.ctes.insert(name.clone(), op);
+        }
+        let (op, _) = self.query(&statement.query)?;
+        expressions.push(Expression::new(op));
+        Ok(expressions)
+    }
+
+    fn query(&mut self, query: &Query) -> Result<(RelationalOp, Scope)> {
+        match query {
+            Query::Select(select) => self.select(select),
+            Query::Compound(lhs, operator, rhs) => {
+                let (lhs, scope) = self.query(lhs)?;
+                let (rhs, _) = self.query(rhs)?;
+                let op: RelationalOp = SetOperation::new(lhs, *operator, rhs).into();
+                let _ = self.analyzer.relational(&op)?;
+                Ok((op, vec![Vec::new(); scope.len()]))
+            }
+            Query::Ordered(query, items) => {
+                let (op, scope) = self.query(query)?;
+                let schema = self.analyzer.relational(&op)?;
+                let keys = items
+                    .iter()
+                    .map(|item| {
+                        let attribute = match &item.key {
+                            OrderKey::Position(position)
+                                if (1..=schema.len()).contains(position) =>
+                            {
+                                attribute_at(position - 1, &schema)
+                            }
+                            OrderKey::Position(position) => {
+                                return Err(attribute_index_invalid(*position))
+                            }
+                            OrderKey::Column(column) => match resolve(column, &schema, &scope) {
+                                Err(Error::AttributeDoesNotExist { .. })
+                                    if self.is_source_column(query, column)? =>
+                                {
+                                    return Err(attribute_not_selected(column.name.clone()))
+                                }
+                                result => result?,
+                            },
+                        };
+                        let key = SortKey::new(attribute, item.direction);
+                        let key = match &item.collation {
+                            None => key,
+                            Some(collation) => key.with_collation(collation.clone()),
+                        };
+                        Ok(match item.nulls {
+                            None => key,
+                            Some(nulls) => key.with_nulls(nulls),
+                        })
+                    })
+                    .collect::<Result<Vec<SortKey>>>()?;
+                let op = RelationalOp::sort_by_keys(keys, op);
+                let _ = self.analyzer.relational(&op)?;
+                Ok((op, scope))
+            }
+        }
+    }
+
+    ///
+    /// Returns `true` if `column` is a column of the `FROM` list of `query`, whether or not it is
+    /// selected.
+    ///
+    fn is_source_column(&mut self, query: &Query, column: &ColumnRef) -> Result<bool> {
+        match query {
+            Query::Select(select) => {
+                let (op, scope) = self.source(select)?;
+                let schema = self.analyzer.relational(&op)?;
+                Ok(resolve(column, &schema, &scope).is_ok())
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn source(&mut self, select: &Select) -> Result<(RelationalOp, Scope)> {
+        let mut from = select.from.iter();
+        let (mut op, mut scope) = self.table_reference(from.next().unwrap())?;
+        for item in from {
+            let (rhs, rhs_scope) = self.table_reference(item)?;
+            op = RelationalOp::cartesian_product(op, rhs);
+            scope.extend(rhs_scope);
+        }
+        Ok((op, scope))
+    }
+
+    fn select(&mut self, select: &Select) -> Result<(RelationalOp, Scope)> {
+        let (mut op, mut scope) = self.source(select)?;
+
+        if let Some(criteria) = &select.criteria {
+            let schema = self.analyzer.relational(&op)?;
+            let criteria = condition_to_term(criteria, &schema, &scope)?;
+            op = RelationalOp::select(criteria, op);
+        }
+
+        let mut aggregates: Vec<Aggregate> = Default::default();
+        // the schema, and scope, before grouping, to report a selected column that is not grouped.
+        let mut ungrouped: Option<(SimpleRelationSchema, Scope)> = None;
+        if !select.group_by.is_empty() || select.items.iter().any(SelectItem::is_aggregate) {
+            let schema = self.analyzer.relational(&op)?;
+            ungrouped = Some((schema.clone(), scope.clone()));
+            let attributes = select
+                .group_by
+                .iter()
+                .map(|column| resolve(column, &schema, &scope))
+                .collect::<Result<Vec<Attribute>>>()?;
+            for item in &select.items {
+                if let SelectItem::Aggregate(call, alias) = item {
+                    let aggregate = match &call.argument {
+                        None => Aggregate::count_all(),
+                        Some(column) if call.distinct => Aggregate::new_distinct(
+                            call.function,
+                            resolve(column, &schema, &scope)?,
+                        ),
+                        Some(column) => {
+                            Aggregate::new(call.function, resolve(column, &schema, &scope)?)
+                        }
+                    };
+                    aggregates.push(match alias {
+                        None => aggregate,
+                        Some(alias) => aggregate.named(alias.clone()),
+                    });
+                }
+            }
+            scope = attributes
+                .iter()
+                .map(|attribute| scope[index_of(attribute, &schema)].clone())
+                .chain(aggregates.iter().map(|_| Vec::new()))
+                .collect();
+            op = RelationalOp::aggregate(attributes, aggregates.clone(), op);
+        }
+        // aggregates follow the grouping attributes in the result.
+        let mut next_aggregate = scope.len() - aggregates.len();
+
+        let schema = self.analyzer.relational(&op)?;
+        let mut projected: Vec<(ProjectedAttribute, Option<&Name>, Vec<Name>)> = Default::default();
+        for item in &select.items {
+            match item {
+                SelectItem::Wildcard(None) => projected.extend(
+                    (0..schema.len())
+                        .map(|i| (attribute_at(i, &schema).into(), None, scope[i].clone())),
+                ),
+                SelectItem::Wildcard(Some(qualifier)) => {
+                    let before = projected.len();
+                    projected.extend(
+                        (0..schema.len())
+                            .filter(|i| scope[*i].contains(qualifier))
+                            .map(|i| (attribute_at(i, &schema).into(), None, scope[i].clone())),
+                    );
+                    if projected.len() == before {
+                        return Err(relation_does_not_exist(qualifier.clone()));
+                    }
+                }
+                SelectItem::Operand(Operand::Column(column), alias) => {
+                    let attribute = match (resolve(column, &schema, &scope), &ungrouped) {
+                        (
+                            Err(Error::AttributeDoesNotExist { .. }),
+                            Some((source, source_scope)),
+                        ) if resolve(column, source, source_scope).is_ok() => {
+                            return Err(attribute_not_grouped(column.name.clone()))
+                        }
+                        (result, _) => result?,
+                    };
+                    let qualifiers = scope[index_of(&attribute, &schema)].clone();
+                    projected.push((attribute.into(), alias.as_ref(), qualifiers));
+                }
+                SelectItem::Operand(Operand::Constant(value), alias) => {
+                    projected.push((value.clone().into(), alias.as_ref(), Vec::new()))
+                }
+                SelectItem::Aggregate(_, alias) => {
+                    projected.push((
+                        attribute_at(next_aggregate, &schema).into(),
+                        alias.as_ref(),
+                        Vec::new(),
+                    ));
+                    next_aggregate += 1;
+                }
+            }
+        }
+
+        let is_identity = projected.len() == schema.len()
+            && projected
+                .iter()
+                .enumerate()
+                .all(|(i, (attribute, _, _))| projected_index(attribute, &schema) == Some(i));
+        if !is_identity {
+            op = RelationalOp::project(
+                projected
+                    .iter()
+                    .map(|(attribute, _, _)| attribute.clone())
+                    .collect(),
+                op,
+            );
+        }
+
+        let schema = self.analyzer.relational(&op)?;
+        let names: Vec<&Name> = projected
+            .iter()
+            .enumerate()
+            .map(|(i, (_, alias, _))| alias.unwrap_or_else(|| schema.attribute(i).unwrap().name()))
+            .collect();
+        let mut renames: HashMap<Attribute, Name> = Default::default();
+        for (i, name) in names.iter().enumerate() {
+            if schema.attribute(i).unwrap().name() != *name {
+                // an alias may not duplicate any other column name.
+                if names.iter().filter(|other| other == &name).count() > 1 {
+                    return Err(ambiguous_attribute((*name).clone()));
+                }
+                let _ = renames.insert(attribute_at(i, &schema), (*name).clone());
+            }
+        }
+        if !renames.is_empty() {
+            op = Rename::new(renames, op)?.into();
+        }
+        Ok((
+            op,
+            projected
+                .into_iter()
+                .map(|(_, _, qualifiers)| qualifiers)
+                .collect(),
+        ))
+    }
+
+    fn table_reference(&mut self, item: &FromItem) -> Result<(RelationalOp, Scope)> {
+        match item {
+            FromItem::Table(name, alias) => {
+                let op = match self.ctes.get(name) {
+                    Some(cte) if self.inline => cte.clone(),
+                    _ => RelationalOp::Relation(name.clone()),
+                };
+                let schema = self.analyzer.relational(&op)?;
+                let qualifier = alias.as_ref().unwrap_or(name);
+                Ok((op, vec![vec![qualifier.clone()]; schema.len()]))
+            }
+            FromItem::Derived(query, alias) => {
+                let (op, scope) = self.query(query)?;
+                Ok((op, vec![alias.iter().cloned().collect(); scope.len()]))
+            }
+            FromItem::Join(lhs, kind, rhs) => {
+                let (lhs, mut scope) = self.table_reference(lhs)?;
+                let (rhs, rhs_scope) = self.table_reference(rhs)?;
+                match kind {
+                    JoinKind::Cross => {
+                        scope.extend(rhs_scope);
+                        Ok((RelationalOp::cartesian_product(lhs, rhs), scope))
+                    }
+                    JoinKind::Natural(outer) => {
+                        let lhs_schema = self.analyzer.relational(&lhs)?;
+                        let rhs_schema = self.analyzer.relational(&rhs)?;
+                        // a common column may be referenced by the qualifiers of either side.
+                        for (i, qualifiers) in rhs_scope.into_iter().enumerate() {
+                            let name = rhs_schema.attribute(i).unwrap().name();
+                            match lhs_schema.attribute_index(name) {
+                                Some(index) => scope[index].extend(qualifiers),
+                                None => scope.push(qualifiers),
+                            }
+                        }
+                        Ok((
+                            match outer {
+                                None => RelationalOp::natural_join(lhs, rhs),
+                                Some(kind) => RelationalOp::outer_join(lhs, *kind, rhs),
+                            },
+                            scope,
+                        ))
+                    }
+                    JoinKind::On(outer, criteria) => {
+                        scope.extend(rhs_scope);
+                        let schema = self.analyzer.relational(&RelationalOp::cartesian_product(
+                            lhs.clone(),
+                            rhs.clone(),
+                        ))?;
+                        let criteria = condition_to_term(criteria, &schema, &scope)?;
+                        Ok((
+                            match outer {
+                                None => RelationalOp::theta_join(lhs, criteria, rhs),
+                                Some(kind) => {
+                                    RelationalOp::outer_theta_join(lhs, *kind, criteria, rhs)
+                                }
+                            },
+                            scope,
+                        ))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl SelectItem {
+    fn is_aggregate(&self) -> bool {
+        matches!(self, Self::Aggregate(_, _))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the attribute at `index`, by name if the name is unique in `schema`.
+///
+fn attribute_at(index: usize, schema: &SimpleRelationSchema) -> Attribute {
+    let name = schema.attribute(index).unwrap().name();
+    if schema
+        .attributes()
+        .filter(|attribute| attribute.name() == name)
+        .count()
+        == 1
+    {
+        Attribute::Name(name.clone())
+    } else {
+        Attribute::Index(index)
+    }
+}
+
+fn index_of(attribute: &Attribute, schema: &SimpleRelationSchema) -> usize {
+    match attribute {
+        Attribute::Index(index) => *index,
+        Attribute::Name(name) => schema.attribute_index(name).unwrap(),
+    }
+}
+
+fn projected_index(attribute: &ProjectedAttribute, schema: &SimpleRelationSchema) -> Option<usize> {
+    match attribute {
+        ProjectedAttribute::Index(index) => Some(*index),
+        ProjectedAttribute::Name(name) => schema.attribute_index(name),
+        ProjectedAttribute::Constant(_) | ProjectedAttribute::Computed(_, _) => None,
+    }
+}
+
+fn resolve(column: &ColumnRef, schema: &SimpleRelationSchema, scope: &Scope) -> Result<Attribute> {
+    let candidates: Vec<usize> = schema
+        .attributes()
+        .enumerate()
+        .filter(|(i, attribute)| {
+            attribute.name() == &column.name
+                && column
+                    .qualifier
+                    .as_ref()
+                    .is_none_or(|qualifier| scope[*i].contains(qualifier))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    match candidates.as_slice() {
+        [] => Err(attribute_does_not_exist(column.name.clone())),
+        [index] => Ok(attribute_at(*index, schema)),
+        _ => Err(ambiguous_attribute(column.name.clone())),
+    }
+}
+
+fn condition_to_term(
+    condition: &Condition,
+    schema: &SimpleRelationSchema,
+    scope: &Scope,
+) -> Result<Term> {
+    Ok(match condition {
+        Condition::Constant(v) => Term::constant(*v),
+        Condition::IsNull(column, is_null) => {
+            let attribute = resolve(column, schema, scope)?;
+            if *is_null {
+                Term::null(attribute)
+            } else {
+                Term::not_null(attribute)
+            }
+        }
+        Condition::Comparison(lhs, operator, rhs) => Term::Atom(Atom::new(
+            resolve(lhs, schema, scope)?,
+            *operator,
+            operand_to_scalar(rhs, schema, scope)?,
+        )),
+        Condition::In(lhs, list) => Term::in_list(
+            resolve(lhs, schema, scope)?,
+            list.iter()
+                .map(|item| operand_to_scalar(item, schema, scope))
+                .collect::<Result<Vec<ScalarExpression>>>()?,
+        ),
+        Condition::Between(lhs, low, high) => Term::between(
+            resolve(lhs, schema, scope)?,
+            operand_to_scalar(low, schema, scope)?,
+            operand_to_scalar(high, schema, scope)?,
+        ),
+        Condition::Not(v) => condition_to_term(v, schema, scope)?.negate(),
+        Condition::And(lhs, rhs) => Term::and(
+            condition_to_term(lhs, schema, scope)?,
+            condition_to_term(rhs, schema, scope)?,
+        ),
+        Condition::Or(lhs, rhs) => Term::or(
+            condition_to_term(lhs, schema, scope)?,
+            condition_to_term(rhs, schema, scope)?,
+        ),
+    })
+}
+
+fn negated(condition: Condition, not: Option<&'static str>) -> Condition {
+    match not {
+        None => condition,
+        Some(_) => Condition::Not(Box::new(condition)),
+    }
+}
+
+fn operand_to_scalar(
+    operand: &Operand,
+    schema: &SimpleRelationSchema,
+    scope: &Scope,
+) -> Result<ScalarExpression> {
+    Ok(match operand {
+        Operand::Column(column) => resolve(column, schema, scope)?.into(),
+        Operand::Constant(value) => value.clone().into(),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Lexical
+
+fn word<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        satisfy(is_identifier_start),
+        many::<String, _, _>(satisfy(is_identifier_continue)),
+    )
+        .map(|(c, mut s)| {
+            s.insert(0, c);
+            s
+        })
+}
+
+fn keyword<Input>(k: &'static str) -> impl Parser<Input, Output = &'static str>
+where
+    Input: Stream<Token = char>,
+{
+    lex(attempt(word().and_then(move |w| {
+        if w.eq_ignore_ascii_case(k) {
+            Ok(k)
+        } else {
+            Err(StreamErrorFor::<Input>::expected_static_message(k))
+        }
+    })))
+    .expected(k)
+}
+
+fn identifier<Input>() -> impl Parser<Input, Output = Name>
+where
+    Input: Stream<Token = char>,
+{
+    lex(choice((
+        between(
+            char('"'),
+            char('"'),
+            many1::<String, _, _>(choice((
+                attempt(string("\"\"")).map(|_| '"'),
+                none_of(['"']),
+            ))),
+        ),
+        attempt(word().and_then(|w| {
+            if RESERVED_WORDS.contains(&w.to_ascii_uppercase().as_str()) {
+                Err(StreamErrorFor::<Input>::message_format(format!(
+                    "`{}` is a reserved word",
+                    w
+                )))
+            } else {
+                Ok(w)
+            }
+        })),
+    )))
+    .map(|s| Name::new_unchecked(&s))
+    .expected("name")
+}
+
+fn alias<Input>() -> impl Parser<Input, Output = Name>
+where
+    Input: Stream<Token = char>,
+{
+    optional(keyword("AS")).with(identifier())
+}
+
+fn column_ref<Input>() -> impl Parser<Input, Output = ColumnRef>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        identifier(),
+        optional(attempt(symbol('.').with(identifier()))),
+    )
+        .map(|(first, second)| match second {
+            None => ColumnRef {
+                qualifier: None,
+                name: first,
+            },
+            Some(name) => ColumnRef {
+                qualifier: Some(first),
+                name,
+            },
+        })
+        .expected("column")
+}
+
+// ------------------------------------------------------------------------------------------------
+// Literal Values
+
+fn value<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        lex(number()),
+        string_literal().map(Value::String),
+        (
+            attempt(
+                choice((
+                    keyword("DATE").map(|_| Domain::Date),
+                    keyword("TIME").map(|_| Domain::Time),
+                    keyword("TIMESTAMP").with(
+                        optional((keyword("WITH"), keyword("TIME"), keyword("ZONE"))).map(|zone| {
+                            match zone {
+                                None => Domain::Timestamp,
+                                Some(_) => Domain::TimestampTz,
+                            }
+                        }),
+                    ),
+                    keyword("INTERVAL").map(|_| Domain::Interval),
+                ))
+                .skip(look_ahead(char('\''))),
+            ),
+            string_literal(),
+        )
+            .and_then(|(domain, s)| {
+                Value::parse(domain, &s).map_err(|_| {
+                    StreamErrorFor::<Input>::message_format(format!(
+                        "invalid {} literal '{}'",
+                        domain, s
+                    ))
+                })
+            }),
+        lex(attempt((satisfy(|c| c == 'X' || c == 'x'), char('\'')))
+            .with(many::<String, _, _>(hex_digit()))
+            .skip(char('\''))
+            .and_then(|s| {
+                if s.len() % 2 == 0 {
+                    Ok(Value::Binary(
+                        (0..s.len())
+                            .step_by(2)
+                            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+                            .collect(),
+                    ))
+                } else {
+                    Err(StreamErrorFor::<Input>::message_static_message(
+                        "a binary literal requires an even number of hex digits",
+                    ))
+                }
+            })),
+        keyword("TRUE").map(|_| Value::Boolean(true)),
+        keyword("FALSE").map(|_| Value::Boolean(false)),
+    ))
+    .expected("literal value")
+}
+
+fn string_literal<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+{
+    lex(between(
+        char('\''),
+        char('\''),
+        many::<String, _, _>(choice((
+            attempt(string("''")).map(|_| '\''),
+            none_of(['\'']),
+        ))),
+    ))
+}
+
+///
+/// Parses a numeric literal; a number with a fraction or exponent is a float, any other is an
+/// integer, or an unsigned integer if it is too large for an integer.
+///
+fn number<Input>() -> impl Parser<Input, Output = Value>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        optional(char('-')),
+        many1::<String, _, _>(digit()),
+        optional(attempt(char('.').with(many1::<String, _, _>(digit())))),
+        optional(attempt((
+            choice((char('e'), char('E'))),
+            optional(choice((char('-'), char('+')))),
+            many1::<String, _, _>(digit()),
+        ))),
+    )
+        .skip(not_followed_by(satisfy(is_identifier_continue)))
+        .and_then(|(sign, whole, fraction, exponent)| {
+            let mut text: String = sign.into_iter().collect();
+            text.push_str(&whole);
+            let is_float = fraction.is_some() || exponent.is_some();
+            if let Some(fraction) = fraction {
+                text.push('.');
+                text.push_str(&fraction);
+            }
+            if let Some((e, sign, digits)) = exponent {
+                text.push(e);
+                text.extend(sign);
+                text.push_str(&digits);
+            }
+            let value = if is_float {
+                text.parse::<f64>().ok().map(Value::Float)
+            } else {
+                text.parse::<i64>()
+                    .map(Value::Integer)
+                    .or_else(|_| text.parse::<u64>().map(Value::UnsignedInteger))
+                    .ok()
+            };
+            value.ok_or_else(|| {
+                StreamErrorFor::<Input>::message_format(format!(
+                    "invalid numeric literal `{}`",
+                    text
+                ))
+            })
+        })
+}
+
+fn operand<Input>() -> impl Parser<Input, Output = Operand>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        value().map(Operand::Constant),
+        column_ref().map(Operand::Column),
+    ))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Conditions
+
+fn comparison_operator<Input>() -> impl Parser<Input, Output = ComparisonOperator>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        lex(choice((
+            attempt(string("<>")).map(|_| ComparisonOperator::NotEqual),
+            attempt(string("!=")).map(|_| ComparisonOperator::NotEqual),
+            attempt(string("!~")).map(|_| ComparisonOperator::StringNotMatch),
+            attempt(string("<=")).map(|_| ComparisonOperator::LessThanOrEqual),
+            attempt(string(">=")).map(|_| ComparisonOperator::GreaterThanOrEqual),
+            char('=').map(|_| ComparisonOperator::Equal),
+            char('<').map(|_| ComparisonOperator::LessThan),
+            char('>').map(|_| ComparisonOperator::GreaterThan),
+            char('~').map(|_| ComparisonOperator::StringMatch),
+        ))),
+        attempt(keyword("NOT").with(choice((
+            keyword("LIKE").map(|_| ComparisonOperator::NotLike),
+            keyword("ILIKE").map(|_| ComparisonOperator::NotILike),
+        )))),
+        keyword("LIKE").map(|_| ComparisonOperator::Like),
+        keyword("ILIKE").map(|_| ComparisonOperator::ILike),
+    ))
+    .silent()
+    .expected("comparison operator")
+}
+
+fn comparison<Input>() -> impl Parser<Input, Output = Condition>
+where
+    Input: Stream<Token = char>,
+{
+    (operand(), comparison_operator(), operand()).and_then(|(lhs, operator, rhs)| {
+        match (lhs, rhs) {
+            (Operand::Column(lhs), rhs) => Ok(Condition::Comparison(lhs, operator, rhs)),
+            (Operand::Constant(lhs), Operand::Column(rhs)) => operator
+                .swap_operands()
+                .map(|operator| Condition::Comparison(rhs, operator, Operand::Constant(lhs)))
+                .ok_or_else(|| {
+                    StreamErrorFor::<Input>::message_static_message(
+                        "a pattern match requires a column on the left",
+                    )
+                }),
+            _ => Err(StreamErrorFor::<Input>::message_static_message(
+                "a comparison requires at least one column",
+            )),
+        }
+    })
+}
+
+fn condition_<Input>() -> impl Parser<Input, Output = Condition>
+where
+    Input: Stream<Token = char>,
+{
+    (conjunction(), optional(keyword("OR").with(condition()))).map(|(lhs, rhs)| match rhs {
+        None => lhs,
+        Some(rhs) => Condition::Or(Box::new(lhs), Box::new(rhs)),
+    })
+}
+
+parser! {
+    fn condition[Input]()(Input) -> Condition
+    where [Input: Stream<Token = char>]
+    {
+        condition_()
+    }
+}
+
+fn conjunction_<Input>() -> impl Parser<Input, Output = Condition>
+where
+    Input: Stream<Token = char>,
+{
+    (negation(), optional(keyword("AND").with(conjunction()))).map(|(lhs, rhs)| match rhs {
+        None => lhs,
+        Some(rhs) => Condition::And(Box::new(lhs), Box::new(rhs)),
+    })
+}
+
+parser! {
+    fn conjunction[Input]()(Input) -> Condition
+    where [Input: Stream<Token = char>]
+    {
+        conjunction_()
+    }
+}
+
+fn negation_<Input>() -> impl Parser<Input, Output = Condition>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        keyword("NOT")
+            .with(negation())
+            .map(|v| Condition::Not(Box::new(v))),
+        primary_condition(),
+    ))
+}
+
+parser! {
+    fn negation[Input]()(Input) -> Condition
+    where [Input: Stream<Token = char>]
+    {
+        negation_()
+    }
+}
+
+fn primary_condition<Input>() -> impl Parser<Input, Output = Condition>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        between(symbol('('), symbol(')'), condition()),
+        attempt(comparison()),
+        attempt((
+            column_ref(),
+            keyword("IS"),
+            optional(keyword("NOT")),
+            keyword("NULL"),
+        ))
+        .map(|(column, _, not, _)| Condition::IsNull(column, not.is_none())),
+        (
+            attempt((column_ref(), optional(keyword("NOT")), keyword("IN"))),
+            between(symbol('('), symbol(')'), sep_by1(operand(), symbol(','))),
+        )
+            .map(|((column, not, _), list)| negated(Condition::In(column, list), not)),
+        (
+            attempt((column_ref(), optional(keyword("NOT")), keyword("BETWEEN"))),
+            operand(),
+            keyword("AND"),
+            operand(),
+        )
+            .map(|((column, not, _), low, _, high)| {
+                negated(Condition::Between(column, low, high), not)
+            }),
+        keyword("TRUE").map(|_| Condition::Constant(true)),
+        keyword("FALSE").map(|_| Condition::Constant(false)),
+    ))
+    .expected("condition")
+}
+
+// ------------------------------------------------------------------------------------------------
+// Queries
+
+fn aggregate_call<Input>() -> impl Parser<Input, Output = AggregateCall>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        attempt(
+            choice((
+                keyword("COUNT").map(|_| AggregateFunction::Count),
+                keyword("SUM").map(|_| AggregateFunction::Sum),
+                keyword("AVG").map(|_| AggregateFunction::Average),
+                keyword("MIN").map(|_| AggregateFunction::Minimum),
+                keyword("MAX").map(|_| AggregateFunction::Maximum),
+            ))
+            .skip(symbol('(')),
+        ),
+        choice((
+            symbol('*').map(|_| None),
+            (
+                optional(choice((keyword("DISTINCT"), keyword("ALL")))),
+                column_ref(),
+            )
+                .map(Some),
+        )),
+        symbol(')'),
+    )
+        .and_then(|(function, argument, _)| match argument {
+            None if function != AggregateFunction::Count => Err(
+                StreamErrorFor::<Input>::message_static_message("only COUNT may be applied to `*`"),
+            ),
+            None => Ok(AggregateCall {
+                function,
+                distinct: false,
+                argument: None,
+            }),
+            Some((quantifier, column)) => Ok(AggregateCall {
+                function,
+                distinct: quantifier == Some("DISTINCT"),
+                argument: Some(column),
+            }),
+        })
+}
+
+fn select_item<Input>() -> impl Parser<Input, Output = SelectItem>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        symbol('*').map(|_| SelectItem::Wildcard(None)),
+        attempt(identifier().skip(symbol('.')).skip(symbol('*')))
+            .map(|qualifier| SelectItem::Wildcard(Some(qualifier))),
+        (aggregate_call(), optional(alias()))
+            .map(|(call, alias)| SelectItem::Aggregate(call, alias)),
+        (operand(), optional(alias())).map(|(operand, alias)| SelectItem::Operand(operand, alias)),
+    ))
+}
+
+fn table_primary<Input>() -> impl Parser<Input, Output = FromItem>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        attempt(look_ahead(
+            many1::<Vec<_>, _, _>(symbol('(')).with(keyword("SELECT")),
+        ))
+        .with((
+            between(symbol('('), symbol(')'), query()),
+            optional(alias()),
+        ))
+        .map(|(query, alias)| FromItem::Derived(Box::new(query), alias)),
+        between(symbol('('), symbol(')'), from_item()),
+        (identifier(), optional(alias())).map(|(name, alias)| FromItem::Table(name, alias)),
+    ))
+    .expected("table")
+}
+
+fn join_clause<Input>() -> impl Parser<Input, Output = (JoinKind, FromItem)>
+where
+    Input: Stream<Token = char>,
+{
+    choice((
+        keyword("CROSS")
+            .with(keyword("JOIN"))
+            .with(table_primary())
+            .map(|rhs| (JoinKind::Cross, rhs)),
+        keyword("NATURAL")
+            .with(join_type())
+            .skip(keyword("JOIN"))
+            .and(table_primary())
+            .map(|(outer, rhs)| (JoinKind::Natural(outer), rhs)),
+        join_type()
+            .skip(keyword("JOIN"))
+            .and((table_primary(), keyword("ON").with(condition())))
+            .map(|(outer, (rhs, criteria))| (JoinKind::On(outer, criteria), rhs)),
+    ))
+}
+
+fn join_type<Input>() -> impl Parser<Input, Output = Option<OuterJoinKind>>
+where
+    Input: Stream<Token = char>,
+{
+    optional(choice((
+        keyword("INNER").map(|_| None),
+        choice((
+            keyword("LEFT").map(|_| OuterJoinKind::Left),
+            keyword("RIGHT").map(|_| OuterJoinKind::Right),
+            keyword("FULL").map(|_| OuterJoinKind::Full),
+        ))
+        .skip(optional(keyword("OUTER")))
+        .map(Some),
+    )))
+    .map(Option::flatten)
+}
+
+fn from_item_<Input>() -> impl Parser<Input, Output = FromItem>
+where
+    Input: Stream<Token = char>,
+{
+    (table_primary(), many::<Vec<_>, _, _>(join_clause())).map(|(first, joins)| {
+        joins.into_iter().fold(first, |lhs, (kind, rhs)| {
+            FromItem::Join(Box::new(lhs), kind, Box::new(rhs))
+        })
+    })
+}
+
+parser! {
+    fn from_item[Input]()(Input) -> FromItem
+    where [Input: Stream<Token = char>]
+    {
+        from_item_()
+    }
+}
+
+fn select<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        keyword("SELECT"),
+        optional(choice((keyword("DISTINCT"), keyword("ALL")))),
+        sep_by1(select_item(), symbol(',')),
+        keyword("FROM"),
+        sep_by1(from_item(), symbol(',')),
+        optional(keyword("WHERE").with(condition())),
+        optional(
+            keyword("GROUP")
+                .with(keyword("BY"))
+                .with(sep_by1(column_ref(), symbol(','))),
+        ),
+    )
+        .map(|(_, _, items, _, from, criteria, group_by)| {
+            Query::Select(Box::new(Select {
+                items,
+                from,
+                criteria,
+                group_by: group_by.unwrap_or_default(),
+            }))
+        })
+}
+
+fn set_operator<Input>(
+    k: &'static str,
+    operator: SetOperator,
+) -> impl Parser<Input, Output = SetOperator>
+where
+    Input: Stream<Token = char>,
+{
+    keyword(k)
+        .with(optional(choice((keyword("DISTINCT"), keyword("ALL")))))
+        .and_then(move |quantifier| match quantifier {
+            Some("ALL") => Err(StreamErrorFor::<Input>::message_static_message(
+                "set operations always remove duplicates, `ALL` is not supported",
+            )),
+            _ => Ok(operator),
+        })
+}
+
+fn query_term<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+{
+    choice((select(), between(symbol('('), symbol(')'), query())))
+}
+
+fn intersection<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        query_term(),
+        many::<Vec<_>, _, _>(
+            set_operator("INTERSECT", SetOperator::Intersection).with(query_term()),
+        ),
+    )
+        .map(|(first, rest)| {
+            rest.into_iter().fold(first, |lhs, rhs| {
+                Query::Compound(Box::new(lhs), SetOperator::Intersection, Box::new(rhs))
+            })
+        })
+}
+
+fn order_item<Input>() -> impl Parser<Input, Output = OrderItem>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        choice((
+            lex(attempt(
+                many1::<String, _, _>(digit())
+                    .skip(not_followed_by(satisfy(is_identifier_continue))),
+            ))
+            .and_then(|s| {
+                s.parse::<usize>().map(OrderKey::Position).map_err(|_| {
+                    StreamErrorFor::<Input>::message_format(format!("invalid position `{}`", s))
+                })
+            }),
+            column_ref().map(OrderKey::Column),
+        )),
+        optional(keyword("COLLATE").with(identifier())),
+        optional(choice((
+            keyword("ASC").map(|_| SortDirection::Ascending),
+            keyword("DESC").map(|_| SortDirection::Descending),
+        ))),
+        optional(keyword("NULLS").with(choice((
+            keyword("FIRST").map(|_| NullOrdering::First),
+            keyword("LAST").map(|_| NullOrdering::Last),
+        )))),
+    )
+        .map(|(key, collation, direction, nulls)| OrderItem {
+            key,
+            collation,
+            direction: direction.unwrap_or_default(),
+            nulls,
+        })
+}
+
+fn query_<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        intersection(),
+        many::<Vec<_>, _, _>((
+            choice((
+                set_operator("UNION", SetOperator::Union),
+                set_operator("EXCEPT", SetOperator::Difference),
+            )),
+            intersection(),
+        )),
+        optional(
+            keyword("ORDER")
+                .with(keyword("BY"))
+                .with(sep_by1(order_item(), symbol(','))),
+        ),
+    )
+        .map(|(first, rest, order_by)| {
+            let query = rest.into_iter().fold(first, |lhs, (operator, rhs)| {
+                Query::Compound(Box::new(lhs), operator, Box::new(rhs))
+            });
+            match order_by {
+                None => query,
+                Some(keys) => Query::Ordered(Box::new(query), keys),
+            }
+        })
+}
+
+parser! {
+    fn query[Input]()(Input) -> Query
+    where [Input: Stream<Token = char>]
+    {
+        query_()
+    }
+}
+
+fn statement<Input>() -> impl Parser<Input, Output = Statement>
+where
+    Input: Stream<Token = char>,
+{
+    (
+        optional(keyword("WITH").with(sep_by1(
+            (
+                identifier(),
+                keyword("AS").with(between(symbol('('), symbol(')'), query())),
+            ),
+            symbol(','),
+        ))),
+        query(),
+    )
+        .map(|(ctes, query)| Statement {
+            ctes: ctes.unwrap_or_default(),
+            query,
+        })
+}
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
 use relational_algebra::{
-    ast::{Attribute, Expression, ExpressionList, RelationalOp, Term},
+    ast::{
+        Aggregate, AggregateFunction, Atom, Attribute, ComparisonOperator, DisplayFormat,
+        Expression, ExpressionList, Format, NullOrdering, OuterJoinKind, ProjectedAttribute,
+        RelationalOp, ScalarExpression, ScalarFunction, SemiJoinKind, SortKey, Term,
+    },
     data::Value,
     Name,
 };
@@ -40,6 +44,30 @@ fn test_term_nested_and() {
     assert_eq!(format!("{}", ast), String::from("?a ∧ ?b ∧ ?c"));
 }
 
+#[test]
+fn test_term_null_tests() {
+    let ast = Term::or(
+        Term::null(Name::new_unchecked("a")),
+        Term::not_null(1).negate(),
+    );
+    assert_eq!(
+        format!("{}", ast),
+        String::from("a is null ∨ ¬1 is not null")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::ToStringAscii),
+        String::from("a is null or not 1 is not null")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("a\\text{ is null} \\lor \\neg1\\text{ is not null}")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("a is null &or; &not;1 is not null")
+    );
+}
+
 #[test]
 fn test_relation_only() {
     let ast = RelationalOp::relation_unchecked("relation");
@@ -50,6 +78,18 @@ fn test_relation_only() {
 fn test_set_operation_only() {
     let ast = RelationalOp::union(Name::new_unchecked("left"), Name::new_unchecked("right"));
     assert_eq!(format!("{}", ast), String::from("left ∪ right"));
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::ToStringAscii),
+        String::from("left union right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\cup right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("left &cup; right")
+    );
 }
 
 #[test]
@@ -95,6 +135,131 @@ fn test_theta_join_only() {
     assert_eq!(format!("{}", ast), String::from("left ⨝[0=1] right"));
 }
 
+#[test]
+fn test_outer_joins() {
+    let ast = RelationalOp::outer_join(
+        Name::new_unchecked("left"),
+        OuterJoinKind::Left,
+        Name::new_unchecked("right"),
+    );
+    assert_eq!(format!("{}", ast), String::from("left ⟕ right"));
+    assert_eq!(format!("{:#}", ast), String::from("left leftjoin right"));
+
+    let ast = RelationalOp::outer_theta_join(
+        Name::new_unchecked("left"),
+        OuterJoinKind::Full,
+        Term::equals(0, 1),
+        Name::new_unchecked("right"),
+    );
+    assert!(ast.is_outer_join());
+    assert_eq!(format!("{}", ast), String::from("left ⟗[0=1] right"));
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("left fulljoin[0=1] right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\mathbin{]\\mkern-5.8mu\\bowtie\\mkern-5.8mu[}_{0=1} right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("left ⟗<sub>0&equals;1</sub> right")
+    );
+
+    let ast = RelationalOp::outer_join(
+        Name::new_unchecked("left"),
+        OuterJoinKind::Right,
+        Name::new_unchecked("right"),
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\mathbin{\\bowtie\\mkern-5.8mu[} right")
+    );
+
+    let ast = RelationalOp::outer_join(
+        Name::new_unchecked("left"),
+        OuterJoinKind::Left,
+        Name::new_unchecked("right"),
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\mathbin{]\\mkern-5.8mu\\bowtie} right")
+    );
+}
+
+#[test]
+fn test_semi_and_anti_joins() {
+    let ast = RelationalOp::semi_join(
+        Name::new_unchecked("left"),
+        SemiJoinKind::Left,
+        Name::new_unchecked("right"),
+    );
+    assert!(ast.is_semi_join());
+    assert_eq!(format!("{}", ast), String::from("left ⋉ right"));
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("left leftsemijoin right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\ltimes right")
+    );
+
+    let ast = RelationalOp::semi_theta_join(
+        Name::new_unchecked("left"),
+        SemiJoinKind::Right,
+        Term::equals(0, 1),
+        Name::new_unchecked("right"),
+    );
+    assert_eq!(format!("{}", ast), String::from("left ⋊[0=1] right"));
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("left &rtimes;<sub>0&equals;1</sub> right")
+    );
+
+    let ast = RelationalOp::anti_theta_join(
+        Name::new_unchecked("left"),
+        Term::equals(0, 1),
+        Name::new_unchecked("right"),
+    );
+    assert!(ast.is_anti_join());
+    assert!(!ast.as_anti_join().unwrap().is_natural());
+    assert_eq!(format!("{}", ast), String::from("left ▷[0=1] right"));
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("left antijoin[0=1] right")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\triangleright_{0=1} right")
+    );
+}
+
+#[test]
+fn test_division() {
+    let ast = RelationalOp::division(
+        Name::new_unchecked("left"),
+        RelationalOp::project(
+            vec![Name::new_unchecked("a").into()],
+            Name::new_unchecked("right"),
+        ),
+    );
+    assert!(ast.is_division());
+    assert_eq!(format!("{}", ast), String::from("left ÷ (π[a]right)"));
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("left divide (project[a]right)")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("left \\div \\(\\pi_{a}right\\)")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("left &divide; (&pi;<sub>a</sub>right)")
+    );
+}
+
 #[test]
 fn test_unnamed_expression() {
     let ast: ExpressionList = Expression::new(RelationalOp::union(
@@ -114,3 +279,185 @@ fn test_named_expression() {
     .into();
     assert_eq!(format!("{}", ast), String::from("A ≔ left ∪ right;\n"));
 }
+
+#[test]
+fn test_group_with_aggregates() {
+    let ast = RelationalOp::aggregate(
+        vec![Name::new_unchecked("course").into()],
+        vec![
+            Aggregate::count_all().named(Name::new_unchecked("n")),
+            Aggregate::new_distinct(AggregateFunction::Average, 2.into()),
+        ],
+        Name::new_unchecked("enrolled"),
+    );
+    assert_eq!(
+        format!("{}", ast),
+        String::from("γ[course, count(*)/n, avg(distinct 2)]enrolled")
+    );
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("group[course, count(*)/n, avg(distinct 2)]enrolled")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from(
+            "\\gamma_{course, \\mathrm{count}(*)/n, \\mathrm{avg}(\\mathrm{distinct}\\ 2)}enrolled"
+        )
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("&gamma;<sub>course, count(*)/n, avg(distinct 2)</sub>enrolled")
+    );
+
+    let ast = RelationalOp::aggregate(
+        vec![],
+        vec![Aggregate::maximum(Name::new_unchecked("id").into())],
+        Name::new_unchecked("students"),
+    );
+    assert_eq!(format!("{}", ast), String::from("γ[max(id)]students"));
+}
+
+#[test]
+fn test_order_with_sort_keys() {
+    let ast = RelationalOp::sort_by_keys(
+        vec![
+            SortKey::descending(Name::new_unchecked("a").into()),
+            SortKey::ascending(1.into())
+                .with_collation(Name::new_unchecked("nocase"))
+                .with_nulls(NullOrdering::First),
+        ],
+        Name::new_unchecked("relation"),
+    );
+    assert_eq!(
+        format!("{}", ast),
+        String::from("τ[a↓, 1↑ collate nocase nulls first]relation")
+    );
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("sort[a desc, 1 asc collate nocase nulls first]relation")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from(
+            "\\tau_{a\\downarrow, 1\\uparrow\\ \\mathrm{collate}\\ nocase\\ \\mathrm{nulls}\\ \\mathrm{first}}relation"
+        )
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("&tau;<sub>a&darr;, 1&uarr; collate nocase nulls first</sub>relation")
+    );
+}
+
+#[test]
+fn test_scalar_expressions() {
+    let a = || ScalarExpression::from(Name::new_unchecked("a"));
+    let expression = ScalarExpression::multiply(
+        ScalarExpression::add(a(), Value::from(1i64)),
+        ScalarExpression::function(
+            ScalarFunction::Absolute,
+            vec![ScalarExpression::subtract(
+                1,
+                ScalarExpression::subtract(2, 3),
+            )],
+        ),
+    );
+    let ast = RelationalOp::project(
+        vec![
+            a().into(),
+            ProjectedAttribute::computed(expression, Some(Name::new_unchecked("b"))),
+        ],
+        Name::new_unchecked("relation"),
+    );
+    assert_eq!(
+        format!("{}", ast),
+        String::from("π[a, (a + 1i64) × abs(1 - (2 - 3))/b]relation")
+    );
+    assert_eq!(
+        format!("{:#}", ast),
+        String::from("project[a, (a + 1i64) * abs(1 - (2 - 3))/b]relation")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Latex),
+        String::from("\\pi_{a, (a + 1) \\times \\mathrm{abs}(1 - (2 - 3))/b}relation")
+    );
+    assert_eq!(
+        ast.to_formatted_string(DisplayFormat::Html),
+        String::from("&pi;<sub>a, (a + 1) &times; abs(1 &minus; (2 &minus; 3))/b</sub>relation")
+    );
+
+    let term = Term::greater_than(
+        ScalarExpression::case(
+            vec![(
+                Term::less_than(a(), 0),
+                ScalarExpression::from(Value::from("neg")),
+            )],
+            Some(ScalarExpression::concatenate(a(), Value::from("!"))),
+        ),
+        Value::from("m"),
+    );
+    assert_eq!(
+        format!("{}", term),
+        String::from("case when a<0 then \"neg\" else a ‖ \"!\" end > \"m\"")
+    );
+    assert_eq!(
+        format!("{:#}", term),
+        String::from("case when a<0 then \"neg\" else a || \"!\" end > \"m\"")
+    );
+    assert_eq!(
+        term.to_formatted_string(DisplayFormat::Latex),
+        String::from(
+            "\\mathrm{case}\\ \\mathrm{when}\\ a<0\\ \\mathrm{then}\\ \"neg\"\\ \\mathrm{else}\\ a \\mathbin{\\|} \"!\"\\ \\mathrm{end} > \"m\""
+        )
+    );
+}
+
+#[test]
+fn test_membership_range_and_patterns() {
+    let a = || Name::new_unchecked("a");
+    let term = Term::and(
+        Term::in_list(a(), [Value::from(1i64), Value::from(2i64)]),
+        Term::or(
+            Term::between(0, Value::from(1i64), Value::from(9i64)),
+            Term::not_ilike(a(), Value::from("x%")),
+        ),
+    );
+    assert_eq!(
+        format!("{}", term),
+        String::from("a ∈ {1i64, 2i64} ∧ (0 between 1i64 and 9i64 ∨ a not ilike \"x%\")")
+    );
+    assert_eq!(
+        format!("{:#}", term),
+        String::from("a in {1i64, 2i64} and (0 between 1i64 and 9i64 or a not ilike \"x%\")")
+    );
+    assert_eq!(
+        term.to_formatted_string(DisplayFormat::Latex),
+        String::from(
+            "a \\in \\{1, 2\\} \\land (0\\text{ between }1\\text{ and }9 \\lor a \\text{ not ilike } \"x%\")"
+        )
+    );
+    assert_eq!(
+        term.to_formatted_string(DisplayFormat::Html),
+        String::from("a &isin; {1, 2} &and; (0 between 1 and 9 &or; a not ilike \"x%\")")
+    );
+}
+
+#[test]
+fn test_normalized_atoms() {
+    let a = || Name::new_unchecked("a");
+    assert_eq!(
+        Atom::less_than(Value::from(1i64), a()).normalized(),
+        Atom::greater_than(a(), Value::from(1i64))
+    );
+    assert_eq!(
+        Atom::equals(Value::from(1i64), a()).normalized(),
+        Atom::equals(a(), Value::from(1i64))
+    );
+    assert_eq!(
+        Atom::like(Value::from("x"), a()).normalized(),
+        Atom::like(Value::from("x"), a())
+    );
+    assert_eq!(
+        ComparisonOperator::GreaterThanOrEqual.negate(),
+        ComparisonOperator::LessThan
+    );
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "simple_data")]
+
+use relational_algebra::{
+    data::{Relation, Value},
+    error::Error,
+    simple::{
+        data::{SimpleRelation, SimpleTuple},
+        io::csv::{
+            read_relation, read_relation_from_path, write_relation, write_relation_to_path,
+            CsvOptions, EmptyFields, Header, Quoting,
+        },
+        sort::{SimpleAttributeSchema, SimpleRelationSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema},
+    Name,
+};
+
+fn people() -> SimpleRelationSchema {
+    SimpleRelationSchema::new(
+        Name::new_unchecked("people"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+            SimpleAttributeSchema::new(Name::new_unchecked("name"), Domain::String),
+            SimpleAttributeSchema::new(Name::new_unchecked("born"), Domain::Date)
+                .with_nullable(true),
+        ],
+    )
+    .unwrap()
+}
+
+fn read(text: &str, options: &CsvOptions) -> Result<SimpleRelation, Error> {
+    read_relation(text.as_bytes(), people(), options)
+}
+
+fn write(relation: &SimpleRelation, options: &CsvOptions) -> String {
+    let mut output: Vec<u8> = Vec::new();
+    write_relation(&mut output, relation, options).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn tuple(id: i64, name: &str, born: Option<&str>) -> SimpleTuple {
+    SimpleTuple::from(vec![
+        Value::from(id),
+        Value::from(name),
+        born.map(|s| Value::parse(Domain::Date, s).unwrap())
+            .unwrap_or(Value::Null),
+    ])
+}
+
+#[test]
+fn test_read_named_columns() {
+    let relation = read(
+        "name,extra,born,id\n\"smith, j\",x,2001-02-03,1\n,y,,2\n",
+        &CsvOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        relation.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        vec![
+            tuple(1, "smith, j", Some("2001-02-03")),
+            // an empty field is null only for a nullable attribute.
+            tuple(2, "", None),
+        ]
+    );
+    assert!(matches!(
+        read("name,id\nalice,1\n", &CsvOptions::default()),
+        Err(Error::AttributeDoesNotExist { .. })
+    ));
+    // an empty file has no header row to name the columns, and no records.
+    let relation = read("", &CsvOptions::default()).unwrap();
+    assert!(relation.is_empty());
+    assert_eq!(relation.schema(), &people());
+}
+
+#[test]
+fn test_read_options() {
+    let options = CsvOptions::default()
+        .with_delimiter(b';')
+        .with_quote(b'\'')
+        .with_header(Header::Absent);
+    let relation = read("1;'a;b';2001-02-03\n2;c;\n", &options).unwrap();
+    assert_eq!(relation.len(), 2);
+    assert!(relation.contains(&tuple(1, "a;b", Some("2001-02-03"))));
+
+    let options = CsvOptions::default().with_header(Header::Ignored);
+    let relation = read("a,b,c\n1,x,\n", &options).unwrap();
+    assert!(relation.contains(&tuple(1, "x", None)));
+
+    let options = CsvOptions::default().with_empty_fields(EmptyFields::Parse);
+    assert!(matches!(
+        read("id,name,born\n1,x,\n", &options),
+        Err(Error::InvalidValue {
+            expecting_domain: Domain::Date,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_read_errors() {
+    let error = read(
+        "id,name,born\n1,alice,2001-02-03\n2,bob,2001-02-30\n",
+        &CsvOptions::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::InvalidValue {
+            expecting_domain: Domain::Date,
+            location: Some((3, 3)),
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "The value `\"2001-02-30\"`, at row 3, column 3, is not a valid `date`."
+    );
+    assert!(matches!(
+        read("id,name,born\none,alice,\n", &CsvOptions::default()),
+        Err(Error::InvalidValue {
+            location: Some((2, 1)),
+            ..
+        })
+    ));
+    assert!(matches!(
+        read(
+            "1,alice\n",
+            &CsvOptions::default().with_header(Header::Absent)
+        ),
+        Err(Error::IncompatibleArity {
+            expected: 3,
+            given: 2,
+            row: Some(1)
+        })
+    ));
+    assert!(matches!(
+        read("id,name,born\n1,alice,\n2,bob\n", &CsvOptions::default()),
+        Err(Error::IncompatibleArity {
+            expected: 3,
+            given: 2,
+            row: Some(3)
+        })
+    ));
+}
+
+#[test]
+fn test_write_options() {
+    let relation = SimpleRelation::from_tuples(
+        people(),
+        [
+            tuple(1, "smith, j", Some("2001-02-03")),
+            tuple(2, "say \"hi\"", None),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        write(&relation, &CsvOptions::default()),
+        "id,name,born\n1,\"smith, j\",2001-02-03\n2,\"say \"\"hi\"\"\",\n"
+    );
+    assert_eq!(
+        write(
+            &relation,
+            &CsvOptions::default()
+                .with_delimiter(b'\t')
+                .with_quoting(Quoting::NonNumeric)
+                .with_header(Header::Absent)
+        ),
+        "1\t\"smith, j\"\t\"2001-02-03\"\n2\t\"say \"\"hi\"\"\"\t\n"
+    );
+}
+
+#[test]
+fn test_empty_strings_and_nulls() {
+    let schema = SimpleRelationSchema::new(
+        Name::new_unchecked("notes"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::Integer),
+            SimpleAttributeSchema::new(Name::new_unchecked("text"), Domain::String)
+                .with_nullable(true),
+            SimpleAttributeSchema::new(Name::new_unchecked("data"), Domain::Binary)
+                .with_nullable(true),
+        ],
+    )
+    .unwrap();
+    let relation = SimpleRelation::from_tuples(
+        schema.clone(),
+        [
+            SimpleTuple::from(vec![Value::from(1i64), Value::from(""), Value::Null]),
+            SimpleTuple::from(vec![
+                Value::from(2i64),
+                Value::Null,
+                Value::Binary(Vec::new()),
+            ]),
+        ],
+    )
+    .unwrap();
+
+    // an empty string is quoted, a null is not.
+    let text = write(&relation, &CsvOptions::default());
+    assert_eq!(text, "id,text,data\n1,\"\",\n2,,\"\"\n");
+    let result = read_relation(text.as_bytes(), schema, &CsvOptions::default()).unwrap();
+    assert_eq!(
+        result.tuples().collect::<Vec<&SimpleTuple>>(),
+        relation.tuples().collect::<Vec<&SimpleTuple>>()
+    );
+}
+
+#[test]
+fn test_round_trip_domains() {
+    let domains = [
+        Domain::Boolean,
+        Domain::Byte,
+        Domain::UnsignedInteger,
+        Domain::Float,
+        Domain::Char,
+        Domain::Binary,
+        Domain::Decimal,
+        Domain::Time,
+        Domain::Timestamp,
+        Domain::TimestampTz,
+        Domain::Interval,
+    ];
+    let schema = SimpleRelationSchema::new(
+        Name::new_unchecked("values"),
+        domains.iter().enumerate().map(|(i, domain)| {
+            SimpleAttributeSchema::new(Name::new_unchecked(&format!("a{}", i)), *domain)
+        }),
+    )
+    .unwrap();
+    let values = [
+        "true",
+        "31",
+        "42",
+        "-2.5",
+        "\"",
+        "01ff",
+        "12.50",
+        "12:30:00.25",
+        "2024-02-29T12:30:00",
+        "2024-02-29T12:30:00-05:00",
+        "P1Y2DT3H",
+    ];
+    let relation = SimpleRelation::from_tuples(
+        schema.clone(),
+        [SimpleTuple::from(
+            domains
+                .iter()
+                .zip(values)
+                .map(|(domain, s)| Value::parse(*domain, s).unwrap())
+                .collect::<Vec<Value>>(),
+        )],
+    )
+    .unwrap();
+
+    let path = std::env::temp_dir().join(format!("relational-algebra-{}.csv", std::process::id()));
+    write_relation_to_path(&path, &relation, &CsvOptions::default()).unwrap();
+    let result = read_relation_from_path(&path, schema, &CsvOptions::default());
+    std::fs::remove_file(&path).unwrap();
+    let result = result.unwrap();
+    assert_eq!(
+        result.tuples().collect::<Vec<&SimpleTuple>>(),
+        relation.tuples().collect::<Vec<&SimpleTuple>>()
+    );
+    assert!(matches!(
+        read_relation_from_path(&path, people(), &CsvOptions::default()),
+        Err(Error::Io { .. })
+    ));
+}
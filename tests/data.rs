@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use relational_algebra::{
+    data::{Decimal, Interval, Value},
+    error::Error,
+    sort::Domain,
+};
+
+fn hash_set<const N: usize>(values: [Value; N]) -> HashSet<Value> {
+    HashSet::from(values)
+}
+
+fn assert_parse_display(domain: Domain, s: &str, expected: &str) {
+    let value = Value::parse(domain, s).unwrap();
+    assert_eq!(value.data_type(), domain);
+    assert_eq!(value.to_string(), expected);
+    assert_eq!(Value::parse(domain, expected).unwrap(), value);
+    assert_eq!(
+        Value::parse(domain, &value.to_plain_string()).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_parse_scalar_values() {
+    assert_eq!(
+        Value::parse(Domain::Integer, "-42").unwrap(),
+        Value::from(-42i64)
+    );
+    assert_eq!(Value::parse(Domain::Char, "c").unwrap(), Value::from('c'));
+    assert_eq!(
+        Value::parse(Domain::Binary, "01ff").unwrap(),
+        Value::from(vec![1u8, 255])
+    );
+    assert_eq!(Value::parse(Domain::Null, "null").unwrap(), Value::Null);
+    for value in [
+        Value::from(0x1fu8),
+        Value::from('c'),
+        Value::from("a \"string\""),
+        Value::from(vec![1u8, 255]),
+        Value::Null,
+    ] {
+        assert_eq!(
+            Value::parse(value.data_type(), &value.to_plain_string()).unwrap(),
+            value
+        );
+    }
+    assert!(matches!(
+        Value::parse(Domain::Byte, "256"),
+        Err(Error::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn test_parse_decimal_values() {
+    assert_parse_display(Domain::Decimal, "12.50", "12.50");
+    assert_parse_display(Domain::Decimal, "-0.001", "-0.001");
+    assert_eq!(
+        Value::parse(Domain::Decimal, "1.5").unwrap(),
+        Value::from(Decimal::from_str("1.50").unwrap())
+    );
+    assert!(Value::parse(Domain::Decimal, "1.5.0").is_err());
+}
+
+#[test]
+fn test_parse_temporal_values() {
+    assert_parse_display(Domain::Date, "2024-02-29", "2024-02-29");
+    assert!(Value::parse(Domain::Date, "2023-02-29").is_err());
+    assert_parse_display(Domain::Time, "09:05", "09:05:00");
+    assert_parse_display(Domain::Time, "23:59:59.250", "23:59:59.25");
+    assert!(Value::parse(Domain::Time, "24:00:00").is_err());
+    assert_parse_display(
+        Domain::Timestamp,
+        "2024-02-29 12:30:00",
+        "2024-02-29T12:30:00",
+    );
+    assert_parse_display(
+        Domain::TimestampTz,
+        "2024-02-29T12:30:00Z",
+        "2024-02-29T12:30:00+00:00",
+    );
+    assert_parse_display(
+        Domain::TimestampTz,
+        "2024-02-29 12:30:00.5-03:30",
+        "2024-02-29T12:30:00.5-03:30",
+    );
+    assert!(Value::parse(Domain::TimestampTz, "2024-02-29T12:30:00").is_err());
+}
+
+#[test]
+fn test_parse_intervals() {
+    assert_parse_display(Domain::Interval, "P1Y2M3DT4H5M6.5S", "P1Y2M3DT4H5M6.5S");
+    assert_parse_display(Domain::Interval, "P2W", "P14D");
+    assert_parse_display(Domain::Interval, "PT90M", "PT1H30M");
+    assert_parse_display(Domain::Interval, "PT-0.5S", "PT-0.5S");
+    assert_parse_display(Domain::Interval, "PT0S", "PT0S");
+    assert_eq!(
+        Interval::from_str("P1MT1S").unwrap(),
+        Interval::new(1, 0, 1_000_000_000)
+    );
+    for invalid in ["P", "PT", "1D", "P1.5D", "P1X"] {
+        assert!(Interval::from_str(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_value_ordering() {
+    let interval = |s: &str| Interval::from_str(s).unwrap();
+    assert!(interval("P1M") < interval("P30DT1S"));
+    assert!(interval("P30D") < interval("P1M"));
+    assert!(interval("PT24H") < interval("P1D"));
+    assert!(interval("P1D") < interval("PT24H1S"));
+
+    let parse = |domain: Domain, s: &str| Value::parse(domain, s).unwrap();
+    assert!(parse(Domain::Date, "2023-12-31") < parse(Domain::Date, "2024-01-01"));
+    assert!(parse(Domain::Decimal, "9.99") < parse(Domain::Decimal, "10.0"));
+    assert_eq!(
+        parse(Domain::TimestampTz, "2024-01-01T12:00:00+01:00"),
+        parse(Domain::TimestampTz, "2024-01-01T11:00:00Z")
+    );
+}
+
+#[test]
+fn test_value_equality_and_hashing() {
+    assert_eq!(Value::from(f64::NAN), Value::from(-f64::NAN));
+    assert_eq!(Value::from(-0.0), Value::from(0.0));
+    assert_ne!(Value::from(1i64), Value::from(1.0));
+    assert_ne!(Value::from(1u64), Value::from(1i64));
+    assert_eq!(
+        Value::parse(Domain::Decimal, "1.5").unwrap(),
+        Value::parse(Domain::Decimal, "1.500").unwrap()
+    );
+    assert_eq!(
+        hash_set([
+            Value::from(f64::NAN),
+            Value::from(f64::NAN.copysign(-1.0)),
+            Value::from(0.0),
+            Value::from(-0.0),
+            Value::parse(Domain::Decimal, "2.0").unwrap(),
+            Value::parse(Domain::Decimal, "2").unwrap(),
+            Value::parse(Domain::TimestampTz, "2024-01-01T12:00:00+01:00").unwrap(),
+            Value::parse(Domain::TimestampTz, "2024-01-01T11:00:00Z").unwrap(),
+            Value::Null,
+            Value::Null,
+        ])
+        .len(),
+        5
+    );
+}
+
+#[test]
+fn test_value_total_order() {
+    let mut values = vec![
+        Value::Null,
+        Value::from(f64::NAN),
+        Value::from(1.5),
+        Value::from(f64::NEG_INFINITY),
+        Value::from("a"),
+        Value::from(2i64),
+        Value::from(-0.0),
+        Value::from(true),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![
+            Value::from(true),
+            Value::from(2i64),
+            Value::from(f64::NEG_INFINITY),
+            Value::from(0.0),
+            Value::from(1.5),
+            Value::from(f64::NAN),
+            Value::from("a"),
+            Value::Null,
+        ]
+    );
+}
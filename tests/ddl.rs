@@ -0,0 +1,150 @@
+#![cfg(feature = "simple_data")]
+
+use relational_algebra::{
+    error::{Error, Location, ParseKind},
+    simple::{
+        ddl::{parse_attribute_schema, parse_relation_schema, parse_schema},
+        sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    Name,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_parse_attribute_schema() {
+    let attribute = parse_attribute_schema("born : date ?").unwrap();
+    assert_eq!(attribute.name(), &Name::new_unchecked("born"));
+    assert_eq!(attribute.domain(), &Domain::Date);
+    assert!(attribute.is_nullable());
+    assert_eq!(attribute.to_string(), "born: date?");
+
+    let attribute = SimpleAttributeSchema::from_str("credits: unsigned").unwrap();
+    assert_eq!(attribute.domain(), &Domain::UnsignedInteger);
+    assert!(!attribute.is_nullable());
+}
+
+#[test]
+fn test_parse_relation_schema() {
+    let relation =
+        parse_relation_schema("student(id: integer, name: string, nick: string?)").unwrap();
+    assert_eq!(relation.name(), &Name::new_unchecked("student"));
+    assert_eq!(
+        relation
+            .attributes()
+            .map(|a| (a.name().to_string(), *a.domain(), a.is_nullable()))
+            .collect::<Vec<(String, Domain, bool)>>(),
+        vec![
+            ("id".to_string(), Domain::Integer, false),
+            ("name".to_string(), Domain::String, false),
+            ("nick".to_string(), Domain::String, true),
+        ]
+    );
+    assert_eq!(
+        SimpleRelationSchema::from_str("empty()")
+            .unwrap()
+            .to_string(),
+        "empty()"
+    );
+}
+
+#[test]
+fn test_parse_schema_round_trip() {
+    let text = "schema uni { student(id: integer, name: string); course(code: string, credits: unsigned); enrolment(id: integer, code: string, grade: char?) }";
+    let schema = SimpleSchema::from_str(text).unwrap();
+    assert_eq!(schema.name(), &Name::new_unchecked("uni"));
+    assert_eq!(
+        schema
+            .relations()
+            .map(|r| r.name().to_string())
+            .collect::<Vec<String>>(),
+        vec!["student", "course", "enrolment"]
+    );
+    assert_eq!(schema.to_string(), text);
+    assert_eq!(
+        SimpleSchema::from_str(&schema.to_string())
+            .unwrap()
+            .to_string(),
+        text
+    );
+
+    let schema = parse_schema(
+        "
+        schema uni {
+            student(id: integer);
+            course(code: string);
+        }
+        ",
+    )
+    .unwrap();
+    assert_eq!(schema.len(), 2);
+    assert_eq!(
+        parse_schema("schema empty {}").unwrap().to_string(),
+        "schema empty {}"
+    );
+}
+
+#[test]
+fn test_parse_schema_errors() {
+    for (text, message, column) in [
+        ("schema s { r(a: int) }", "unknown domain `int`", 17),
+        (
+            "schema s { r(a: integer, a: string) }",
+            "duplicate attribute `a` in relation `r`",
+            26,
+        ),
+        (
+            "schema s { r(a: integer); r(b: string) }",
+            "duplicate relation `r` in schema `s`",
+            27,
+        ),
+    ] {
+        match parse_schema(text) {
+            Err(Error::Parse { details }) => {
+                assert!(
+                    details.to_string().contains(message),
+                    "{} did not contain {}",
+                    details,
+                    message
+                );
+                assert_eq!(details.span().start, Location::new(1, column));
+            }
+            result => panic!("expected a parse error, not {:?}", result),
+        }
+    }
+    match parse_schema("schema x { r(a: varchar) }") {
+        Err(Error::Parse { details }) => {
+            assert_eq!(details.kind(), ParseKind::Schema);
+            assert!(details
+                .to_string()
+                .starts_with("The schema could not be parsed at 1:17"));
+        }
+        result => panic!("expected a parse error, not {:?}", result),
+    }
+    assert!(matches!(
+        parse_schema("uni { r(a: integer) }"),
+        Err(Error::Parse { .. })
+    ));
+    assert!(matches!(
+        parse_schema("schema uni { r(a integer) }"),
+        Err(Error::Parse { .. })
+    ));
+}
+
+#[test]
+fn test_schema_duplicate_relation() {
+    let relation = SimpleRelationSchema::from_str("r(a: integer)").unwrap();
+    assert!(matches!(
+        SimpleSchema::new(
+            Name::new_unchecked("s"),
+            [relation.clone(), relation.clone()]
+        ),
+        Err(Error::DuplicateRelation { name }) if name.as_ref() == "r"
+    ));
+    assert_eq!(
+        SimpleSchema::new(Name::new_unchecked("s"), [relation])
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "evaluation")]
+
+use relational_algebra::{
+    ast::RelationalOp,
+    data::{Database, Relation, Tuple, Value},
+    error::Error,
+    eval::evaluate_relational,
+    simple::io::directory::{
+        read_directory, write_directory, Catalog, DirectoryOptions, FileFormat,
+    },
+    sort::{Domain, RelationSchema, Schema},
+    Name,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+fn directory(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "relational-algebra-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn write_files(path: &Path, files: &[(&str, &str)]) {
+    for (name, content) in files {
+        fs::write(path.join(name), content).unwrap();
+    }
+}
+
+fn schema_strings(catalog: &Catalog) -> Vec<String> {
+    let mut schemas: Vec<String> = catalog
+        .schema()
+        .relations()
+        .map(|schema| schema.to_string())
+        .collect();
+    schemas.sort();
+    schemas
+}
+
+#[test]
+fn test_domain_from_str() {
+    for domain in [
+        Domain::Null,
+        Domain::Boolean,
+        Domain::UnsignedInteger,
+        Domain::Binary,
+        Domain::TimestampTz,
+        Domain::Interval,
+    ] {
+        assert_eq!(Domain::from_str(&domain.to_string()).unwrap(), domain);
+    }
+    assert!(matches!(
+        Domain::from_str("int"),
+        Err(Error::InvalidDomain { .. })
+    ));
+}
+
+#[test]
+fn test_read_and_write_directory() {
+    let path = directory("uni");
+    write_files(
+        &path,
+        &[
+            (
+                "_schema.json",
+                r#"{
+                    "name": "uni",
+                    "relations": {
+                        "course": { "code": "string", "credits": "unsigned" },
+                        "enrolment": { "id": "integer", "code": "string", "grade": "char?" }
+                    }
+                }"#,
+            ),
+            ("course.json", r#"[{"code": "cs101", "credits": 3}]"#),
+            (
+                "student list.csv",
+                "id,name,born\n1,alice,2001-02-03\n2,bob,\n",
+            ),
+            ("notes.txt", "not a relation"),
+        ],
+    );
+
+    let catalog = read_directory(&path, &DirectoryOptions::default()).unwrap();
+    fs::remove_dir_all(&path).unwrap();
+    assert_eq!(catalog.schema().name().as_ref(), "uni");
+    assert_eq!(
+        schema_strings(&catalog),
+        vec![
+            "course(code: string, credits: unsigned)",
+            "enrolment(id: integer, code: string, grade: char?)",
+            "student_list(id: integer, name: string, born: date?)",
+        ]
+    );
+    assert_eq!(catalog.len(), 3);
+    assert_eq!(
+        catalog
+            .relation(&Name::new_unchecked("enrolment"))
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let query = RelationalOp::from_str("π[name](σ[id=1i64]student_list)").unwrap();
+    let result = evaluate_relational(&query, catalog.schema(), &catalog).unwrap();
+    assert_eq!(
+        result
+            .tuples()
+            .flat_map(|tuple| tuple.values().cloned().collect::<Vec<Value>>())
+            .collect::<Vec<Value>>(),
+        vec![Value::from("alice")]
+    );
+
+    let path = directory("uni-copy");
+    write_directory(
+        &path,
+        &catalog,
+        &DirectoryOptions::default().with_format(FileFormat::JsonLines),
+    )
+    .unwrap();
+    let mut files: Vec<String> = fs::read_dir(&path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            "_schema.json",
+            "course.jsonl",
+            "enrolment.jsonl",
+            "student_list.jsonl"
+        ]
+    );
+
+    let copy = read_directory(&path, &DirectoryOptions::default()).unwrap();
+    fs::remove_dir_all(&path).unwrap();
+    assert_eq!(schema_strings(&copy), schema_strings(&catalog));
+    for relation in catalog.relations() {
+        let other = copy.relation(relation.schema().name()).unwrap();
+        assert_eq!(
+            other.tuples().collect::<Vec<_>>(),
+            relation.tuples().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_read_directory_errors() {
+    let path = directory("duplicates");
+    write_files(&path, &[("a.csv", "x\n1\n"), ("a.json", "[]")]);
+    let result = read_directory(&path, &DirectoryOptions::default());
+    fs::remove_dir_all(&path).unwrap();
+    assert!(matches!(result, Err(Error::Io { .. })));
+
+    let path = directory("manifest");
+    write_files(
+        &path,
+        &[(
+            "manifest.json",
+            r#"{"name": "bad", "relations": {"a": {"x": "int"}}}"#,
+        )],
+    );
+    let result = read_directory(
+        &path,
+        &DirectoryOptions::default().with_manifest("manifest.json"),
+    );
+    fs::remove_dir_all(&path).unwrap();
+    assert!(matches!(result, Err(Error::InvalidDomain { value }) if value == "int"));
+
+    assert!(matches!(
+        read_directory(
+            std::env::temp_dir().join(format!("relational-algebra-{}-missing", std::process::id())),
+            &DirectoryOptions::default()
+        ),
+        Err(Error::Io { .. })
+    ));
+}
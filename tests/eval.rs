@@ -0,0 +1,934 @@
+#![cfg(feature = "evaluation")]
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use relational_algebra::{
+    ast::{Attribute, ExpressionList, RelationalOp, Term},
+    data::{Relation, Tuple, Value},
+    error::Error,
+    eval::{
+        analyze_expression_list, analyze_relational, evaluate_expression_list, evaluate_relational,
+        ops::{CartesianProduct, Difference, NaturalJoin, Project, Rename, Select, Union},
+    },
+    simple::{
+        data::{SimpleRelation, SimpleTuple},
+        sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    Name,
+};
+
+fn relation_schema(name: &str, attributes: &[(&str, Domain)]) -> SimpleRelationSchema {
+    SimpleRelationSchema::new(
+        Name::new_unchecked(name),
+        attributes
+            .iter()
+            .map(|(name, domain)| SimpleAttributeSchema::new(Name::new_unchecked(name), *domain)),
+    )
+    .unwrap()
+}
+
+fn example() -> (SimpleSchema, HashMap<Name, SimpleRelation>) {
+    let students = relation_schema(
+        "students",
+        &[("id", Domain::Integer), ("name", Domain::String)],
+    );
+    let enrolled = relation_schema(
+        "enrolled",
+        &[("id", Domain::Integer), ("course", Domain::String)],
+    );
+    let graduates = relation_schema(
+        "graduates",
+        &[("id", Domain::Integer), ("name", Domain::String)],
+    );
+    let schema = SimpleSchema::new(
+        Name::new_unchecked("uni"),
+        [students.clone(), enrolled.clone(), graduates.clone()],
+    )
+    .unwrap();
+
+    let mut database: HashMap<Name, SimpleRelation> = Default::default();
+    let _ = database.insert(
+        Name::new_unchecked("students"),
+        SimpleRelation::from_tuples(
+            students,
+            [
+                SimpleTuple::from(vec![Value::from(1i64), Value::from("alice")]),
+                SimpleTuple::from(vec![Value::from(2i64), Value::from("bob")]),
+                SimpleTuple::from(vec![Value::from(3i64), Value::from("carol")]),
+            ],
+        )
+        .unwrap(),
+    );
+    let _ = database.insert(
+        Name::new_unchecked("enrolled"),
+        SimpleRelation::from_tuples(
+            enrolled,
+            [
+                SimpleTuple::from(vec![Value::from(1i64), Value::from("maths")]),
+                SimpleTuple::from(vec![Value::from(1i64), Value::from("physics")]),
+                SimpleTuple::from(vec![Value::from(3i64), Value::from("maths")]),
+            ],
+        )
+        .unwrap(),
+    );
+    let _ = database.insert(
+        Name::new_unchecked("graduates"),
+        SimpleRelation::from_tuples(
+            graduates,
+            [SimpleTuple::from(vec![
+                Value::from(3i64),
+                Value::from("carol"),
+            ])],
+        )
+        .unwrap(),
+    );
+    (schema, database)
+}
+
+fn evaluate(query: &str) -> Result<SimpleRelation, Error> {
+    let (schema, database) = example();
+    evaluate_relational(&RelationalOp::from_str(query).unwrap(), &schema, &database)
+}
+
+fn column(relation: &SimpleRelation, index: usize) -> Vec<Value> {
+    relation
+        .tuples()
+        .map(|t| t.value(index).unwrap().clone())
+        .collect()
+}
+
+#[test]
+fn test_evaluate_selection_and_projection() {
+    let result = evaluate("π[name](σ[id>1i64]students)").unwrap();
+    assert_eq!(result.schema().len(), 1);
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from("bob"), Value::from("carol")]
+    );
+}
+
+#[test]
+fn test_evaluate_set_operations() {
+    assert_eq!(evaluate("students ∪ graduates").unwrap().len(), 3);
+    assert_eq!(evaluate("students ∩ graduates").unwrap().len(), 1);
+    assert_eq!(evaluate("students ∖ graduates").unwrap().len(), 2);
+    assert_eq!(evaluate("students × enrolled").unwrap().len(), 9);
+    // -0.0 and 0.0 are the same value in a set.
+    assert_eq!(
+        evaluate("π[0.0f64]students ∪ π[-0.0f64]graduates")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(matches!(
+        evaluate("students ∪ π[id]enrolled"),
+        Err(Error::IncompatibleArity { .. })
+    ));
+}
+
+#[test]
+fn test_evaluate_joins() {
+    let result = evaluate("students ⨝ enrolled").unwrap();
+    assert_eq!(result.schema().len(), 3);
+    assert_eq!(result.len(), 3);
+    assert_eq!(
+        column(&result, 1),
+        vec![
+            Value::from("alice"),
+            Value::from("alice"),
+            Value::from("carol")
+        ]
+    );
+
+    let result = evaluate("students ⨝[0=2 ∧ course=\"maths\"] enrolled").unwrap();
+    assert_eq!(result.schema().len(), 4);
+    assert_eq!(result.len(), 2);
+}
+
+#[test]
+fn test_evaluate_outer_joins() {
+    let result = evaluate("students ⟕ enrolled").unwrap();
+    assert_eq!(result.schema().len(), 3);
+    assert_eq!(
+        column(&result, 2),
+        vec![
+            Value::from("maths"),
+            Value::from("physics"),
+            Value::Null,
+            Value::from("maths")
+        ]
+    );
+    assert_eq!(
+        evaluate("σ[?course](students ⟕ enrolled)").unwrap().len(),
+        3
+    );
+
+    let result = evaluate("σ[id>1i64]students ⟗ enrolled").unwrap();
+    assert_eq!(
+        column(&result, 0),
+        vec![
+            Value::from(2i64),
+            Value::from(3i64),
+            Value::from(1i64),
+            Value::from(1i64)
+        ]
+    );
+    assert_eq!(
+        column(&result, 1),
+        vec![
+            Value::from("bob"),
+            Value::from("carol"),
+            Value::Null,
+            Value::Null
+        ]
+    );
+
+    let result = evaluate("σ[id>1i64]students ⟖[0=2] enrolled").unwrap();
+    assert_eq!(result.schema().len(), 4);
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from(3i64), Value::Null, Value::Null]
+    );
+    assert_eq!(
+        column(&result, 3),
+        vec![
+            Value::from("maths"),
+            Value::from("maths"),
+            Value::from("physics")
+        ]
+    );
+
+    // null values are ignored by aggregates.
+    let result = evaluate("γ[name, count(course)](students ⟕ enrolled)").unwrap();
+    assert_eq!(
+        column(&result, 1),
+        vec![Value::from(2i64), Value::from(0i64), Value::from(1i64)]
+    );
+}
+
+#[test]
+fn test_evaluate_nulls() {
+    // a comparison with a null is unknown, as is its negation.
+    let query = "(students ⟕ enrolled)";
+    assert_eq!(
+        evaluate(&format!("σ[¬(course=\"maths\")]{}", query))
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        evaluate(&format!("σ[course=\"maths\" ∨ true]{}", query))
+            .unwrap()
+            .len(),
+        4
+    );
+    let result = evaluate(&format!("σ[course is null]{}", query)).unwrap();
+    assert_eq!(column(&result, 1), vec![Value::from("bob")]);
+    assert_eq!(
+        evaluate(&format!("σ[course≠\"maths\" ∨ course is null]{}", query))
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        evaluate(&format!("σ[id is not null ∧ null]{}", query))
+            .unwrap()
+            .len(),
+        0
+    );
+
+    // padded attributes are nullable.
+    let result = analyze(&format!("σ[course is not null]{}", query)).unwrap();
+    assert!(!result.attribute(1).unwrap().is_nullable());
+    assert!(result.attribute(2).unwrap().is_nullable());
+    assert_eq!(
+        result.to_string(),
+        "students(id: integer, name: string, course: string?)"
+    );
+    assert_eq!(evaluate(query).unwrap().schema(), &result);
+
+    let result = evaluate(&format!("τ[course nulls first]{}", query)).unwrap();
+    assert_eq!(
+        column(&result, 2),
+        vec![
+            Value::Null,
+            Value::from("maths"),
+            Value::from("maths"),
+            Value::from("physics")
+        ]
+    );
+    let result = evaluate(&format!("τ[course↓ nulls last]{}", query)).unwrap();
+    assert_eq!(column(&result, 2)[3], Value::Null);
+
+    let mut schema = relation_schema(
+        "enrolled",
+        &[("id", Domain::Integer), ("course", Domain::String)],
+    );
+    let mut relation = SimpleRelation::new(schema.clone());
+    let tuple = SimpleTuple::from(vec![Value::from(2i64), Value::Null]);
+    assert!(matches!(
+        relation.insert(tuple.clone()),
+        Err(Error::InvalidValue { .. })
+    ));
+    schema.attribute_mut(1).unwrap().set_nullable(true);
+    let mut relation = SimpleRelation::new(schema);
+    assert!(relation.insert(tuple).unwrap());
+}
+
+#[test]
+fn test_evaluate_typed_values() {
+    assert_eq!(evaluate("σ[id>1.5dec]students").unwrap().len(), 2);
+    assert_eq!(evaluate("σ[id≤2.00dec]students").unwrap().len(), 2);
+    // a date is compared with a timestamp as midnight on that date.
+    let query = "π[id, date\"2024-02-29\"]students";
+    assert_eq!(
+        evaluate(&format!("σ[_1<timestamp\"2024-02-29T00:00:01\"]{}", query))
+            .unwrap()
+            .len(),
+        3
+    );
+    assert_eq!(
+        evaluate(&format!("σ[_1=timestamp\"2024-02-29T00:00:00\"]{}", query))
+            .unwrap()
+            .len(),
+        3
+    );
+    assert!(matches!(
+        evaluate(&format!("σ[_1=time\"00:00\"]{}", query)),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+
+    let result = evaluate("γ[sum(_1), avg(_1)](π[id, 2.50dec]students)").unwrap();
+    assert_eq!(
+        result.schema().attribute(0).unwrap().domain(),
+        &Domain::Decimal
+    );
+    assert_eq!(
+        result
+            .tuples()
+            .next()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<Value>>(),
+        vec![
+            Value::parse(Domain::Decimal, "7.50").unwrap(),
+            Value::parse(Domain::Decimal, "2.5").unwrap()
+        ]
+    );
+}
+
+#[test]
+fn test_evaluate_scalar_expressions() {
+    let result =
+        evaluate("π[id × 10i64/tens, upper(name) ‖ \"!\"](σ[id mod 2i64 = 1i64]students)").unwrap();
+    assert_eq!(attribute_names(result.schema()), vec!["tens", "_1"]);
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from(10i64), Value::from(30i64)]
+    );
+    assert_eq!(
+        column(&result, 1),
+        vec![Value::from("ALICE!"), Value::from("CAROL!")]
+    );
+    assert_eq!(
+        evaluate("σ[length(name) - id > 3i64]students")
+            .unwrap()
+            .len(),
+        1
+    );
+    let result =
+        evaluate("π[case when id < 2i64 then \"first\" else substr(name, 2i64) end]students")
+            .unwrap();
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from("first"), Value::from("ob"), Value::from("arol")]
+    );
+
+    // null propagates through operations, but not through coalesce.
+    let query = "(students ⟕ enrolled)";
+    let result = evaluate(&format!(
+        "π[name, coalesce(upper(course), \"none\") ‖ \"?\", id + null]{}",
+        query
+    ))
+    .unwrap();
+    assert_eq!(
+        column(&result, 1),
+        vec![
+            Value::from("MATHS?"),
+            Value::from("PHYSICS?"),
+            Value::from("none?"),
+            Value::from("MATHS?")
+        ]
+    );
+    assert_eq!(column(&result, 2)[0], Value::Null);
+
+    let result = evaluate(
+        "π[date\"2024-01-31\" + interval\"P1M\", date\"2024-03-01\" - date\"2024-02-01\", 2.50dec × id]students",
+    )
+    .unwrap();
+    let values = result
+        .tuples()
+        .next()
+        .unwrap()
+        .values()
+        .cloned()
+        .collect::<Vec<Value>>();
+    assert_eq!(
+        values,
+        vec![
+            Value::parse(Domain::Timestamp, "2024-02-29T00:00:00").unwrap(),
+            Value::from(29i64),
+            Value::parse(Domain::Decimal, "2.50").unwrap()
+        ]
+    );
+
+    // a float is rounded to at most the places it can represent.
+    let result = evaluate(
+        "π[round(1.25f64, 1i64), round(1.5f64, 400i64), round(1e300f64, 20i64)](σ[id = 1i64]students)",
+    )
+    .unwrap();
+    assert_eq!(
+        result
+            .tuples()
+            .next()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<Value>>(),
+        vec![Value::Float(1.3), Value::Float(1.5), Value::Float(1e300)]
+    );
+
+    assert!(matches!(
+        evaluate("π[id ÷ (id - 1i64)]students"),
+        Err(Error::ArithmeticOverflow { .. })
+    ));
+    assert!(matches!(
+        evaluate("π[9223372036854775807i64 + id]students"),
+        Err(Error::ArithmeticOverflow { .. })
+    ));
+}
+
+#[test]
+fn test_evaluate_membership_range_and_patterns() {
+    let names = |query: &str| column(&evaluate(query).unwrap(), 1);
+    assert_eq!(
+        names("σ[id ∈ {1i64, 3i64, 5.0f64}]students"),
+        vec![Value::from("alice"), Value::from("carol")]
+    );
+    assert_eq!(
+        names("σ[id between 2i64 and 3i64]students"),
+        vec![Value::from("bob"), Value::from("carol")]
+    );
+    assert_eq!(names("σ[3i64 ≤ id]students"), vec![Value::from("carol")]);
+    assert_eq!(
+        names("σ[name like \"_o%\" ∨ name ilike \"%LIC_\"]students"),
+        vec![Value::from("alice"), Value::from("bob")]
+    );
+    assert_eq!(
+        names("σ[name not like \"%o%\"]students"),
+        vec![Value::from("alice")]
+    );
+    assert_eq!(
+        names("σ[¬(name like \"a%\") ∧ (id>2i64 ∨ name~\"^b\")]students"),
+        vec![Value::from("bob"), Value::from("carol")]
+    );
+    // pattern characters, including those special to regular expressions, may be escaped.
+    assert_eq!(
+        evaluate("σ[\"a%.b\" like \"a\\\\%.b\"]students")
+            .unwrap()
+            .len(),
+        3
+    );
+    assert_eq!(
+        evaluate("σ[\"ab.b\" like \"a\\\\%.b\"]students")
+            .unwrap()
+            .len(),
+        0
+    );
+
+    // a list with a null is unknown, rather than false, where nothing else matches.
+    let query = "(students ⟕ enrolled)";
+    assert_eq!(
+        evaluate(&format!("σ[¬(course ∈ {{\"maths\", null}})]{}", query))
+            .unwrap()
+            .len(),
+        0
+    );
+    assert_eq!(
+        evaluate(&format!("σ[¬(course ∈ {{\"maths\"}})]{}", query))
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(matches!(
+        evaluate("σ[id like \"1%\"]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        evaluate("σ[name between 1i64 and 2i64]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+}
+
+#[test]
+fn test_evaluate_case_patterns() {
+    let result = evaluate(
+        "π[case when name like \"a%\" then \"a\" when name ~ \"^b\" then \"b\" else \"other\" end]students",
+    )
+    .unwrap();
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from("a"), Value::from("b"), Value::from("other")]
+    );
+    assert_eq!(
+        column(
+            &evaluate("σ[(case when name ilike \"%O%\" then 1i64 else 0i64 end) = 1i64]students")
+                .unwrap(),
+            1
+        ),
+        vec![Value::from("bob"), Value::from("carol")]
+    );
+
+    // the patterns of a computed attribute are compiled before any tuple is evaluated.
+    let query = RelationalOp::from_str("π[case when name ~ \"(a\" then 1i64 end]students").unwrap();
+    let projected: Vec<_> = query
+        .as_projection()
+        .unwrap()
+        .attributes()
+        .cloned()
+        .collect();
+    let (schema, _) = example();
+    let empty = SimpleRelation::new(
+        schema
+            .relation(&Name::new_unchecked("students"))
+            .unwrap()
+            .clone(),
+    );
+    assert!(matches!(
+        empty.extended_project(&projected),
+        Err(Error::InvalidPattern { pattern }) if pattern == "(a"
+    ));
+}
+
+#[test]
+fn test_evaluate_semi_and_anti_joins() {
+    let result = evaluate("students ⋉ enrolled").unwrap();
+    assert_eq!(result.schema().len(), 2);
+    assert_eq!(
+        column(&result, 1),
+        vec![Value::from("alice"), Value::from("carol")]
+    );
+    assert_eq!(
+        column(&evaluate("students ▷ enrolled").unwrap(), 1),
+        vec![Value::from("bob")]
+    );
+    let result = evaluate("graduates ⋊ enrolled").unwrap();
+    assert_eq!(column(&result, 1), vec![Value::from("maths")]);
+
+    // criteria refer to the attributes of both operands, left then right.
+    let result = evaluate("students ⋉[0<2 ∧ 3=\"maths\"] enrolled").unwrap();
+    assert_eq!(
+        column(&result, 1),
+        vec![Value::from("alice"), Value::from("bob")]
+    );
+    let result = evaluate("students ▷[0=2] σ[course=\"physics\"]enrolled").unwrap();
+    assert_eq!(
+        column(&result, 1),
+        vec![Value::from("bob"), Value::from("carol")]
+    );
+    assert!(evaluate("students ⋉[0=4] enrolled").is_err());
+}
+
+#[test]
+fn test_evaluate_division() {
+    let result = evaluate("enrolled ÷ π[course]enrolled").unwrap();
+    assert_eq!(result.schema().len(), 1);
+    assert_eq!(column(&result, 0), vec![Value::from(1i64)]);
+    assert_eq!(
+        column(
+            &evaluate("enrolled ÷ π[course](σ[course=\"maths\"]enrolled)").unwrap(),
+            0
+        ),
+        vec![Value::from(1i64), Value::from(3i64)]
+    );
+    // every student is enrolled in every one of no courses.
+    assert_eq!(
+        evaluate("enrolled ÷ π[course](σ[course=\"art\"]enrolled)")
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_evaluate_rename_and_order() {
+    let result = evaluate("τ[student](ρ[name/student]students)").unwrap();
+    assert!(result
+        .schema()
+        .has_attribute(&Name::new_unchecked("student")));
+
+    let result = evaluate("τ[course, id](enrolled)").unwrap();
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from(1i64), Value::from(3i64), Value::from(1i64)]
+    );
+
+    let result = evaluate("τ[course↓, id↓](enrolled)").unwrap();
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from(1i64), Value::from(3i64), Value::from(1i64)]
+    );
+    assert_eq!(
+        column(&result, 1),
+        vec![
+            Value::from("physics"),
+            Value::from("maths"),
+            Value::from("maths")
+        ]
+    );
+
+    let result =
+        evaluate("τ[name collate nocase](students ∪ ρ[_0/id, _1/name]π[4i64, \"Bob\"]students)")
+            .unwrap();
+    assert_eq!(
+        column(&result, 1),
+        vec![
+            Value::from("alice"),
+            Value::from("bob"),
+            Value::from("Bob"),
+            Value::from("carol")
+        ]
+    );
+    assert!(matches!(
+        evaluate("τ[name collate klingon]students"),
+        Err(Error::UnsupportedCollation { .. })
+    ));
+}
+
+#[test]
+fn test_evaluate_errors() {
+    assert!(matches!(
+        evaluate("σ[id=1i64]teachers"),
+        Err(Error::RelationDoesNotExist { .. })
+    ));
+    assert!(matches!(
+        evaluate("π[age]students"),
+        Err(Error::AttributeDoesNotExist { .. })
+    ));
+    assert!(matches!(
+        evaluate("π[7]students"),
+        Err(Error::AttributeIndexInvalid { index: 7 })
+    ));
+    assert!(matches!(
+        evaluate("σ[name=1i64]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        evaluate("students ⨝[id=id] enrolled"),
+        Err(Error::AmbiguousAttribute { name }) if name.as_ref() == "id"
+    ));
+}
+
+#[test]
+fn test_evaluate_expression_list() {
+    let (schema, database) = example();
+    let list =
+        ExpressionList::from_str("A ≔ σ[course=\"maths\"]enrolled; π[name](students ⨝ A)").unwrap();
+    let results = evaluate_expression_list(&list, &schema, &database).unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].schema().name(), &Name::new_unchecked("A"));
+    assert_eq!(
+        column(&results[1], 0),
+        vec![Value::from("alice"), Value::from("carol")]
+    );
+}
+
+fn relation(database: &HashMap<Name, SimpleRelation>, name: &str) -> SimpleRelation {
+    database.get(&Name::new_unchecked(name)).unwrap().clone()
+}
+
+#[test]
+fn test_ops_chained() {
+    let (_, database) = example();
+    let result = relation(&database, "students")
+        .natural_join(relation(&database, "enrolled"))
+        .unwrap()
+        .select(Term::equals(
+            Name::new_unchecked("course"),
+            Value::from("maths"),
+        ))
+        .unwrap()
+        .project(&[Name::new_unchecked("name").into()])
+        .unwrap();
+    assert_eq!(result.schema().name(), &Name::new_unchecked("students"));
+    assert_eq!(
+        result
+            .schema()
+            .attributes()
+            .map(|a| a.name().to_string())
+            .collect::<Vec<String>>(),
+        vec!["name".to_string()]
+    );
+    assert_eq!(
+        column(&result, 0),
+        vec![Value::from("alice"), Value::from("carol")]
+    );
+}
+
+#[test]
+fn test_ops_output_schemas() {
+    let (_, database) = example();
+    let product = relation(&database, "students")
+        .cartesian_product(relation(&database, "enrolled"))
+        .unwrap();
+    assert_eq!(product.schema().len(), 4);
+    assert_eq!(
+        product.schema().attribute(3).unwrap().domain(),
+        &Domain::String
+    );
+
+    let renamed = relation(&database, "graduates")
+        .rename(Attribute::Index(1), Name::new_unchecked("name"))
+        .unwrap()
+        .rename(
+            Name::new_unchecked("id").into(),
+            Name::new_unchecked("student"),
+        )
+        .unwrap();
+    assert_eq!(
+        renamed
+            .schema()
+            .attribute_index(&Name::new_unchecked("student")),
+        Some(0)
+    );
+
+    let all = relation(&database, "students")
+        .union(relation(&database, "graduates"))
+        .unwrap()
+        .difference(relation(&database, "graduates"))
+        .unwrap();
+    assert_eq!(all.len(), 2);
+    assert!(matches!(
+        relation(&database, "students").union(renamed.project(&[0.into()]).unwrap()),
+        Err(Error::IncompatibleArity {
+            expected: 2,
+            given: 1,
+            ..
+        })
+    ));
+}
+
+fn analyze(query: &str) -> Result<SimpleRelationSchema, Error> {
+    let (schema, _) = example();
+    analyze_relational(&RelationalOp::from_str(query).unwrap(), &schema)
+}
+
+fn attribute_names(schema: &SimpleRelationSchema) -> Vec<String> {
+    schema.attributes().map(|a| a.name().to_string()).collect()
+}
+
+#[test]
+fn test_analyze_output_schemas() {
+    let result = analyze("π[name, 1i64](σ[id>1u64]students)").unwrap();
+    assert_eq!(result.name(), &Name::new_unchecked("students"));
+    assert_eq!(attribute_names(&result), vec!["name", "_1"]);
+    assert_eq!(result.attribute(1).unwrap().domain(), &Domain::Integer);
+
+    let result = analyze("ρ[id/student](students ⨝ enrolled)").unwrap();
+    assert_eq!(attribute_names(&result), vec!["student", "name", "course"]);
+
+    let result = analyze("students ⨝[0=2] enrolled").unwrap();
+    assert_eq!(attribute_names(&result), vec!["id", "name", "id", "course"]);
+
+    let (schema, _) = example();
+    let list = ExpressionList::from_str("A ≔ γ[course]enrolled; A ∪ π[name]students").unwrap();
+    let results = analyze_expression_list(&list, &schema).unwrap();
+    assert_eq!(results[0].name(), &Name::new_unchecked("A"));
+    assert_eq!(attribute_names(&results[1]), vec!["course"]);
+}
+
+#[test]
+fn test_analyze_scalar_expressions() {
+    let result = analyze("π[id + 1.5f64/next, round(2.555dec, 2i64), name ‖ 'x']students").unwrap();
+    assert_eq!(attribute_names(&result), vec!["next", "_1", "_2"]);
+    assert_eq!(result.attribute(0).unwrap().domain(), &Domain::Float);
+    assert_eq!(result.attribute(1).unwrap().domain(), &Domain::Decimal);
+    assert_eq!(result.attribute(2).unwrap().domain(), &Domain::String);
+    assert!(!result.attribute(0).unwrap().is_nullable());
+
+    let result = analyze("π[length(course)](students ⟕ enrolled)").unwrap();
+    assert!(result.attribute(0).unwrap().is_nullable());
+    assert!(matches!(
+        analyze("σ[name + 1i64 = 2i64]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        analyze("π[substr(name, 1.5f64)]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+}
+
+#[test]
+fn test_analyze_errors() {
+    assert!(matches!(
+        analyze("teachers ∪ students"),
+        Err(Error::RelationDoesNotExist { .. })
+    ));
+    assert!(matches!(
+        analyze("ρ[age/years]students"),
+        Err(Error::AttributeDoesNotExist { .. })
+    ));
+    assert!(matches!(
+        analyze("ρ[name/id]students"),
+        Err(Error::DuplicateAttribute { name }) if name.as_ref() == "id"
+    ));
+    assert!(matches!(
+        evaluate("ρ[name/id]students"),
+        Err(Error::DuplicateAttribute { .. })
+    ));
+    assert_eq!(
+        attribute_names(&analyze("ρ[id/name, name/id]students").unwrap()),
+        vec!["name", "id"]
+    );
+    assert!(matches!(
+        analyze("σ[id=1i64](students × enrolled)"),
+        Err(Error::AmbiguousAttribute { .. })
+    ));
+    assert!(matches!(
+        analyze("τ[2]students"),
+        Err(Error::AttributeIndexInvalid { index: 2 })
+    ));
+    assert!(matches!(
+        analyze("σ[name=1i64]students"),
+        Err(Error::IncompatibleTypes {
+            lhs_domain: Domain::String,
+            rhs_domain: Domain::Integer
+        })
+    ));
+    assert!(analyze("students ∪ ρ[course/name]enrolled").is_ok());
+    assert!(matches!(
+        analyze("students ∖ π[course, id]enrolled"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        analyze("σ[name~\"(a\"]students"),
+        Err(Error::InvalidPattern { pattern }) if pattern == "(a"
+    ));
+    // a pattern taken from an attribute can only be compiled during evaluation.
+    assert!(analyze("σ[name~_2](π[id, name, \"(a\"]students)").is_ok());
+    assert!(matches!(
+        evaluate("σ[name~_2](π[id, name, \"(a\"]students)"),
+        Err(Error::InvalidPattern { .. })
+    ));
+    assert!(matches!(
+        analyze("σ[id~\"1.*\"]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        analyze("enrolled ÷ students"),
+        Err(Error::AttributeDoesNotExist { .. })
+    ));
+    assert!(matches!(
+        analyze("enrolled ÷ ρ[id/course]π[id]students"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+    assert!(matches!(
+        analyze("enrolled ÷ enrolled"),
+        Err(Error::NullaryFactsNotAllowed)
+    ));
+}
+
+#[test]
+fn test_evaluate_aggregates() {
+    let result =
+        evaluate("γ[course, count(*)/students, max(id), avg(id), sum(distinct id)]enrolled")
+            .unwrap();
+    assert_eq!(
+        result
+            .schema()
+            .attributes()
+            .map(|a| (a.name().to_string(), *a.domain()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("course".to_string(), Domain::String),
+            ("students".to_string(), Domain::Integer),
+            ("max_id".to_string(), Domain::Integer),
+            ("avg_id".to_string(), Domain::Float),
+            ("sum_id".to_string(), Domain::Integer),
+        ]
+    );
+    assert_eq!(
+        result.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        vec![
+            SimpleTuple::from(vec![
+                Value::from("maths"),
+                Value::from(2i64),
+                Value::from(3i64),
+                Value::from(2.0),
+                Value::from(4i64),
+            ]),
+            SimpleTuple::from(vec![
+                Value::from("physics"),
+                Value::from(1i64),
+                Value::from(1i64),
+                Value::from(1.0),
+                Value::from(1i64),
+            ]),
+        ]
+    );
+
+    // with no grouping attributes the whole relation is one group.
+    let result = evaluate("γ[count(*), count(distinct id), min(course)]enrolled").unwrap();
+    assert_eq!(
+        result.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        vec![SimpleTuple::from(vec![
+            Value::from(3i64),
+            Value::from(2i64),
+            Value::from("maths"),
+        ])]
+    );
+    // ...even when it is empty, when only count is not null.
+    let result =
+        evaluate("γ[count(*)/n, count(id), sum(id), max(course)](σ[id>9i64]enrolled)").unwrap();
+    assert_eq!(
+        result.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        vec![SimpleTuple::from(vec![
+            Value::from(0i64),
+            Value::from(0i64),
+            Value::Null,
+            Value::Null,
+        ])]
+    );
+    let result = evaluate("γ[sum(id)/s, count(id)/n](σ[id is null]enrolled)").unwrap();
+    assert_eq!(
+        result
+            .schema()
+            .attributes()
+            .map(|a| (a.name().to_string(), a.is_nullable()))
+            .collect::<Vec<_>>(),
+        vec![("s".to_string(), true), ("n".to_string(), false)]
+    );
+    assert_eq!(
+        result.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        vec![SimpleTuple::from(vec![Value::Null, Value::from(0i64)])]
+    );
+    assert_eq!(
+        evaluate("γ[course, count(*)](σ[id>9i64]enrolled)")
+            .unwrap()
+            .len(),
+        0
+    );
+
+    assert!(matches!(
+        evaluate("γ[sum(_1)](π[course, 9223372036854775807i64]enrolled)"),
+        Err(Error::ArithmeticOverflow { .. })
+    ));
+
+    assert!(matches!(
+        evaluate("γ[sum(course)]enrolled"),
+        Err(Error::IncompatibleTypes { .. })
+    ));
+}
@@ -2,6 +2,7 @@
 mod tests {
     use relational_algebra::{ast::RelationalOp, graph::relational_to_graphviz, Name};
     use simple_dot::writer::write_graph_to_string;
+    use std::str::FromStr;
 
     #[test]
     fn test_generate_dot() {
@@ -16,4 +17,21 @@ mod tests {
 
         println!("{}", write_graph_to_string(&graph).unwrap());
     }
+
+    #[test]
+    fn test_generate_dot_group() {
+        let expr = RelationalOp::from_str("γ[course, count(*)/n]enrolled").unwrap();
+        let dot = write_graph_to_string(&relational_to_graphviz(&expr).unwrap()).unwrap();
+        println!("{}", dot);
+        assert!(dot.contains("course, count(*)/n"));
+    }
+
+    #[test]
+    fn test_generate_dot_semi_and_anti_joins() {
+        let expr = RelationalOp::from_str("students ⋉ enrolled ▷[0=2] graduates").unwrap();
+        let dot = write_graph_to_string(&relational_to_graphviz(&expr).unwrap()).unwrap();
+        println!("{}", dot);
+        assert!(dot.contains("⋉"));
+        assert!(dot.contains("▷\\n0=2"));
+    }
 }
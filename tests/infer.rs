@@ -0,0 +1,218 @@
+#![cfg(feature = "simple_data")]
+
+use relational_algebra::{
+    data::{Relation, Tuple},
+    error::Error,
+    simple::{
+        io::{
+            csv::{self, CsvOptions, Header},
+            infer::{sanitize_name, InferredSchema},
+            json::{self, JsonFormat, JsonOptions},
+        },
+        sort::SimpleRelationSchema,
+    },
+    sort::{Domain, RelationSchema},
+    Name,
+};
+
+fn name() -> Name {
+    Name::new_unchecked("sample")
+}
+
+fn domains(inferred: &InferredSchema) -> Vec<Domain> {
+    inferred
+        .columns()
+        .iter()
+        .map(|column| column.domain())
+        .collect()
+}
+
+#[test]
+fn test_sanitize_name() {
+    assert_eq!(sanitize_name("price"), Some(Name::new_unchecked("price")));
+    assert_eq!(
+        sanitize_name(" Unit Price ($) "),
+        Some(Name::new_unchecked("Unit_Price"))
+    );
+    assert_eq!(
+        sanitize_name("2nd-place"),
+        Some(Name::new_unchecked("_2nd_place"))
+    );
+    assert_eq!(sanitize_name("_id"), Some(Name::new_unchecked("_id")));
+    assert_eq!(sanitize_name("größe"), Some(Name::new_unchecked("größe")));
+    assert_eq!(sanitize_name("%%"), None);
+    assert_eq!(sanitize_name(&"x".repeat(200)).unwrap().as_ref().len(), 127);
+}
+
+#[test]
+fn test_infer_csv_domains() {
+    let text = "\
+flag,count,big,ratio,day,at,label,empty
+true,1,1,1,2024-02-29,2024-02-29T12:00:00,a,
+false,-2,18446744073709551615,2.5,2024-03-01,2024-02-29 13:00:00,2,
+,3,3,-1.5e3,,,,
+";
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &CsvOptions::default(), 10).unwrap();
+    assert!(inferred.is_complete());
+    assert_eq!(inferred.records(), 3);
+    assert_eq!(
+        domains(&inferred),
+        vec![
+            Domain::Boolean,
+            Domain::Integer,
+            Domain::UnsignedInteger,
+            Domain::Float,
+            Domain::Date,
+            Domain::Timestamp,
+            Domain::String,
+            Domain::String,
+        ]
+    );
+    assert_eq!(
+        inferred
+            .columns()
+            .iter()
+            .map(|column| column.is_nullable())
+            .collect::<Vec<bool>>(),
+        vec![true, false, false, false, true, true, true, true]
+    );
+    assert_eq!(inferred.columns()[1].confidence(), 1.0);
+    assert_eq!(inferred.columns()[7].values(), 0);
+    assert_eq!(inferred.confidence(), 0.0);
+
+    // words that parse as floats do not make a column of floats.
+    let text = "name,ratio\nNan,NaN\nInf,0.5\ninfinity,-inf\n";
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &CsvOptions::default(), 10).unwrap();
+    assert_eq!(
+        inferred.schema().to_string(),
+        "sample(name: string, ratio: string)"
+    );
+}
+
+#[test]
+fn test_infer_csv_sample() {
+    let text = "1;a\n2;b\nthree;c\n";
+    let options = CsvOptions::default()
+        .with_delimiter(b';')
+        .with_header(Header::Absent);
+
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &options, 2).unwrap();
+    assert!(!inferred.is_complete());
+    assert_eq!(
+        inferred.schema().to_string(),
+        "sample(column_1: integer, column_2: string)"
+    );
+
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &options, 3).unwrap();
+    assert!(inferred.is_complete());
+    assert_eq!(domains(&inferred), vec![Domain::String, Domain::String]);
+}
+
+#[test]
+fn test_infer_and_read_csv() {
+    let text = "Item ID,Item ID,Unit Price\n1,a,2.50\n2,b,3\n";
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &CsvOptions::default(), 10).unwrap();
+    assert_eq!(
+        inferred.schema().to_string(),
+        "sample(Item_ID: integer, Item_ID_2: string, Unit_Price: float)"
+    );
+    assert_eq!(inferred.columns()[2].label(), "Unit Price");
+
+    let relation = csv::read_relation(
+        "Unit Price,Item ID\n1.5,7\n".as_bytes(),
+        SimpleRelationSchema::new(name(), inferred.schema().attributes().cloned().step_by(2))
+            .unwrap(),
+        &CsvOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(relation.len(), 1);
+}
+
+#[test]
+fn test_infer_and_read_csv_positional_names() {
+    let text = "a,,a,%%\n1,x,true,2024-02-29\n2,y,false,\n";
+    let inferred = csv::infer_schema(text.as_bytes(), name(), &CsvOptions::default(), 10).unwrap();
+    assert_eq!(
+        inferred.schema().to_string(),
+        "sample(a: integer, column_2: string, a_2: boolean, column_4: date?)"
+    );
+
+    let relation = csv::read_relation(
+        text.as_bytes(),
+        inferred.schema().clone(),
+        &CsvOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(relation.len(), 2);
+    assert_eq!(
+        relation
+            .tuples()
+            .map(|tuple| tuple
+                .values()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>())
+            .collect::<Vec<Vec<String>>>()[0],
+        vec!["1", "\"x\"", "true", "2024-02-29"]
+    );
+}
+
+#[test]
+fn test_infer_and_read_json() {
+    let text = "\
+{\"id\": 1, \"unit price\": 2.5, \"tags\": \"x\", \"seen\": null}
+
+{\"id\": 2, \"unit price\": \"3\", \"tags\": \"y\", \"seen\": \"2024-02-29\"}
+{\"id\": 3, \"unit price\": 4, \"tags\": \"z\", \"seen\": null}
+";
+    let options = JsonOptions::default().with_format(JsonFormat::Lines);
+    let inferred = json::infer_schema(text.as_bytes(), name(), &options, 2).unwrap();
+    assert!(!inferred.is_complete());
+    assert_eq!(
+        inferred.schema().to_string(),
+        "sample(id: integer, unit_price: float, tags: string, seen: date?)"
+    );
+    assert_eq!(inferred.columns()[3].confidence(), 0.5);
+
+    let relation = json::read_relation(text.as_bytes(), inferred.into_schema(), &options).unwrap();
+    assert_eq!(relation.len(), 3);
+
+    let inferred = json::infer_schema(
+        r#"[{"id": 1, "v": true}, {"id": 2, "v": false}]"#.as_bytes(),
+        name(),
+        &JsonOptions::default(),
+        2,
+    )
+    .unwrap();
+    assert!(inferred.is_complete());
+    assert_eq!(domains(&inferred), vec![Domain::Integer, Domain::Boolean]);
+}
+
+#[test]
+fn test_infer_json_errors() {
+    let options = JsonOptions::default();
+    assert!(matches!(
+        json::infer_schema(
+            r#"[{"id": 1}, {"id": 2, "x": 1}]"#.as_bytes(),
+            name(),
+            &options,
+            10
+        ),
+        Err(Error::UnexpectedKey { row: 2, .. })
+    ));
+    assert!(matches!(
+        json::infer_schema(
+            r#"[{"id": 1, "x": 1}, {"id": 2}]"#.as_bytes(),
+            name(),
+            &options,
+            10
+        ),
+        Err(Error::MissingKey { row: 2, .. })
+    ));
+    assert!(matches!(
+        json::infer_schema(r#"[{"id": [1]}]"#.as_bytes(), name(), &options, 10),
+        Err(Error::InvalidValue {
+            location: Some((1, 1)),
+            ..
+        })
+    ));
+}
@@ -0,0 +1,220 @@
+#![cfg(feature = "simple_data")]
+
+use relational_algebra::{
+    data::{Relation, Value},
+    error::Error,
+    simple::{
+        data::{SimpleRelation, SimpleTuple},
+        io::json::{
+            read_relation, read_relation_from_path, write_relation, write_relation_to_path,
+            JsonFormat, JsonOptions,
+        },
+        sort::{SimpleAttributeSchema, SimpleRelationSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema},
+    Name,
+};
+
+fn items() -> SimpleRelationSchema {
+    SimpleRelationSchema::new(
+        Name::new_unchecked("items"),
+        [
+            SimpleAttributeSchema::new(Name::new_unchecked("id"), Domain::UnsignedInteger),
+            SimpleAttributeSchema::new(Name::new_unchecked("label"), Domain::String),
+            SimpleAttributeSchema::new(Name::new_unchecked("price"), Domain::Decimal)
+                .with_nullable(true),
+        ],
+    )
+    .unwrap()
+}
+
+fn read(text: &str, format: JsonFormat) -> Result<SimpleRelation, Error> {
+    read_relation(
+        text.as_bytes(),
+        items(),
+        &JsonOptions::default().with_format(format),
+    )
+}
+
+fn write(relation: &SimpleRelation, options: &JsonOptions) -> String {
+    let mut output: Vec<u8> = Vec::new();
+    write_relation(&mut output, relation, options).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn item(id: u64, label: &str, price: Option<&str>) -> SimpleTuple {
+    SimpleTuple::from(vec![
+        Value::from(id),
+        Value::from(label),
+        price
+            .map(|s| Value::parse(Domain::Decimal, s).unwrap())
+            .unwrap_or(Value::Null),
+    ])
+}
+
+#[test]
+fn test_read_formats() {
+    let expected = vec![
+        item(1, "pen", Some("1.50")),
+        item(2, "ink", Some("12.5")),
+        item(3, "pad", None),
+    ];
+    let relation = read(
+        r#"[
+            {"label": "pen", "id": 1, "price": "1.50"},
+            {"id": 2, "label": "ink", "price": 12.5},
+            {"id": "3", "label": "pad", "price": null}
+        ]"#,
+        JsonFormat::Array,
+    )
+    .unwrap();
+    assert_eq!(
+        relation.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        expected
+    );
+
+    let relation = read(
+        "{\"id\": 1, \"label\": \"pen\", \"price\": \"1.50\"}\n\n{\"id\": 2, \"label\": \"ink\", \"price\": 12.5}\n{\"id\": 3, \"label\": \"pad\", \"price\": null}\n",
+        JsonFormat::Lines,
+    )
+    .unwrap();
+    assert_eq!(
+        relation.tuples().cloned().collect::<Vec<SimpleTuple>>(),
+        expected
+    );
+}
+
+#[test]
+fn test_read_errors() {
+    let error = read(
+        "{\"id\": 1, \"label\": \"pen\", \"price\": null}\n\n{\"id\": 2, \"label\": \"ink\"}\n",
+        JsonFormat::Lines,
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::MissingKey { row: 3, .. }));
+    assert_eq!(
+        error.to_string(),
+        "The record at row 3 has no value for the attribute `price`."
+    );
+
+    let error = read(
+        r#"[{"id": 1, "label": "pen", "price": null, "colour": "red"}]"#,
+        JsonFormat::Array,
+    )
+    .unwrap_err();
+    assert!(matches!(&error, Error::UnexpectedKey { key, row: 1 } if key == "colour"));
+
+    assert!(matches!(
+        read(
+            r#"[{"id": 1, "label": "pen", "price": null}, {"id": -2, "label": "ink", "price": null}]"#,
+            JsonFormat::Array,
+        ),
+        Err(Error::InvalidValue {
+            expecting_domain: Domain::UnsignedInteger,
+            location: Some((2, 1)),
+            ..
+        })
+    ));
+    assert!(matches!(
+        read(
+            r#"[{"id": 1, "label": null, "price": null}]"#,
+            JsonFormat::Array,
+        ),
+        Err(Error::InvalidValue {
+            expecting_domain: Domain::String,
+            location: Some((1, 2)),
+            ..
+        })
+    ));
+    assert!(matches!(
+        read(r#"{"id": 1}"#, JsonFormat::Array),
+        Err(Error::Io { .. })
+    ));
+    assert!(matches!(
+        read("[1, 2]", JsonFormat::Lines),
+        Err(Error::Io { .. })
+    ));
+}
+
+#[test]
+fn test_write_formats() {
+    let relation = SimpleRelation::from_tuples(
+        items(),
+        [item(1, "pen", Some("1.50")), item(2, "\"ink\"", None)],
+    )
+    .unwrap();
+    assert_eq!(
+        write(&relation, &JsonOptions::default()),
+        "[{\"id\":1,\"label\":\"pen\",\"price\":\"1.50\"},{\"id\":2,\"label\":\"\\\"ink\\\"\",\"price\":null}]\n"
+    );
+    assert_eq!(
+        write(
+            &relation,
+            &JsonOptions::default().with_format(JsonFormat::Lines)
+        ),
+        "{\"id\":1,\"label\":\"pen\",\"price\":\"1.50\"}\n{\"id\":2,\"label\":\"\\\"ink\\\"\",\"price\":null}\n"
+    );
+    assert!(write(&relation, &JsonOptions::default().with_pretty(true))
+        .starts_with("[\n  {\n    \"id\": 1,\n    \"label\": \"pen\",\n"));
+}
+
+#[test]
+fn test_round_trip_domains() {
+    let domains = [
+        Domain::Boolean,
+        Domain::Byte,
+        Domain::Integer,
+        Domain::Float,
+        Domain::Float,
+        Domain::Char,
+        Domain::Binary,
+        Domain::Date,
+        Domain::TimestampTz,
+        Domain::Interval,
+    ];
+    let schema = SimpleRelationSchema::new(
+        Name::new_unchecked("values"),
+        domains.iter().enumerate().map(|(i, domain)| {
+            SimpleAttributeSchema::new(Name::new_unchecked(&format!("a{}", i)), *domain)
+        }),
+    )
+    .unwrap();
+    let values = [
+        "false",
+        "255",
+        "-42",
+        "0.25",
+        "NaN",
+        "x",
+        "00ff",
+        "2024-02-29",
+        "2024-02-29T12:30:00+01:00",
+        "P1M",
+    ];
+    let relation = SimpleRelation::from_tuples(
+        schema.clone(),
+        [SimpleTuple::from(
+            domains
+                .iter()
+                .zip(values)
+                .map(|(domain, s)| Value::parse(*domain, s).unwrap())
+                .collect::<Vec<Value>>(),
+        )],
+    )
+    .unwrap();
+
+    let options = JsonOptions::default().with_format(JsonFormat::Lines);
+    let path =
+        std::env::temp_dir().join(format!("relational-algebra-{}.jsonl", std::process::id()));
+    write_relation_to_path(&path, &relation, &options).unwrap();
+    let result = read_relation_from_path(&path, schema, &options);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        result
+            .unwrap()
+            .tuples()
+            .cloned()
+            .collect::<Vec<SimpleTuple>>(),
+        relation.tuples().cloned().collect::<Vec<SimpleTuple>>()
+    );
+}
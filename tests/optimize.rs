@@ -0,0 +1,284 @@
+#![cfg(feature = "evaluation")]
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use relational_algebra::{
+    ast::{ExpressionList, RelationalOp},
+    data::{Statistics, Value},
+    eval::{analyze_relational, evaluate_relational},
+    optimize::{
+        join_order::JoinOrderer,
+        rules::{MergeProjections, PushSelectionDown, SplitConjunctiveSelection},
+        Optimizer,
+    },
+    simple::{
+        data::{SimpleAttributeStatistics, SimpleRelation, SimpleStatistics, SimpleTuple},
+        sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema},
+    },
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    Name,
+};
+
+fn relation_schema(name: &str, attributes: &[(&str, Domain)]) -> SimpleRelationSchema {
+    SimpleRelationSchema::new(
+        Name::new_unchecked(name),
+        attributes
+            .iter()
+            .map(|(name, domain)| SimpleAttributeSchema::new(Name::new_unchecked(name), *domain)),
+    )
+    .unwrap()
+}
+
+fn schema() -> SimpleSchema {
+    SimpleSchema::new(
+        Name::new_unchecked("uni"),
+        [
+            relation_schema(
+                "students",
+                &[("id", Domain::Integer), ("name", Domain::String)],
+            ),
+            relation_schema(
+                "enrolled",
+                &[("id", Domain::Integer), ("course", Domain::String)],
+            ),
+            relation_schema(
+                "graduates",
+                &[("student", Domain::Integer), ("name", Domain::String)],
+            ),
+        ],
+    )
+    .unwrap()
+}
+
+fn example_database() -> (SimpleSchema, HashMap<Name, SimpleRelation>) {
+    let schema = schema();
+    let mut database: HashMap<Name, SimpleRelation> = Default::default();
+    for (name, rows) in [
+        (
+            "students",
+            vec![(1i64, "alice"), (2i64, "bob"), (3i64, "carol")],
+        ),
+        (
+            "enrolled",
+            vec![(1i64, "maths"), (1i64, "physics"), (3i64, "maths")],
+        ),
+        ("graduates", vec![(3i64, "carol")]),
+    ] {
+        let name = Name::new_unchecked(name);
+        let relation = SimpleRelation::from_tuples(
+            schema.relation(&name).unwrap().clone(),
+            rows.into_iter()
+                .map(|(id, s)| SimpleTuple::from(vec![Value::from(id), Value::from(s)])),
+        )
+        .unwrap();
+        let _ = database.insert(name, relation);
+    }
+    (schema, database)
+}
+
+fn assert_optimized(optimizer: &Optimizer, query: &str, expected: &str) {
+    let optimized = optimizer
+        .optimize(&RelationalOp::from_str(query).unwrap(), &schema())
+        .unwrap();
+    println!("{} ⟶ {}", query, optimized);
+    assert_eq!(optimized, RelationalOp::from_str(expected).unwrap());
+}
+
+#[test]
+fn test_push_selection_through_natural_join() {
+    let optimizer = Optimizer::default();
+    assert_optimized(
+        &optimizer,
+        "σ[name=\"alice\" ∧ course=\"maths\"](students ⨝ enrolled)",
+        "σ[name=\"alice\"]students ⨝ σ[course=\"maths\"]enrolled",
+    );
+    // a selection on the common attribute is pushed to the left.
+    assert_optimized(
+        &optimizer,
+        "σ[id=1i64](students ⨝ enrolled)",
+        "σ[id=1i64]students ⨝ enrolled",
+    );
+    // an index past the left-hand side is mapped to the right-hand side.
+    assert_optimized(
+        &optimizer,
+        "σ[2=\"maths\"](students ⨝ enrolled)",
+        "students ⨝ σ[1=\"maths\"]enrolled",
+    );
+}
+
+#[test]
+fn test_push_selection_through_set_operation() {
+    assert_optimized(
+        &Optimizer::default(),
+        "σ[id>2i64](students ∪ ρ[student/id]graduates)",
+        "σ[id>2i64]students ∪ σ[id>2i64]ρ[student/id]graduates",
+    );
+    assert_optimized(
+        &Optimizer::default(),
+        "σ[id>2i64](students ∖ graduates)",
+        "σ[id>2i64]students ∖ σ[0>2i64]graduates",
+    );
+}
+
+#[test]
+fn test_selection_over_product() {
+    let optimizer = Optimizer::default();
+    assert_optimized(
+        &optimizer,
+        "σ[0=2 ∧ course=\"maths\"](students × enrolled)",
+        "students ⨝[0=2] σ[course=\"maths\"]enrolled",
+    );
+    assert_optimized(
+        &optimizer,
+        "σ[0=2](σ[1=3](students × enrolled))",
+        "students ⨝[1=3 ∧ 0=2] enrolled",
+    );
+}
+
+#[test]
+fn test_merge_projections_and_renames() {
+    let optimizer = Optimizer::default();
+    assert_optimized(
+        &optimizer,
+        "π[name](π[name, id]students)",
+        "π[name]students",
+    );
+    // a constant is named for its position, so it cannot be moved.
+    assert_optimized(
+        &optimizer,
+        "π[1](π[id, 42i64]students)",
+        "π[1](π[id, 42i64]students)",
+    );
+    assert_optimized(&optimizer, "π[0](π[42i64, id]students)", "π[42i64]students");
+    // attributes of a computed expression are mapped through the inner projection.
+    assert_optimized(
+        &optimizer,
+        "π[twice, 0 + 1i64/next](π[id, id × 2i64/twice]students)",
+        "π[id × 2i64/twice, id + 1i64/next]students",
+    );
+    assert_optimized(
+        &optimizer,
+        "π[_1 + 1i64](π[id, 42i64]students)",
+        "π[_1 + 1i64](π[id, 42i64]students)",
+    );
+    assert_optimized(&optimizer, "ρ[id/id, name/name]students", "students");
+    assert_optimized(
+        &optimizer,
+        "ρ[id/id, name/student]students",
+        "ρ[name/student]students",
+    );
+}
+
+#[test]
+fn test_selected_rules() {
+    let optimizer = Optimizer::new(vec![
+        Box::new(SplitConjunctiveSelection),
+        Box::new(MergeProjections),
+    ]);
+    assert_eq!(optimizer.rules().count(), 2);
+    assert_optimized(
+        &optimizer,
+        "σ[name=\"alice\" ∧ course=\"maths\"](students ⨝ enrolled)",
+        "σ[name=\"alice\"]σ[course=\"maths\"](students ⨝ enrolled)",
+    );
+
+    let optimizer = Optimizer::new(vec![Box::new(PushSelectionDown)]);
+    assert_optimized(
+        &optimizer,
+        "σ[name=\"alice\" ∧ course=\"maths\"](students ⨝ enrolled)",
+        "σ[name=\"alice\" ∧ course=\"maths\"](students ⨝ enrolled)",
+    );
+}
+
+#[test]
+fn test_optimize_expression_list() {
+    let list =
+        ExpressionList::from_str("A ≔ π[id](π[id, course]enrolled); σ[id=1i64](students ⨝ A)")
+            .unwrap();
+    let optimized = Optimizer::default()
+        .optimize_expression_list(&list, &schema())
+        .unwrap();
+    assert_eq!(
+        optimized,
+        ExpressionList::from_str("A ≔ π[id]enrolled; σ[id=1i64]students ⨝ A").unwrap()
+    );
+}
+
+fn statistics(relations: &[(&str, usize, &[usize])]) -> HashMap<Name, SimpleStatistics> {
+    relations
+        .iter()
+        .map(|(name, cardinality, distinct)| {
+            (
+                Name::new_unchecked(name),
+                SimpleStatistics::new(
+                    *cardinality,
+                    distinct
+                        .iter()
+                        .map(|d| SimpleAttributeStatistics::new(Some(*d), None, None))
+                        .collect(),
+                ),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_join_order_natural_chain() {
+    let statistics = statistics(&[
+        ("students", 100_000, &[100_000, 90_000]),
+        ("enrolled", 200_000, &[100_000, 50]),
+        ("graduates", 10, &[10, 10]),
+    ]);
+    let orderer = JoinOrderer::new(&statistics);
+    let query = RelationalOp::from_str("(enrolled ⨝ students) ⨝ ρ[student/id]graduates").unwrap();
+    let reordered = orderer.reorder(&query, &schema()).unwrap();
+    println!("{} ⟶ {}", query, reordered);
+    assert_eq!(
+        reordered,
+        RelationalOp::from_str("enrolled ⨝ (students ⨝ ρ[student/id]graduates)").unwrap()
+    );
+
+    // already the best order, so nothing changes.
+    assert_eq!(orderer.reorder(&reordered, &schema()).unwrap(), reordered);
+}
+
+#[test]
+fn test_join_order_theta_chain() {
+    let (schema, database) = example_database();
+    let statistics: HashMap<Name, SimpleStatistics> = database
+        .iter()
+        .map(|(name, relation)| (name.clone(), relation.statistics()))
+        .collect();
+    assert_eq!(
+        statistics[&Name::new_unchecked("students")].cardinality(),
+        3
+    );
+    assert_eq!(
+        statistics[&Name::new_unchecked("enrolled")].distinct_count(1),
+        Some(2)
+    );
+
+    let query =
+        RelationalOp::from_str("(students × enrolled) ⨝[0=4 ∧ 1=5] σ[name=\"carol\"]graduates")
+            .unwrap();
+    let reordered = JoinOrderer::new(&statistics)
+        .reorder(&query, &schema)
+        .unwrap();
+    println!("{} ⟶ {}", query, reordered);
+    assert_ne!(reordered, query);
+    assert_eq!(
+        analyze_relational(&reordered, &schema).unwrap(),
+        analyze_relational(&query, &schema).unwrap()
+    );
+    assert_eq!(
+        evaluate_relational(&reordered, &schema, &database)
+            .unwrap()
+            .into_parts()
+            .1,
+        evaluate_relational(&query, &schema, &database)
+            .unwrap()
+            .into_parts()
+            .1
+    );
+}
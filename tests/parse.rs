@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use relational_algebra::{
+    ast::{
+        Aggregate, AggregateFunction, Attribute, DisplayFormat, Expression, ExpressionList, Format,
+        NullOrdering, OuterJoinKind, ProjectedAttribute, RelationalOp, ScalarExpression,
+        ScalarFunction, SemiJoinKind, SetOperation, SortKey, Term,
+    },
+    data::Value,
+    error::{Error, Location, ParseError, ParseKind},
+    sort::Domain,
+    Name,
+};
+
+fn assert_round_trip(ast: RelationalOp) {
+    for fmt in [DisplayFormat::ToStringUnicode, DisplayFormat::ToStringAscii] {
+        let text = ast.to_formatted_string(fmt);
+        println!("{}", text);
+        assert_eq!(RelationalOp::from_str(&text).unwrap(), ast);
+    }
+}
+
+fn assert_term_round_trip(ast: Term) {
+    for fmt in [DisplayFormat::ToStringUnicode, DisplayFormat::ToStringAscii] {
+        let text = ast.to_formatted_string(fmt);
+        println!("{}", text);
+        assert_eq!(Term::from_str(&text).unwrap(), ast);
+    }
+}
+
+#[test]
+fn test_parse_selection() {
+    assert_eq!(
+        RelationalOp::from_str("σ[0=1]relation").unwrap(),
+        RelationalOp::select(Term::equals(0, 1), Name::new_unchecked("relation"))
+    );
+    assert_eq!(
+        RelationalOp::from_str("select[a<b]r").unwrap(),
+        RelationalOp::select(
+            Term::less_than(Name::new_unchecked("a"), Name::new_unchecked("b")),
+            Name::new_unchecked("r")
+        )
+    );
+}
+
+#[test]
+fn test_parse_named_expression() {
+    assert_eq!(
+        ExpressionList::from_str("A ≔ left ∪ right;").unwrap(),
+        ExpressionList::from(Expression::named(
+            Name::new_unchecked("A"),
+            RelationalOp::union(Name::new_unchecked("left"), Name::new_unchecked("right")),
+        ))
+    );
+    assert_eq!(
+        Expression::from_str("A := left union right").unwrap(),
+        Expression::named(
+            Name::new_unchecked("A"),
+            RelationalOp::union(Name::new_unchecked("left"), Name::new_unchecked("right")),
+        )
+    );
+}
+
+#[test]
+fn test_parse_keyword_prefixed_relation() {
+    assert_eq!(
+        RelationalOp::from_str("selection union joined").unwrap(),
+        RelationalOp::union(
+            Name::new_unchecked("selection"),
+            Name::new_unchecked("joined")
+        )
+    );
+}
+
+#[test]
+fn test_set_operation_ascii() {
+    let ast = RelationalOp::from_str("π[a]r ∖ π[a]s").unwrap();
+    let text = ast.to_formatted_string(DisplayFormat::ToStringAscii);
+    assert_eq!(text, "(project[a]r) difference (project[a]s)");
+    assert_eq!(RelationalOp::from_str(&text).unwrap(), ast);
+    assert_eq!(
+        RelationalOp::from_str("r ∪ s △ t")
+            .unwrap()
+            .to_formatted_string(DisplayFormat::ToStringAscii),
+        "(r union s) symdifference t"
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(RelationalOp::from_str("σ[0=]relation").is_err());
+    assert!(RelationalOp::from_str("left ∪").is_err());
+    assert!(RelationalOp::from_str("ρ[a, a]relation").is_err());
+    assert!(Term::from_str("a ∧").is_err());
+}
+
+#[test]
+fn test_round_trip_set_operations() {
+    let people = Name::new_unchecked("people");
+    let places = Name::new_unchecked("places");
+    assert_round_trip(RelationalOp::union(people.clone(), places.clone()));
+    assert_round_trip(RelationalOp::intersect(people.clone(), places.clone()));
+    assert_round_trip(RelationalOp::difference(people.clone(), places.clone()));
+    assert_round_trip(RelationalOp::cartesian_product(
+        people.clone(),
+        places.clone(),
+    ));
+    assert_round_trip(
+        SetOperation::symmetric_difference(
+            RelationalOp::union(people.clone(), places.clone()),
+            RelationalOp::intersect(people, places),
+        )
+        .into(),
+    );
+}
+
+#[test]
+fn test_round_trip_unary_operations() {
+    let relation = Name::new_unchecked("relation");
+    assert_round_trip(RelationalOp::project(
+        vec![
+            2.into(),
+            Name::new_unchecked("a").into(),
+            Value::from(42i64).into(),
+            Value::from(42u64).into(),
+            Value::from(1.0).into(),
+            Value::from("a \"quoted\"\tstring").into(),
+            Value::from('\'').into(),
+            Value::from(0x1fu8).into(),
+            Value::from(vec![1u8, 2, 3]).into(),
+            Value::from(true).into(),
+            Value::Null.into(),
+        ],
+        relation.clone(),
+    ));
+    assert_round_trip(
+        RelationalOp::rename_by_index(
+            vec![Name::new_unchecked("a"), Name::new_unchecked("b")],
+            relation.clone(),
+        )
+        .unwrap(),
+    );
+    assert_round_trip(
+        RelationalOp::rename(
+            [
+                (Attribute::Index(1), Name::new_unchecked("b")),
+                (Name::new_unchecked("c").into(), Name::new_unchecked("d")),
+            ]
+            .into_iter()
+            .collect::<HashMap<Attribute, Name>>(),
+            relation.clone(),
+        )
+        .unwrap(),
+    );
+    assert_round_trip(RelationalOp::sort_by(
+        vec![Name::new_unchecked("a").into(), 1.into()],
+        relation.clone(),
+    ));
+    assert_round_trip(RelationalOp::sort_by_keys(
+        vec![
+            SortKey::descending(Name::new_unchecked("a").into()).with_nulls(NullOrdering::Last),
+            SortKey::ascending(1.into()).with_collation(Name::new_unchecked("nocase")),
+        ],
+        relation.clone(),
+    ));
+    // the direction is optional, and ascending by default.
+    assert_eq!(
+        RelationalOp::from_str("τ[a, b↓]r").unwrap(),
+        RelationalOp::from_str("sort[a asc, b desc]r").unwrap()
+    );
+    assert_round_trip(RelationalOp::group_by(
+        vec![Name::new_unchecked("a").into()],
+        RelationalOp::select(
+            Term::equals(0, ProjectedAttribute::Index(1)),
+            relation.clone(),
+        ),
+    ));
+    assert_round_trip(RelationalOp::aggregate(
+        vec![Name::new_unchecked("a").into(), 2.into()],
+        vec![
+            Aggregate::count_all(),
+            Aggregate::sum(1.into()).named(Name::new_unchecked("total")),
+            Aggregate::new_distinct(
+                AggregateFunction::Count,
+                Name::new_unchecked("count").into(),
+            ),
+        ],
+        relation.clone(),
+    ));
+    assert_round_trip(RelationalOp::aggregate(
+        vec![],
+        vec![Aggregate::minimum(Name::new_unchecked("a").into())],
+        relation,
+    ));
+}
+
+#[test]
+fn test_group_errors() {
+    assert!(RelationalOp::from_str("γ[sum(*)]r").is_err());
+    assert!(RelationalOp::from_str("γ[count(*), a]r").is_err());
+    assert!(RelationalOp::from_str("γ[]r").is_err());
+}
+
+#[test]
+fn test_round_trip_joins() {
+    let left = Name::new_unchecked("left");
+    let right = Name::new_unchecked("right");
+    assert_round_trip(RelationalOp::natural_join(left.clone(), right.clone()));
+    assert_round_trip(RelationalOp::theta_join(
+        RelationalOp::natural_join(left.clone(), right.clone()),
+        Term::and(
+            Term::equals(Name::new_unchecked("a"), Name::new_unchecked("b")),
+            Term::not_equals(0, Value::from(-3i64)),
+        ),
+        RelationalOp::project(vec![Name::new_unchecked("c").into()], right.clone()),
+    ));
+    assert_round_trip(RelationalOp::outer_join(
+        RelationalOp::outer_join(left.clone(), OuterJoinKind::Left, right.clone()),
+        OuterJoinKind::Full,
+        right.clone(),
+    ));
+    assert_round_trip(RelationalOp::outer_theta_join(
+        left.clone(),
+        OuterJoinKind::Right,
+        Term::equals(0, 2),
+        right.clone(),
+    ));
+    assert_eq!(
+        RelationalOp::from_str("left ⟕[0=2] right ⟖ right").unwrap(),
+        RelationalOp::from_str("left leftjoin[0=2] right rightjoin right").unwrap()
+    );
+    assert_round_trip(RelationalOp::semi_join(
+        RelationalOp::anti_join(left.clone(), right.clone()),
+        SemiJoinKind::Right,
+        right.clone(),
+    ));
+    assert_round_trip(RelationalOp::semi_theta_join(
+        left.clone(),
+        SemiJoinKind::Left,
+        Term::equals(0, 2),
+        right.clone(),
+    ));
+    assert_eq!(
+        RelationalOp::from_str("left ⋉ right ▷[0=2] right").unwrap(),
+        RelationalOp::from_str("left leftsemijoin right antijoin[0=2] right").unwrap()
+    );
+    assert_round_trip(RelationalOp::division(
+        RelationalOp::division(left.clone(), right.clone()),
+        right.clone(),
+    ));
+    assert_eq!(
+        RelationalOp::from_str("left ÷ right").unwrap(),
+        RelationalOp::from_str("left divide right").unwrap()
+    );
+}
+
+#[test]
+fn test_round_trip_terms() {
+    let a = || Name::new_unchecked("a");
+    assert_term_round_trip(Term::and(a(), Term::and(0, 1)));
+    assert_term_round_trip(Term::and(Term::and(a(), 0), 1));
+    assert_term_round_trip(Term::or(Term::and(a(), 0), Term::or(1, 2)));
+    assert_term_round_trip(Term::and(Term::or(a(), 0), Term::or(1, 2)));
+    assert_term_round_trip(Term::or(Term::or(a(), 0), 1));
+    assert_term_round_trip(Term::and(Term::string_match(a(), Value::from("x.*")), 1).negate());
+    assert_term_round_trip(Term::string_not_match(a(), Value::from("x.*")).negate());
+    assert_term_round_trip(Term::less_than_or_equal(0, Value::from(2.5)));
+    assert_term_round_trip(Term::greater_than_or_equal(0, Value::from(2.5)));
+    assert_term_round_trip(Term::greater_than(a(), Value::from('x')));
+    assert_term_round_trip(Term::constant(true));
+    assert_term_round_trip(Term::or(Term::null(a()), Term::not_null(1).negate()));
+    assert_term_round_trip(Term::and(Term::not_null(a()), Term::constant(Value::Null)));
+}
+
+#[test]
+fn test_round_trip_typed_values() {
+    let a = || Name::new_unchecked("a");
+    for (domain, s) in [
+        (Domain::Decimal, "-12.50"),
+        (Domain::Date, "2024-02-29"),
+        (Domain::Time, "12:30:00.25"),
+        (Domain::Timestamp, "2024-02-29T12:30:00"),
+        (Domain::TimestampTz, "2024-02-29T12:30:00-05:00"),
+        (Domain::Interval, "P1Y2DT3H"),
+    ] {
+        assert_term_round_trip(Term::less_than(a(), Value::parse(domain, s).unwrap()));
+    }
+    assert_eq!(
+        Term::from_str("a<12.50dec").unwrap(),
+        Term::less_than(a(), Value::parse(Domain::Decimal, "12.50").unwrap())
+    );
+    assert!(Term::from_str("a<date\"2023-02-29\"").is_err());
+    // a domain name not followed by a string is an attribute.
+    assert_eq!(
+        Term::from_str("date=time").unwrap(),
+        Term::equals(Name::new_unchecked("date"), Name::new_unchecked("time"))
+    );
+}
+
+#[test]
+fn test_round_trip_scalar_expressions() {
+    let a = || ScalarExpression::from(Name::new_unchecked("a"));
+    let b = || ScalarExpression::from(Name::new_unchecked("b"));
+    assert_eq!(
+        Term::from_str("a + b * 2i64 >= 1").unwrap(),
+        Term::greater_than_or_equal(
+            ScalarExpression::add(a(), ScalarExpression::multiply(b(), Value::from(2i64))),
+            1
+        )
+    );
+    assert_eq!(
+        Term::from_str("a - b - 1=0").unwrap(),
+        Term::equals(
+            ScalarExpression::subtract(ScalarExpression::subtract(a(), b()), 1),
+            0
+        )
+    );
+    assert_term_round_trip(Term::less_than(
+        ScalarExpression::subtract(a(), ScalarExpression::modulo(b(), Value::from(3i64))),
+        ScalarExpression::divide(ScalarExpression::add(a(), b()), 1),
+    ));
+    assert_term_round_trip(Term::equals(
+        ScalarExpression::function(
+            ScalarFunction::Substring,
+            vec![
+                ScalarExpression::concatenate(a(), Value::from("x")),
+                Value::from(1i64).into(),
+                Value::from(2i64).into(),
+            ],
+        ),
+        ScalarExpression::case(
+            vec![(
+                Term::null(Name::new_unchecked("b")),
+                Value::from("none").into(),
+            )],
+            Some(ScalarExpression::function(ScalarFunction::Upper, vec![b()])),
+        ),
+    ));
+    assert_round_trip(RelationalOp::project(
+        vec![
+            ProjectedAttribute::computed(
+                ScalarExpression::multiply(ScalarExpression::add(a(), 1), 2),
+                Some(Name::new_unchecked("c")),
+            ),
+            ProjectedAttribute::computed(
+                ScalarExpression::function(ScalarFunction::Coalesce, vec![a(), b()]),
+                None,
+            ),
+            0.into(),
+        ],
+        Name::new_unchecked("relation"),
+    ));
+    assert!(Term::from_str("upper(a, b)=a").is_err());
+    assert!(Term::from_str("a + =1").is_err());
+}
+
+#[test]
+fn test_round_trip_membership_range_and_patterns() {
+    let a = || Name::new_unchecked("a");
+    assert_term_round_trip(Term::and(
+        Term::in_list(a(), [Value::from(1i64), Value::from(2i64)]).negate(),
+        Term::between(
+            ScalarExpression::add(a(), 1),
+            Value::from(1i64),
+            Value::from(9i64),
+        ),
+    ));
+    assert_term_round_trip(Term::or(
+        Term::like(a(), Value::from("x_%")),
+        Term::not_ilike(a(), Value::from("%Y")),
+    ));
+    assert_term_round_trip(Term::or(
+        Term::not_like(a(), Value::from("x")),
+        Term::ilike(Value::from("y"), a()),
+    ));
+    assert_eq!(
+        Term::from_str("a between 1i64 ∧ 2i64 and b in {c}").unwrap(),
+        Term::and(
+            Term::between(a(), Value::from(1i64), Value::from(2i64)),
+            Term::in_list(Name::new_unchecked("b"), [Name::new_unchecked("c")])
+        )
+    );
+    assert_eq!(
+        Term::from_str("1i64<a").unwrap(),
+        Term::less_than(Value::from(1i64), a())
+    );
+    assert!(Term::from_str("a ∈ {}").is_err());
+    assert!(Term::from_str("a between 1i64").is_err());
+}
+
+#[test]
+fn test_round_trip_expression_list() {
+    let list = ExpressionList::from(vec![
+        Expression::named(
+            Name::new_unchecked("A"),
+            RelationalOp::union(Name::new_unchecked("left"), Name::new_unchecked("right")),
+        ),
+        Expression::new(RelationalOp::select(
+            Term::exists(Name::new_unchecked("x")),
+            Name::new_unchecked("A"),
+        )),
+    ]);
+    assert_eq!(
+        ExpressionList::from_str(&format!("{}", list)).unwrap(),
+        list
+    );
+    assert_eq!(
+        ExpressionList::from_str(&format!("{:#}", list)).unwrap(),
+        list
+    );
+}
+
+fn parse_error_details(error: &Error) -> &ParseError {
+    match error {
+        Error::Parse { details } => details,
+        _ => panic!("expected a parse error, not {:?}", error),
+    }
+}
+
+#[test]
+fn test_parse_error_span() {
+    let error = RelationalOp::from_str("σ[0=]relation").unwrap_err();
+    println!("{}", error);
+    let details = parse_error_details(&error);
+    assert_eq!(details.kind(), ParseKind::Expression);
+    assert!(error
+        .to_string()
+        .starts_with("The expression could not be parsed at 1:5"));
+    assert_eq!(details.span().start, Location::new(1, 5));
+    assert_eq!(details.span().end, Location::new(1, 6));
+    assert_eq!(details.fragment().map(String::as_str), Some("]"));
+    assert!(details
+        .expected()
+        .any(|e| e == "attribute, or literal value"));
+    assert!(error.to_string().ends_with("1 | σ[0=]relation\n  |     ^"));
+}
+
+#[test]
+fn test_parse_error_second_line() {
+    let error = ExpressionList::from_str("A ≔ left ∪ right;\nB ≔ A ⨝ ;").unwrap_err();
+    println!("{}", error);
+    let details = parse_error_details(&error);
+    assert_eq!(details.span().start, Location::new(2, 9));
+    assert_eq!(details.fragment().map(String::as_str), Some(";"));
+    assert!(error.to_string().ends_with("2 | B ≔ A ⨝ ;\n  |         ^"));
+}
+
+#[test]
+fn test_parse_error_identifier_fragment() {
+    let error = RelationalOp::from_str("left ∪ right extra").unwrap_err();
+    println!("{}", error);
+    let details = parse_error_details(&error);
+    assert_eq!(details.span().start, Location::new(1, 14));
+    assert_eq!(details.span().end, Location::new(1, 19));
+    assert_eq!(details.fragment().map(String::as_str), Some("extra"));
+    assert!(details.expected().any(|e| e == "binary operator"));
+    assert!(error.to_string().ends_with("|              ^^^^^"));
+}
+
+#[test]
+fn test_parse_error_end_of_input() {
+    let error = RelationalOp::from_str("left ∪").unwrap_err();
+    println!("{}", error);
+    assert!(parse_error_details(&error).fragment().is_none());
+    assert!(error.to_string().contains("found end of input"));
+}
+
+#[test]
+fn test_parse_error_message() {
+    let error = RelationalOp::from_str("σ[a=300u8]r").unwrap_err();
+    println!("{}", error);
+    let details = parse_error_details(&error);
+    assert_eq!(details.fragment().map(String::as_str), Some("300u8"));
+    assert!(details
+        .message()
+        .unwrap()
+        .contains("invalid numeric literal"));
+}
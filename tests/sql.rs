@@ -0,0 +1,458 @@
+#![cfg(feature = "evaluation")]
+
+use std::str::FromStr;
+
+use relational_algebra::{
+    ast::{ExpressionList, RelationalOp},
+    error::{Error, ParseKind},
+    simple::sort::{SimpleAttributeSchema, SimpleRelationSchema, SimpleSchema},
+    sort::{AttributeSchema, Domain, RelationSchema, Schema},
+    sql::{
+        expression_list_to_sql,
+        parse::{sql_to_expression_list, sql_to_relational},
+        relational_to_sql,
+    },
+    Name,
+};
+
+fn relation_schema(name: &str, attributes: &[(&str, Domain)]) -> SimpleRelationSchema {
+    SimpleRelationSchema::new(
+        Name::new_unchecked(name),
+        attributes
+            .iter()
+            .map(|(name, domain)| SimpleAttributeSchema::new(Name::new_unchecked(name), *domain)),
+    )
+    .unwrap()
+}
+
+fn schema() -> SimpleSchema {
+    SimpleSchema::new(
+        Name::new_unchecked("uni"),
+        [
+            relation_schema(
+                "students",
+                &[("id", Domain::Integer), ("name", Domain::String)],
+            ),
+            relation_schema(
+                "enrolled",
+                &[("id", Domain::Integer), ("course", Domain::String)],
+            ),
+            relation_schema(
+                "courses",
+                &[("course", Domain::String), ("credits", Domain::Integer)],
+            ),
+        ],
+    )
+    .unwrap()
+}
+
+fn assert_sql(query: &str, expected: &str) {
+    let sql = relational_to_sql(&RelationalOp::from_str(query).unwrap(), &schema()).unwrap();
+    println!("{} ⟶ {}", query, sql);
+    assert_eq!(sql, expected);
+}
+
+#[test]
+fn test_unary_operators() {
+    assert_sql("students", "SELECT * FROM students");
+    assert_sql(
+        "σ[name=\"o'neil\" ∨ ¬(id>2i64)]students",
+        "SELECT * FROM students WHERE name = 'o''neil' OR NOT (id > 2)",
+    );
+    assert_sql(
+        "π[1, 42i64]students",
+        "SELECT DISTINCT name, 42 AS _1 FROM students",
+    );
+    assert_sql(
+        "π[9.90dec, date\"2024-02-29\", interval\"P1D\"]students",
+        "SELECT DISTINCT 9.90 AS _0, DATE '2024-02-29' AS _1, INTERVAL 'P1D' AS _2 FROM students",
+    );
+    assert_sql(
+        "ρ[name/student]students",
+        "SELECT id, name AS student FROM students",
+    );
+    assert_sql(
+        "π[student](τ[student]ρ[name/student]students)",
+        "SELECT DISTINCT name AS student FROM students ORDER BY name",
+    );
+    assert_sql(
+        "γ[course]enrolled",
+        "SELECT course FROM enrolled GROUP BY course",
+    );
+    assert_sql(
+        "τ[name↓ collate nocase, id nulls first]students",
+        "SELECT * FROM students ORDER BY name COLLATE nocase DESC, id NULLS FIRST",
+    );
+}
+
+#[test]
+fn test_aggregates() {
+    assert_sql(
+        "γ[course, count(*)/n, avg(distinct id)]enrolled",
+        "SELECT course, COUNT(*) AS n, AVG(DISTINCT id) AS avg_id FROM enrolled GROUP BY course",
+    );
+    assert_sql(
+        "γ[max(id)]students",
+        "SELECT MAX(id) AS max_id FROM students",
+    );
+    // a selection over a group becomes a derived table.
+    assert_sql(
+        "σ[n>1i64]γ[course, count(*)/n]enrolled",
+        "SELECT * FROM (SELECT course, COUNT(*) AS n FROM enrolled GROUP BY course) AS t1 \
+         WHERE n > 1",
+    );
+    assert_algebra(
+        "SELECT course, COUNT(*) AS n FROM enrolled GROUP BY course",
+        "γ[course, count(*)/n]enrolled",
+    );
+    assert_algebra(
+        "SELECT COUNT(DISTINCT e.id), course FROM enrolled e GROUP BY course ORDER BY 2",
+        "τ[course](π[count_id, course]γ[course, count(distinct id)]enrolled)",
+    );
+    assert!(sql_to_relational(
+        "SELECT id, COUNT(*) FROM enrolled GROUP BY course",
+        &schema()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_joins() {
+    assert_sql(
+        "students ⨝ enrolled",
+        "SELECT * FROM students NATURAL JOIN enrolled",
+    );
+    // SQL places the common column first.
+    assert_sql(
+        "enrolled ⨝ courses",
+        "SELECT enrolled.id, enrolled.course, courses.credits FROM enrolled NATURAL JOIN courses",
+    );
+    assert_sql(
+        "σ[credits>3i64](students ⨝ enrolled ⨝ courses)",
+        "SELECT students.id, students.name, enrolled.course, courses.credits \
+         FROM students NATURAL JOIN enrolled NATURAL JOIN courses WHERE courses.credits > 3",
+    );
+    assert_sql(
+        "students ⨝[0=2 ∧ course=\"maths\"] enrolled",
+        "SELECT * FROM students JOIN enrolled ON students.id = enrolled.id \
+         AND enrolled.course = 'maths'",
+    );
+    assert_sql(
+        "students ⨝ (enrolled ⨝ courses)",
+        "SELECT * FROM students NATURAL JOIN (enrolled NATURAL JOIN courses)",
+    );
+    assert_sql(
+        "students × students",
+        "SELECT * FROM students CROSS JOIN students AS t1",
+    );
+    // a renamed operand changes the common attributes, so it is a derived table.
+    assert_sql(
+        "students ⨝ ρ[course/name]enrolled",
+        "SELECT * FROM students NATURAL JOIN (SELECT id, course AS name FROM enrolled) AS t1",
+    );
+}
+
+#[test]
+fn test_outer_joins() {
+    assert_sql(
+        "students ⟕ enrolled",
+        "SELECT * FROM students NATURAL LEFT JOIN enrolled",
+    );
+    // the common column of a full join may come from either side.
+    assert_sql(
+        "enrolled ⟗ courses",
+        "SELECT enrolled.id, COALESCE(enrolled.course, courses.course) AS course, courses.credits \
+         FROM enrolled NATURAL FULL JOIN courses",
+    );
+    // a selection on either side of an outer join must be applied before the join.
+    assert_sql(
+        "σ[id>1i64]students ⟖[0=2] enrolled",
+        "SELECT * FROM (SELECT * FROM students WHERE id > 1) AS t1 RIGHT JOIN enrolled \
+         ON t1.id = enrolled.id",
+    );
+    assert_algebra(
+        "SELECT * FROM students LEFT OUTER JOIN enrolled e ON students.id = e.id",
+        "students ⟕[0=2] enrolled",
+    );
+    assert_algebra(
+        "SELECT name FROM students NATURAL FULL JOIN enrolled",
+        "π[name](students ⟗ enrolled)",
+    );
+}
+
+#[test]
+fn test_set_operations() {
+    assert_sql(
+        "π[id]students ∪ π[id]enrolled",
+        "SELECT DISTINCT id FROM students UNION SELECT DISTINCT id FROM enrolled",
+    );
+    assert_sql(
+        "π[id]students △ π[id]enrolled",
+        "(SELECT DISTINCT id FROM students EXCEPT SELECT DISTINCT id FROM enrolled) UNION \
+         (SELECT DISTINCT id FROM enrolled EXCEPT SELECT DISTINCT id FROM students)",
+    );
+    assert_sql(
+        "σ[id>1i64](students ∖ ρ[course/name]enrolled)",
+        "SELECT * FROM (SELECT * FROM students EXCEPT SELECT id, course AS name FROM enrolled) \
+         AS t1 WHERE id > 1",
+    );
+}
+
+#[test]
+fn test_expression_list_as_common_table_expressions() {
+    let list = ExpressionList::from_str(
+        "maths ≔ σ[course=\"maths\"]enrolled; \
+         unused ≔ π[name]students; \
+         students ⨝ maths; \
+         Pairs ≔ maths × maths",
+    )
+    .unwrap();
+    let statements = expression_list_to_sql(&list, &schema()).unwrap();
+    assert_eq!(
+        statements,
+        vec![
+            "WITH maths AS (SELECT * FROM enrolled WHERE course = 'maths') \
+             SELECT * FROM students NATURAL JOIN maths"
+                .to_string(),
+            "WITH maths AS (SELECT * FROM enrolled WHERE course = 'maths'), \
+             \"Pairs\" AS (SELECT maths.id, maths.course, t1.id AS id_2, t1.course AS course_2 \
+             FROM maths CROSS JOIN maths AS t1) \
+             SELECT id, course, id_2 AS id, course_2 AS course FROM \"Pairs\""
+                .to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_analysis_errors() {
+    assert!(relational_to_sql(&RelationalOp::from_str("teachers").unwrap(), &schema()).is_err());
+    assert!(relational_to_sql(
+        &RelationalOp::from_str("π[age]students").unwrap(),
+        &schema()
+    )
+    .is_err());
+}
+
+fn assert_algebra(sql: &str, expected: &str) {
+    let op = sql_to_relational(sql, &schema()).unwrap();
+    println!("{} ⟶ {}", sql, op);
+    assert_eq!(op, RelationalOp::from_str(expected).unwrap());
+}
+
+#[test]
+fn test_parse_select() {
+    assert_algebra("SELECT * FROM students", "students");
+    assert_algebra("select distinct name from students;", "π[name]students");
+    assert_algebra(
+        "SELECT s.name AS student, 42 FROM students s WHERE NOT s.id > 2 OR name = 'o''neil'",
+        "ρ[name/student](π[name, 42i64](σ[¬(id>2i64) ∨ name=\"o'neil\"]students))",
+    );
+    // a constant on the left of a comparison is moved to the right.
+    assert_algebra(
+        "SELECT * FROM students WHERE 2 < id AND name IS NOT NULL",
+        "σ[id>2i64 ∧ name is not null]students",
+    );
+    assert_algebra(
+        "SELECT * FROM students WHERE name IS NULL",
+        "σ[name is null]students",
+    );
+    assert_algebra(
+        "SELECT *, DATE '2024-02-29', timestamp with time zone '2024-02-29 12:00:00Z' FROM students",
+        "π[id, name, date\"2024-02-29\", timestamptz\"2024-02-29T12:00:00Z\"]students",
+    );
+    assert_algebra(
+        "SELECT course FROM enrolled GROUP BY course ORDER BY 1",
+        "τ[course]γ[course]enrolled",
+    );
+    assert_algebra(
+        "SELECT * FROM students ORDER BY name COLLATE nocase DESC NULLS LAST, 1 ASC",
+        "τ[name↓ collate nocase nulls last, id↑]students",
+    );
+}
+
+#[test]
+fn test_parse_joins() {
+    assert_algebra(
+        "SELECT * FROM students NATURAL JOIN enrolled",
+        "students ⨝ enrolled",
+    );
+    assert_algebra(
+        "SELECT students.name, e.course FROM students JOIN enrolled AS e ON students.id = e.id",
+        "π[name, course](students ⨝[0=2] enrolled)",
+    );
+    assert_algebra(
+        "SELECT * FROM students, enrolled WHERE students.id = enrolled.id",
+        "σ[0=2](students × enrolled)",
+    );
+    assert_algebra(
+        "SELECT t.name FROM (SELECT * FROM students WHERE id = 1) AS t CROSS JOIN courses",
+        "π[name](σ[id=1i64]students × courses)",
+    );
+}
+
+#[test]
+fn test_parse_set_operations_and_ctes() {
+    assert_algebra(
+        "SELECT id FROM students UNION SELECT id FROM enrolled INTERSECT SELECT id FROM students",
+        "π[id]students ∪ (π[id]enrolled ∩ π[id]students)",
+    );
+    assert_algebra(
+        "WITH maths AS (SELECT * FROM enrolled WHERE course = 'maths') \
+         SELECT name FROM students NATURAL JOIN maths",
+        "π[name](students ⨝ σ[course=\"maths\"]enrolled)",
+    );
+    assert_eq!(
+        sql_to_expression_list(
+            "WITH maths AS (SELECT * FROM enrolled WHERE course = 'maths') \
+             SELECT name FROM students NATURAL JOIN maths; SELECT * FROM courses",
+            &schema()
+        )
+        .unwrap(),
+        ExpressionList::from_str(
+            "maths ≔ σ[course=\"maths\"]enrolled; π[name](students ⨝ maths); courses"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_parse_round_trip() {
+    for query in [
+        "σ[name=\"o'neil\" ∨ ¬(id>2i64)]students",
+        "students ⨝ enrolled",
+        "students ⨝[0=2 ∧ course=\"maths\"] enrolled",
+        "π[id]students ∪ π[id]enrolled",
+        "σ[id ∈ {1i64, 2i64} ∧ ¬(id between 2i64 and 3i64)]students",
+        "σ[name like \"a%\" ∨ name not ilike \"%B\"]students",
+    ] {
+        let op = RelationalOp::from_str(query).unwrap();
+        let sql = relational_to_sql(&op, &schema()).unwrap();
+        assert_eq!(sql_to_relational(&sql, &schema()).unwrap(), op);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    for sql in [
+        "SELECT * FROM teachers",
+        "SELECT age FROM students",
+        "SELECT id FROM students, enrolled",
+        "SELECT * FROM students WHERE 1 = 2",
+        "SELECT id FROM students UNION ALL SELECT id FROM enrolled",
+        "SELECT * FROM students ORDER BY 3",
+        "SELECT id AS x, name AS x FROM students",
+        "SELECT * FROM select",
+        "SELECT * FROM students WHERE 'a%' LIKE name",
+        "SELECT * FROM students WHERE id IN ()",
+    ] {
+        assert!(sql_to_relational(sql, &schema()).is_err(), "{}", sql);
+    }
+    match sql_to_relational("SELECT FROM students", &schema()) {
+        Err(Error::Parse { details }) => {
+            assert_eq!(details.kind(), ParseKind::Sql);
+            assert!(details
+                .to_string()
+                .starts_with("The SQL statement could not be parsed at 1:8"));
+        }
+        result => panic!("expected a parse error, not {:?}", result),
+    }
+}
+
+#[test]
+fn test_unselected_and_ungrouped_columns() {
+    assert!(matches!(
+        sql_to_relational("SELECT name FROM students ORDER BY id", &schema()),
+        Err(Error::AttributeNotSelected { name }) if name.as_ref() == "id"
+    ));
+    assert!(matches!(
+        sql_to_relational("SELECT name FROM students ORDER BY age", &schema()),
+        Err(Error::AttributeDoesNotExist { name }) if name.as_ref() == "age"
+    ));
+    assert!(matches!(
+        sql_to_relational(
+            "SELECT course, id FROM enrolled GROUP BY course",
+            &schema()
+        ),
+        Err(Error::AttributeNotGrouped { name }) if name.as_ref() == "id"
+    ));
+    assert!(matches!(
+        sql_to_relational(
+            "SELECT course, age FROM enrolled GROUP BY course",
+            &schema()
+        ),
+        Err(Error::AttributeDoesNotExist { name }) if name.as_ref() == "age"
+    ));
+}
+
+#[test]
+fn test_semi_and_anti_joins() {
+    assert_sql(
+        "students ⋉ enrolled",
+        "SELECT * FROM students WHERE EXISTS \
+         (SELECT * FROM enrolled WHERE enrolled.id = students.id)",
+    );
+    assert_sql(
+        "students ▷[0=2 ∧ 3=\"maths\"] σ[id>1i64]enrolled",
+        "SELECT * FROM students WHERE NOT EXISTS (SELECT * FROM enrolled \
+         WHERE enrolled.id > 1 AND students.id = enrolled.id AND enrolled.course = 'maths')",
+    );
+    // the subquery is given an alias where it would hide the outer relation.
+    assert_sql(
+        "enrolled ⋊ enrolled",
+        "SELECT * FROM enrolled WHERE EXISTS (SELECT * FROM enrolled AS t1 \
+         WHERE enrolled.id = t1.id AND enrolled.course = t1.course)",
+    );
+    assert_sql(
+        "γ[id, count(*)]enrolled ⋊ π[id]students",
+        "SELECT DISTINCT id FROM students WHERE EXISTS (SELECT * FROM \
+         (SELECT id, COUNT(*) AS count FROM enrolled GROUP BY id) AS t1 WHERE students.id = t1.id)",
+    );
+}
+
+#[test]
+fn test_division() {
+    assert_sql(
+        "enrolled ÷ π[course]courses",
+        "SELECT DISTINCT id FROM enrolled WHERE NOT EXISTS (SELECT * FROM courses WHERE NOT EXISTS \
+         (SELECT * FROM enrolled AS t1 WHERE t1.id = enrolled.id AND t1.course = courses.course))",
+    );
+    assert_sql(
+        "σ[id>1i64]enrolled ÷ π[course](σ[credits>1i64]courses)",
+        "SELECT DISTINCT id FROM enrolled WHERE id > 1 AND NOT EXISTS (SELECT * FROM courses \
+         WHERE courses.credits > 1 AND NOT EXISTS (SELECT * FROM enrolled AS t1 \
+         WHERE t1.id > 1 AND t1.id = enrolled.id AND t1.course = courses.course))",
+    );
+}
+
+#[test]
+fn test_scalar_expressions() {
+    assert_sql(
+        "π[(id + 1i64) × 2i64/twice, upper(name) ‖ \"!\"](σ[id mod 2i64 = 1i64]students)",
+        "SELECT DISTINCT (id + 1) * 2 AS twice, UPPER(name) || '!' AS _1 FROM students WHERE id % 2 = 1",
+    );
+    assert_sql(
+        "π[case when id < 2i64 then \"first\" else coalesce(name, \"?\") end/label]students",
+        "SELECT DISTINCT CASE WHEN id < 2 THEN 'first' ELSE COALESCE(name, '?') END AS label FROM students",
+    );
+}
+
+#[test]
+fn test_membership_range_and_patterns() {
+    assert_sql(
+        "σ[id ∈ {1i64, 2i64} ∧ ¬(id between 2i64 and 3i64)]students",
+        "SELECT * FROM students WHERE id IN (1, 2) AND NOT (id BETWEEN 2 AND 3)",
+    );
+    assert_sql(
+        "σ[name like \"a%\" ∨ 2i64 < id]students",
+        "SELECT * FROM students WHERE name LIKE 'a%' OR 2 < id",
+    );
+    assert_eq!(
+        sql_to_relational(
+            "SELECT * FROM students WHERE id NOT IN (1, 2) AND name NOT LIKE 'a%' AND 2 < id",
+            &schema()
+        )
+        .unwrap(),
+        RelationalOp::from_str("σ[¬(id ∈ {1i64, 2i64}) ∧ name not like \"a%\" ∧ id>2i64]students")
+            .unwrap()
+    );
+}